@@ -2,6 +2,8 @@ use crate::tools::ToolType;
 use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use std::fmt::{Display, Error, Formatter};
+use std::path::PathBuf;
+use warpgate::PluginLocator;
 
 #[derive(ValueEnum, Clone, Debug, Default)]
 pub enum LogLevel {
@@ -33,6 +35,143 @@ impl Display for LogLevel {
     }
 }
 
+#[derive(ValueEnum, Clone, Debug)]
+pub enum EnvShell {
+    Bash,
+    Zsh,
+    Fish,
+    #[value(alias = "powershell")]
+    Pwsh,
+    #[value(alias = "nushell")]
+    Nu,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum MigrateSource {
+    Asdf,
+    Fnm,
+    Nvm,
+    Volta,
+}
+
+impl Display for MigrateSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(
+            f,
+            "{}",
+            match self {
+                MigrateSource::Asdf => "asdf",
+                MigrateSource::Fnm => "fnm",
+                MigrateSource::Nvm => "nvm",
+                MigrateSource::Volta => "volta",
+            }
+        )?;
+
+        Ok(())
+    }
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum SchemaKind {
+    /// The .prototools file
+    Prototools,
+    /// The ~/.proto/config.toml file
+    UserConfig,
+    /// A TOML schema plugin
+    Plugin,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum PluginsCommands {
+    #[command(
+        name = "add",
+        about = "Add a plugin to the local .prototools or global config.",
+        long_about = "Add a plugin to the .prototools in the current directory, or to ~/.proto/config.toml when --global is passed."
+    )]
+    Add {
+        #[arg(required = true, help = "ID of plugin")]
+        id: String,
+
+        #[arg(required = true, help = "Locator string to find and load the plugin")]
+        locator: PluginLocator,
+
+        #[arg(
+            long,
+            help = "Add to the global user config instead of local .prototools"
+        )]
+        global: bool,
+    },
+
+    #[command(
+        name = "info",
+        about = "Display information about a plugin.",
+        long_about = "Display the locator, cached file, last fetch time, and registered metadata of a plugin."
+    )]
+    Info {
+        #[arg(required = true, help = "ID of plugin")]
+        id: String,
+
+        #[arg(long, help = "Print the info in JSON format")]
+        json: bool,
+    },
+
+    #[command(
+        name = "refresh",
+        about = "Re-download plugins that use a latest version.",
+        long_about = "Re-download plugins that use a latest version, instead of waiting for their 7 day cache to expire.\nIf no plugin is provided, all configured plugins will be refreshed."
+    )]
+    Refresh {
+        #[arg(help = "ID of plugin")]
+        id: Option<String>,
+    },
+
+    #[command(
+        name = "remove",
+        about = "Remove a plugin from the local .prototools or global config."
+    )]
+    Remove {
+        #[arg(required = true, help = "ID of plugin")]
+        id: String,
+
+        #[arg(
+            long,
+            help = "Remove from the global user config instead of local .prototools"
+        )]
+        global: bool,
+    },
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ShimsCommands {
+    #[command(
+        name = "list",
+        alias = "ls",
+        about = "List all shims and the tools that own them."
+    )]
+    List {
+        #[arg(long, help = "Print the list in JSON format")]
+        json: bool,
+    },
+
+    #[command(
+        name = "prune",
+        about = "Remove shims for tools that are no longer installed.",
+        long_about = "Remove shims for tools that are no longer installed, and unregister shims that no longer exist."
+    )]
+    Prune,
+
+    #[command(
+        name = "regen",
+        about = "Regenerate shims for installed tools.",
+        long_about = "Regenerate global and local shims for all installed versions of a tool.\nIf no tool is provided, shims will be regenerated for all installed tools."
+    )]
+    Regen {
+        #[arg(help = "Type of tool")]
+        tool: Option<ToolType>,
+    },
+}
+
 #[derive(Debug, Parser)]
 #[command(
     name = "proto",
@@ -61,6 +200,16 @@ pub struct App {
 
 #[derive(Clone, Debug, Subcommand)]
 pub enum Commands {
+    #[command(
+        name = "activate",
+        about = "Print a shell hook that activates tools when changing directories.",
+        long_about = "Print a shell hook that activates tools when changing directories, by prepending their\ninstall directories to PATH, instead of going through shims. Add the following to your shell profile:\n\n  eval \"$(proto activate bash)\""
+    )]
+    Activate {
+        #[arg(required = true, value_enum, help = "Shell to activate")]
+        shell: EnvShell,
+    },
+
     #[command(
         name = "a",
         name = "alias",
@@ -76,6 +225,9 @@ pub enum Commands {
 
         #[arg(required = true, help = "Version (or alias) to associate with")]
         semver: String,
+
+        #[arg(long, help = "Print the aliases in JSON format")]
+        json: bool,
     },
 
     #[command(
@@ -92,16 +244,38 @@ pub enum Commands {
 
         #[arg(long, help = "Display shim path when available")]
         shim: bool,
+
+        #[arg(long, help = "Print the binary information in JSON format")]
+        json: bool,
     },
 
     #[command(
         name = "clean",
-        about = "Clean the ~/.proto directory by removing stale and old tools."
+        about = "Clean the ~/.proto directory by removing stale and old tools.",
+        long_about = "Clean the ~/.proto directory by removing stale and old tools, plugins, and temporary files.\nThe global default version is always kept."
     )]
     Clean {
         #[arg(long, help = "Clean tools older than the specified number of days")]
         days: Option<u8>,
 
+        #[arg(
+            long,
+            help = "Keep the newest N versions per major version, and remove the rest"
+        )]
+        keep_latest: Option<usize>,
+
+        #[arg(
+            long,
+            help = "Keep versions pinned in the .prototools of this project directory (can be repeated)"
+        )]
+        keep_pinned: Vec<PathBuf>,
+
+        #[arg(long, help = "List what would be removed, without removing anything")]
+        dry_run: bool,
+
+        #[arg(long, help = "Print the results in JSON format")]
+        json: bool,
+
         #[arg(long, help = "Avoid and force confirm prompts")]
         yes: bool,
     },
@@ -116,18 +290,100 @@ pub enum Commands {
     },
 
     #[command(
-        alias = "i",
-        name = "install",
-        about = "Download and install a tool.",
-        long_about = "Download and install a tool by unpacking the archive to ~/.proto/tools."
+        name = "detect",
+        about = "Detect the version of a tool for the current directory.",
+        long_about = "Detect the version of a tool for the current directory. If no tool is provided,\nall built-in tools and tools configured in .prototools will be detected."
     )]
-    Install {
+    Detect {
+        #[arg(help = "Type of tool")]
+        tool: Option<ToolType>,
+
+        #[arg(requires = "tool", help = "Version of tool")]
+        semver: Option<String>,
+
+        #[arg(long, help = "Explain each step taken to detect the version")]
+        explain: bool,
+
+        #[arg(long, help = "Print the results in JSON format")]
+        json: bool,
+    },
+
+    #[command(
+        name = "doctor",
+        about = "Diagnose and repair issues with your proto installation.",
+        long_about = "Diagnose and repair issues with your proto installation. Checks tool manifests against\nthe file system, the PATH order, shim health, and the plugin cache."
+    )]
+    Doctor {
+        #[arg(long, help = "Repair issues that can be safely fixed")]
+        fix: bool,
+    },
+
+    #[command(
+        name = "du",
+        about = "Display disk usage of installed tools, plugins, and caches.",
+        long_about = "Display disk usage of installed tools (per tool and per version), plugins, temporary files,\nand cached version manifests. Also highlights the largest versions that haven't been used recently."
+    )]
+    Du {
+        #[arg(long, help = "Print the usage in JSON format")]
+        json: bool,
+    },
+
+    #[command(
+        name = "env",
+        about = "Print environment variables for activating tools in the current directory."
+    )]
+    Env {
+        #[arg(long, value_enum, default_value = "bash", help = "Shell to format for")]
+        shell: EnvShell,
+    },
+
+    #[command(
+        name = "exec",
+        about = "Execute a command with multiple tools available on PATH.",
+        long_about = "Execute a command with multiple tools available on PATH. Each tool will be resolved (and installed\nwhen auto-install is enabled), and its bin and globals directories prepended to PATH.\nIf no tools are provided, all tools configured in .prototools will be used."
+    )]
+    Exec {
+        #[arg(help = "Tools to make available, in the format of <tool> or <tool>@<version>")]
+        tools: Vec<String>,
+
+        // Passthrough args (after --)
+        #[arg(
+            last = true,
+            required = true,
+            help = "Command and arguments to execute"
+        )]
+        command: Vec<String>,
+    },
+
+    #[command(
+        name = "info",
+        about = "Display information about an installed tool.",
+        long_about = "Display information about an installed tool, including its bin, shim, and globals paths,\nand where it was installed from. If no version is provided, it will be detected from the current environment."
+    )]
+    Info {
         #[arg(required = true, help = "Type of tool")]
         tool: ToolType,
 
-        #[arg(default_value = "latest", help = "Version of tool")]
+        #[arg(help = "Version of tool")]
         semver: Option<String>,
 
+        #[arg(long, help = "Print the information in JSON format")]
+        json: bool,
+    },
+
+    #[command(
+        alias = "i",
+        name = "install",
+        about = "Download and install one or many tools.",
+        long_about = "Download and install one or many tools by unpacking the archive to ~/.proto/tools.\nTools can be passed as <tool> <version>, or as multiple <tool>@<version> specs, which will be installed concurrently."
+    )]
+    Install {
+        #[arg(
+            required = true,
+            help = "Tools to install, in the format of <tool> <version>, or <tool>@<version>..."
+        )]
+        specs: Vec<String>,
+
         #[arg(long, help = "Pin version as the global default")]
         pin: bool,
 
@@ -172,6 +428,9 @@ pub enum Commands {
     List {
         #[arg(required = true, help = "Type of tool")]
         tool: ToolType,
+
+        #[arg(long, help = "Print the versions in JSON format")]
+        json: bool,
     },
 
     #[command(
@@ -183,6 +442,9 @@ pub enum Commands {
     ListGlobal {
         #[arg(required = true, help = "Type of tool")]
         tool: ToolType,
+
+        #[arg(long, help = "Print the globals in JSON format")]
+        json: bool,
     },
 
     #[command(
@@ -194,6 +456,18 @@ pub enum Commands {
     ListRemote {
         #[arg(required = true, help = "Type of tool")]
         tool: ToolType,
+
+        #[arg(long, help = "Include prerelease versions")]
+        include_prereleases: bool,
+
+        #[arg(
+            long,
+            help = "Only include versions greater than or equal to this version"
+        )]
+        since: Option<String>,
+
+        #[arg(long, help = "Print the versions in JSON format")]
+        json: bool,
     },
 
     #[command(
@@ -205,12 +479,44 @@ pub enum Commands {
         #[arg(required = true, help = "Type of tool")]
         tool: ToolType,
 
-        #[arg(required = true, help = "Version of tool")]
-        semver: String,
+        #[arg(required_unless_present = "unset", help = "Version of tool")]
+        semver: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with = "semver",
+            help = "Remove the local version of the tool"
+        )]
+        unset: bool,
+    },
+
+    #[command(
+        name = "migrate",
+        about = "Migrate Node.js installs from another version manager.",
+        long_about = "Migrate Node.js installs from another version manager (nvm, volta, asdf, or fnm) into ~/.proto/tools,\nand carry over its default version and aliases. Installs are copied unless --move is passed."
+    )]
+    Migrate {
+        #[arg(
+            long,
+            value_enum,
+            required = true,
+            help = "Version manager to migrate from"
+        )]
+        from: MigrateSource,
+
+        #[arg(long = "move", help = "Move installs instead of copying them")]
+        move_files: bool,
     },
 
-    #[command(name = "plugins", about = "List all active and configured plugins.")]
+    #[command(
+        name = "plugins",
+        about = "List all active and configured plugins.",
+        long_about = "List all active and configured plugins, or manage them with a subcommand."
+    )]
     Plugins {
+        #[command(subcommand)]
+        command: Option<PluginsCommands>,
+
         #[arg(long, help = "Print the list in JSON format")]
         json: bool,
     },
@@ -239,6 +545,16 @@ pub enum Commands {
         passthrough: Vec<String>,
     },
 
+    #[command(
+        name = "schema",
+        about = "Generate a JSON schema for a proto configuration file.",
+        long_about = "Generate a JSON schema for a proto configuration file, and print it to stdout.\nCan be used to validate .prototools, ~/.proto/config.toml, and TOML schema plugins in editors."
+    )]
+    Schema {
+        #[arg(value_enum, required = true, help = "Kind of schema to generate")]
+        kind: SchemaKind,
+    },
+
     #[command(name = "setup", about = "Setup proto for your current shell.")]
     Setup {
         #[arg(long, help = "Shell to setup for")]
@@ -248,6 +564,26 @@ pub enum Commands {
         profile: bool,
     },
 
+    #[command(
+        name = "shims",
+        about = "Manage the shims within ~/.proto/bin.",
+        long_about = "Manage the shims within ~/.proto/bin, and the registry that tracks which tool owns each shim."
+    )]
+    Shims {
+        #[command(subcommand)]
+        command: ShimsCommands,
+    },
+
+    #[command(
+        name = "status",
+        about = "List all tools for the current directory and their status.",
+        long_about = "List all tools that apply to the current directory, from .prototools files and the tool's ecosystem,\nalong with the requested version, the resolved version, and whether it has been installed."
+    )]
+    Status {
+        #[arg(long, help = "Print the tools in JSON format")]
+        json: bool,
+    },
+
     #[command(name = "ua", name = "unalias", about = "Remove an alias from a tool.")]
     Unalias {
         #[arg(required = true, help = "Type of tool")]
@@ -274,9 +610,21 @@ pub enum Commands {
     #[command(
         alias = "up",
         name = "upgrade",
-        about = "Upgrade proto to the latest version."
+        about = "Upgrade proto to the latest version.",
+        long_about = "Upgrade proto to the latest version, or the provided version. The download is verified\nagainst its published checksum, and the previous binary is kept so that it can be rolled back.",
+        disable_version_flag = true
     )]
-    Upgrade,
+    Upgrade {
+        #[arg(long, help = "Version of proto to upgrade (or downgrade) to")]
+        version: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with = "version",
+            help = "Rollback to the version used before the last upgrade"
+        )]
+        rollback: bool,
+    },
 
     #[command(
         alias = "u",
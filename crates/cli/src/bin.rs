@@ -5,11 +5,12 @@ mod hooks;
 mod shell;
 pub mod tools;
 
-use app::{App as CLI, Commands};
+use app::{App as CLI, Commands, PluginsCommands, ShimsCommands};
 use clap::Parser;
 use starbase::{system, tracing::TracingOptions, App, MainResult, State};
 use starbase_utils::string_vec;
 use std::env;
+use std::process;
 use tracing::metadata::LevelFilter;
 
 #[derive(State)]
@@ -18,39 +19,103 @@ pub struct CliCommand(pub Commands);
 #[system]
 async fn run(command: StateRef<CliCommand>) {
     match command.0.clone() {
+        Commands::Activate { shell } => commands::activate(shell).await?,
         Commands::Alias {
             tool,
             alias,
             semver,
-        } => commands::alias(tool, alias, semver).await?,
-        Commands::Bin { tool, semver, shim } => commands::bin(tool, semver, shim).await?,
-        Commands::Clean { days, yes } => commands::clean(days, yes).await?,
+            json,
+        } => commands::alias(tool, alias, semver, json).await?,
+        Commands::Bin {
+            tool,
+            semver,
+            shim,
+            json,
+        } => commands::bin(tool, semver, shim, json).await?,
+        Commands::Clean {
+            days,
+            keep_latest,
+            keep_pinned,
+            dry_run,
+            json,
+            yes,
+        } => {
+            commands::clean(commands::CleanOptions {
+                days,
+                keep_latest,
+                keep_pinned,
+                versions_only: false,
+                dry_run,
+                json,
+                yes,
+            })
+            .await?
+        }
         Commands::Completions { shell } => commands::completions(shell).await?,
-        Commands::Install {
+        Commands::Detect {
             tool,
             semver,
+            explain,
+            json,
+        } => commands::detect(tool, semver, explain, json).await?,
+        Commands::Doctor { fix } => commands::doctor(fix).await?,
+        Commands::Du { json } => commands::du(json).await?,
+        Commands::Env { shell } => commands::env(shell).await?,
+        Commands::Exec { tools, command } => commands::exec(tools, command).await?,
+        Commands::Info { tool, semver, json } => commands::info(tool, semver, json).await?,
+        Commands::Install {
+            specs,
             pin,
             passthrough,
-        } => commands::install(tool, semver, pin, passthrough).await?,
+        } => commands::install_many(specs, pin, passthrough).await?,
         Commands::InstallGlobal { tool, dependencies } => {
             commands::install_global(tool, dependencies).await?
         }
         Commands::Global { tool, semver } => commands::global(tool, semver).await?,
-        Commands::List { tool } => commands::list(tool).await?,
-        Commands::ListGlobal { tool } => commands::list_global(tool).await?,
-        Commands::ListRemote { tool } => commands::list_remote(tool).await?,
-        Commands::Local { tool, semver } => commands::local(tool, semver).await?,
-        Commands::Plugins { json } => commands::plugins(json).await?,
+        Commands::List { tool, json } => commands::list(tool, json).await?,
+        Commands::ListGlobal { tool, json } => commands::list_global(tool, json).await?,
+        Commands::ListRemote {
+            tool,
+            include_prereleases,
+            since,
+            json,
+        } => commands::list_remote(tool, include_prereleases, since, json).await?,
+        Commands::Local {
+            tool,
+            semver,
+            unset,
+        } => commands::local(tool, semver, unset).await?,
+        Commands::Migrate { from, move_files } => commands::migrate(from, move_files).await?,
+        Commands::Plugins { command, json } => match command {
+            Some(PluginsCommands::Add {
+                id,
+                locator,
+                global,
+            }) => commands::plugins_add(id, locator, global).await?,
+            Some(PluginsCommands::Info { id, json }) => commands::plugins_info(id, json).await?,
+            Some(PluginsCommands::Refresh { id }) => commands::plugins_refresh(id).await?,
+            Some(PluginsCommands::Remove { id, global }) => {
+                commands::plugins_remove(id, global).await?
+            }
+            None => commands::plugins(json).await?,
+        },
         Commands::Run {
             tool,
             semver,
             bin,
             passthrough,
         } => commands::run(tool, semver, bin, passthrough).await?,
+        Commands::Schema { kind } => commands::schema(kind).await?,
         Commands::Setup { shell, profile } => commands::setup(shell, profile).await?,
+        Commands::Shims { command } => match command {
+            ShimsCommands::List { json } => commands::shims_list(json).await?,
+            ShimsCommands::Prune => commands::shims_prune().await?,
+            ShimsCommands::Regen { tool } => commands::shims_regen(tool).await?,
+        },
+        Commands::Status { json } => commands::status(json).await?,
         Commands::Unalias { tool, alias } => commands::unalias(tool, alias).await?,
         Commands::Uninstall { tool, semver } => commands::uninstall(tool, semver).await?,
-        Commands::Upgrade => commands::upgrade().await?,
+        Commands::Upgrade { version, rollback } => commands::upgrade(version, rollback).await?,
         Commands::Use => commands::install_all().await?,
     };
 }
@@ -68,9 +133,21 @@ async fn main() -> MainResult {
     }
 
     App::setup_tracing_with_options(TracingOptions {
-        default_level: if matches!(cli.command, Commands::Bin { .. } | Commands::Run { .. }) {
+        default_level: if matches!(
+            cli.command,
+            Commands::Bin { .. }
+                | Commands::Detect { .. }
+                | Commands::Env { .. }
+                | Commands::Exec { .. }
+                | Commands::Info { .. }
+                | Commands::Run { .. }
+                | Commands::Status { .. }
+        ) {
             LevelFilter::WARN
-        } else if matches!(cli.command, Commands::Completions { .. }) {
+        } else if matches!(
+            cli.command,
+            Commands::Activate { .. } | Commands::Completions { .. } | Commands::Schema { .. }
+        ) {
             LevelFilter::OFF
         } else {
             LevelFilter::INFO
@@ -84,7 +161,13 @@ async fn main() -> MainResult {
     let mut app = App::new();
     app.set_state(CliCommand(cli.command));
     app.execute(run);
-    app.run().await?;
+
+    if let Err(error) = app.run().await {
+        let code = helpers::get_exit_code(&error);
+
+        eprintln!("Error: {error:?}");
+        process::exit(code);
+    }
 
     Ok(())
 }
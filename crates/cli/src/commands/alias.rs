@@ -1,9 +1,17 @@
 use crate::tools::{create_tool, ToolType};
+use miette::IntoDiagnostic;
 use proto_core::{color, is_alias_name, ProtoError};
 use starbase::SystemResult;
+use starbase_utils::json;
+use std::collections::BTreeMap;
 use tracing::info;
 
-pub async fn alias(tool_type: ToolType, alias: String, version: String) -> SystemResult {
+pub async fn alias(
+    tool_type: ToolType,
+    alias: String,
+    version: String,
+    json: bool,
+) -> SystemResult {
     if alias == version {
         return Err(ProtoError::Message("Cannot map an alias to itself.".into()))?;
     }
@@ -20,6 +28,23 @@ pub async fn alias(tool_type: ToolType, alias: String, version: String) -> Syste
     manifest.aliases.insert(alias.clone(), version.clone());
     manifest.save()?;
 
+    let aliases = BTreeMap::from_iter(manifest.aliases.clone());
+
+    if json {
+        println!(
+            "{}",
+            json::to_string_pretty(&json::json!({
+                "tool": tool.get_id(),
+                "alias": alias,
+                "version": version,
+                "aliases": aliases,
+            }))
+            .into_diagnostic()?
+        );
+
+        return Ok(());
+    }
+
     info!(
         "Added alias {} ({}) for {}",
         color::id(alias),
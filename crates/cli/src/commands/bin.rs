@@ -1,11 +1,24 @@
 use crate::tools::{create_tool, ToolType};
+use miette::IntoDiagnostic;
 use proto_core::detect_version;
+use serde::Serialize;
 use starbase::SystemResult;
+use starbase_utils::json;
+use std::path::PathBuf;
+
+#[derive(Serialize)]
+pub struct BinItem {
+    tool: String,
+    version: String,
+    path: PathBuf,
+    shim: Option<PathBuf>,
+}
 
 pub async fn bin(
     tool_type: ToolType,
     forced_version: Option<String>,
     use_shim: bool,
+    json: bool,
 ) -> SystemResult {
     let mut tool = create_tool(&tool_type).await?;
     let version = detect_version(&tool, forced_version).await?;
@@ -15,7 +28,30 @@ pub async fn bin(
 
     if use_shim {
         tool.setup_shims(true).await?;
+    }
+
+    if json {
+        // Locate existing shims without creating them
+        if !use_shim {
+            tool.create_shims(true).await?;
+        }
 
+        let item = BinItem {
+            tool: tool.get_id().to_owned(),
+            version: tool.get_resolved_version().to_owned(),
+            path: tool.get_bin_path()?.to_path_buf(),
+            shim: tool
+                .get_shim_path()
+                .filter(|path| path.exists())
+                .map(|path| path.to_path_buf()),
+        };
+
+        println!("{}", json::to_string_pretty(&item).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    if use_shim {
         if let Some(shim_path) = tool.get_shim_path() {
             println!("{}", shim_path.to_string_lossy());
 
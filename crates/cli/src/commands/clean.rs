@@ -1,56 +1,166 @@
+use crate::helpers::resolve_installed_version;
 use crate::tools::{create_tool, ToolType};
 use dialoguer::Confirm;
-use proto_core::{color, Tool, ToolsConfig};
-use rustc_hash::FxHashSet;
-use starbase::{diagnostics::IntoDiagnostic, SystemResult};
-use starbase_utils::fs;
-use std::time::SystemTime;
-use strum::IntoEnumIterator;
+use miette::IntoDiagnostic;
+use proto_core::{
+    color, get_dir_size, get_modified_time, now, Manifest, Proto, ProtoError, ShimRegistry, Tool,
+    ToolsConfig, UserConfig,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use semver::Version;
+use serde::Serialize;
+use starbase::SystemResult;
+use starbase_utils::{fs, json};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use tracing::{debug, info};
 
 fn is_older_than_days(now: u128, other: u128, days: u8) -> bool {
-    (now - other) > ((days as u128) * 24 * 60 * 60 * 1000)
+    now.saturating_sub(other) > ((days as u128) * 24 * 60 * 60 * 1000)
 }
 
-pub async fn do_clean(
-    mut tool: Box<dyn Tool<'_>>,
-    now: u128,
-    days: u8,
-    yes: bool,
-) -> miette::Result<usize> {
-    info!("Checking {}", color::shell(tool.get_name()));
+fn get_path_size(path: &Path) -> u64 {
+    if path.is_dir() {
+        get_dir_size(path)
+    } else {
+        std::fs::metadata(path).map(|meta| meta.len()).unwrap_or(0)
+    }
+}
+
+#[derive(Default)]
+pub struct CleanOptions {
+    /// Remove versions that haven't been used in this many days. Defaults to 30.
+    pub days: Option<u8>,
+    /// Keep the newest N versions per major version, and remove the rest.
+    pub keep_latest: Option<usize>,
+    /// Keep versions pinned in the `.prototools` of these directories.
+    pub keep_pinned: Vec<PathBuf>,
+    /// Only remove stale tool versions, and not plugins or temporary files.
+    pub versions_only: bool,
+    pub dry_run: bool,
+    pub json: bool,
+    pub yes: bool,
+}
+
+#[derive(Serialize)]
+pub struct CleanedVersion {
+    pub tool: String,
+    pub version: String,
+    pub reason: String,
+    pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct CleanedFile {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+#[derive(Default, Serialize)]
+pub struct CleanResult {
+    pub dry_run: bool,
+    pub versions: Vec<CleanedVersion>,
+    pub plugins: Vec<CleanedFile>,
+    pub temp: Vec<CleanedFile>,
+    pub total_size: u64,
+}
+
+/// Versions pinned by each tool, gathered from the provided project directories.
+type PinnedVersions = FxHashMap<String, FxHashSet<String>>;
+
+fn load_pinned_versions(dirs: &[PathBuf]) -> Result<PinnedVersions, ProtoError> {
+    let mut pinned = PinnedVersions::default();
+
+    for dir in dirs {
+        let config = ToolsConfig::load_upwards_from(dir)?;
 
-    if !tool.get_tool_dir().exists() {
-        debug!("Not being used, skipping");
-        return Ok(0);
+        for (id, version) in config.tools {
+            pinned.entry(id).or_default().insert(version);
+        }
+    }
+
+    Ok(pinned)
+}
+
+/// Find the newest N versions of each major version.
+fn find_latest_versions(manifest: &Manifest, count: usize) -> FxHashSet<String> {
+    let mut majors: FxHashMap<u64, Vec<Version>> = FxHashMap::default();
+
+    for version in &manifest.installed_versions {
+        if let Ok(version) = Version::parse(version) {
+            majors.entry(version.major).or_default().push(version);
+        }
     }
 
-    let manifest = tool.get_manifest()?;
-    let mut versions_to_clean = FxHashSet::default();
+    majors
+        .into_values()
+        .flat_map(|mut versions| {
+            versions.sort_by(|a, b| b.cmp(a));
+            versions.truncate(count);
+            versions
+        })
+        .map(|version| version.to_string())
+        .collect()
+}
+
+fn find_stale_versions(
+    tool_dir: &Path,
+    manifest: &Manifest,
+    pinned: Option<&FxHashSet<String>>,
+    options: &CleanOptions,
+    now: u128,
+) -> Result<Vec<(String, String)>, ProtoError> {
+    let days = options.days.unwrap_or(30);
+    let mut stale = vec![];
+    let mut keep = FxHashSet::default();
 
-    debug!("Scanning file system for stale and untracked versions");
+    debug!("Scanning file system for untracked versions");
 
-    for dir in fs::read_dir(tool.get_tool_dir())? {
-        let dir_path = dir.path();
+    for dir in fs::read_dir(tool_dir)? {
         let Ok(dir_type) = dir.file_type() else {
             continue;
         };
 
         if dir_type.is_dir() {
-            let version = fs::file_name(&dir_path);
+            let version = fs::file_name(dir.path());
 
-            if version != "globals" && !manifest.versions.contains_key(&version) {
+            if version != "globals"
+                && !version.starts_with('.')
+                && !manifest.versions.contains_key(&version)
+                && !manifest.installed_versions.contains(&version)
+            {
                 debug!("Version {} not found in manifest, removing", version);
 
-                versions_to_clean.insert(version);
+                stale.push((version, "not tracked in manifest".to_owned()));
             }
         }
     }
 
+    if let Some(default_version) = &manifest.default_version {
+        debug!("Version {} is the global default, keeping", default_version);
+
+        keep.insert(default_version.to_owned());
+    }
+
+    for pinned_version in pinned.into_iter().flatten() {
+        if let Some(version) = resolve_installed_version(manifest, pinned_version) {
+            debug!("Version {} is pinned in a project, keeping", version);
+
+            keep.insert(version);
+        }
+    }
+
+    let latest = options
+        .keep_latest
+        .map(|count| find_latest_versions(manifest, count));
+
     debug!("Comparing last used timestamps from manifest");
 
-    for (version, metadata) in &manifest.versions {
-        if versions_to_clean.contains(version) {
+    let mut versions = manifest.versions.iter().collect::<Vec<_>>();
+    versions.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (version, metadata) in versions {
+        if keep.contains(version) {
             continue;
         }
 
@@ -59,6 +169,33 @@ pub async fn do_clean(
             continue;
         }
 
+        if let Some(latest) = &latest {
+            if latest.contains(version) {
+                continue;
+            }
+
+            // Non-semantic versions (canary, nightly, etc) fall through to timestamps
+            if let Ok(semver) = Version::parse(version) {
+                debug!(
+                    "Version {} is not one of the newest {} for major {}, removing",
+                    version,
+                    options.keep_latest.unwrap_or_default(),
+                    semver.major,
+                );
+
+                stale.push((
+                    version.to_owned(),
+                    format!(
+                        "not one of the newest {} for major {}",
+                        options.keep_latest.unwrap_or_default(),
+                        semver.major
+                    ),
+                ));
+
+                continue;
+            }
+        }
+
         // None may mean a few things:
         // - It was recently installed but not used yet
         // - It was installed before we started tracking last used timestamps
@@ -70,88 +207,328 @@ pub async fn do_clean(
                     version, days
                 );
 
-                versions_to_clean.insert(version.to_owned());
+                stale.push((version.to_owned(), format!("not used in over {days} days")));
             }
         }
     }
 
-    let count = versions_to_clean.len();
-    let mut clean_count = 0;
+    Ok(stale)
+}
 
-    if count == 0 {
-        debug!("No versions to remove, continuing to next tool");
-        return Ok(0);
-    }
-
-    if yes
-        || Confirm::new()
-            .with_prompt(format!(
-                "Found {} versions, remove {}?",
-                count,
-                versions_to_clean
-                    .iter()
-                    .map(color::id)
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ))
-            .interact()
-            .into_diagnostic()?
-    {
-        for version in versions_to_clean {
-            tool.set_version(&version);
-            tool.teardown().await?;
+fn confirm(options: &CleanOptions, prompt: String) -> miette::Result<bool> {
+    if options.yes || options.dry_run {
+        return Ok(true);
+    }
+
+    Confirm::new()
+        .with_prompt(prompt)
+        .interact()
+        .into_diagnostic()
+}
+
+/// Remove a version through the tool when possible, so that its uninstall
+/// hooks run, and its shims are removed from the registry.
+async fn remove_version(
+    tool: Option<&mut Box<dyn Tool<'static>>>,
+    tool_dir: &Path,
+    version: &str,
+) -> miette::Result<()> {
+    if let Some(tool) = tool {
+        tool.set_version(version);
+
+        if tool.teardown().await? {
+            return Ok(());
         }
+    }
 
-        clean_count += count;
-    } else {
+    // Untracked versions, or tools that could not be loaded
+    let version_dir = tool_dir.join(version);
+
+    if version_dir.exists() {
+        fs::remove_dir_all(&version_dir)?;
+    }
+
+    let mut manifest = Manifest::load_from(tool_dir)?;
+    manifest.remove_version(version)?;
+
+    let mut registry = ShimRegistry::load_default()?;
+    registry.remove_local_shims(&version_dir)?;
+
+    if manifest.installed_versions.is_empty() {
+        registry.remove_tool(&fs::file_name(tool_dir))?;
+    }
+
+    Ok(())
+}
+
+pub async fn do_clean(
+    tool_dir: &Path,
+    pinned: &PinnedVersions,
+    options: &CleanOptions,
+    now: u128,
+) -> miette::Result<Vec<CleanedVersion>> {
+    let id = fs::file_name(tool_dir);
+
+    info!("Checking {}", color::shell(&id));
+
+    let manifest = Manifest::load_from(tool_dir)?;
+    let stale = find_stale_versions(tool_dir, &manifest, pinned.get(&id), options, now)?;
+
+    if stale.is_empty() {
+        debug!("No versions to remove, continuing to next tool");
+        return Ok(vec![]);
+    }
+
+    if !confirm(
+        options,
+        format!(
+            "Found {} {} versions, remove {}?",
+            stale.len(),
+            id,
+            stale
+                .iter()
+                .map(|(version, _)| color::id(version))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    )? {
         debug!("Skipping remove, continuing to next tool");
+        return Ok(vec![]);
+    }
+
+    let mut tool = None;
+
+    if !options.dry_run {
+        match ToolType::from_str(&id) {
+            Ok(tool_type) => match create_tool(&tool_type).await {
+                Ok(instance) => {
+                    tool = Some(instance);
+                }
+                Err(error) => {
+                    debug!(
+                        "Unable to load {}, removing versions directly: {}",
+                        id, error
+                    );
+                }
+            },
+            Err(error) => {
+                debug!("Unknown tool {}, removing versions directly: {}", id, error);
+            }
+        };
+    }
+
+    let mut cleaned = vec![];
+
+    for (version, reason) in stale {
+        let size = get_path_size(&tool_dir.join(&version));
+
+        if !options.dry_run {
+            remove_version(tool.as_mut(), tool_dir, &version).await?;
+        }
+
+        cleaned.push(CleanedVersion {
+            tool: id.clone(),
+            version,
+            reason,
+            size,
+        });
     }
 
-    Ok(clean_count)
+    Ok(cleaned)
 }
 
-pub async fn clean(days: Option<u8>, yes: bool) -> SystemResult {
-    let days = days.unwrap_or(30);
-    let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap()
-        .as_millis();
-    let mut clean_count = 0;
+/// Return the plugin ID from a file name in the plugins cache, which
+/// is in the format of `<id>-<hash>.wasm` or `<id>-latest-<hash>.wasm`.
+fn get_cached_plugin_id(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let (id, hash) = stem.rsplit_once('-')?;
 
-    info!("Finding tools to clean up...");
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some(id.strip_suffix("-latest").unwrap_or(id).to_owned())
+}
+
+/// Plugin IDs that are configured in the user config, the current `.prototools`,
+/// the `.prototools` of pinned directories, or are built-in.
+fn load_referenced_plugins(options: &CleanOptions) -> Result<FxHashSet<String>, ProtoError> {
+    let mut ids = FxHashSet::default();
+
+    ids.extend(UserConfig::load()?.plugins.into_keys());
+    ids.extend(ToolsConfig::builtin_plugins().into_keys());
+    ids.extend(ToolsConfig::load_upwards()?.plugins.into_keys());
+
+    for dir in &options.keep_pinned {
+        ids.extend(ToolsConfig::load_upwards_from(dir)?.plugins.into_keys());
+    }
+
+    Ok(ids)
+}
+
+fn find_stale_files<F>(dir: &Path, days: u8, now: u128, filter: F) -> Vec<CleanedFile>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut files = vec![];
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return files;
+    };
+
+    for entry in entries {
+        let path = entry.path();
 
-    for tool_type in ToolType::iter() {
-        if matches!(tool_type, ToolType::Plugin(_)) {
+        if !filter(&path) {
             continue;
         }
 
-        let tool = create_tool(&tool_type).await?;
+        if get_modified_time(&path)
+            .is_some_and(|modified| is_older_than_days(now, modified.as_millis(), days))
+        {
+            files.push(CleanedFile {
+                size: get_path_size(&path),
+                path,
+            });
+        }
+    }
 
-        if matches!(tool_type, ToolType::Rust) {
-            info!(
-                "Skipping {}, use rustup instead",
-                color::shell(tool.get_name())
-            );
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
 
-            continue;
+fn remove_files(
+    label: &str,
+    files: Vec<CleanedFile>,
+    options: &CleanOptions,
+) -> miette::Result<Vec<CleanedFile>> {
+    if files.is_empty()
+        || !confirm(
+            options,
+            format!("Found {} stale {label}, remove?", files.len()),
+        )?
+    {
+        return Ok(vec![]);
+    }
+
+    if !options.dry_run {
+        for file in &files {
+            if file.path.is_dir() {
+                fs::remove_dir_all(&file.path)?;
+            } else {
+                fs::remove_file(&file.path)?;
+            }
         }
+    }
+
+    Ok(files)
+}
 
-        clean_count += do_clean(tool, now, days, yes).await?;
+pub async fn clean(options: CleanOptions) -> SystemResult {
+    if options.json && !options.yes && !options.dry_run {
+        return Err(ProtoError::Message(
+            "The --json option requires --yes or --dry-run, as confirm prompts cannot be displayed."
+                .into(),
+        ))?;
     }
 
-    info!("Finding plugins to clean up...");
+    let proto = Proto::new()?;
+    let days = options.days.unwrap_or(30);
+    let now = now();
+    let pinned = load_pinned_versions(&options.keep_pinned)?;
+    let mut result = CleanResult {
+        dry_run: options.dry_run,
+        ..CleanResult::default()
+    };
+
+    info!("Finding tools to clean up...");
+
+    if proto.tools_dir.exists() {
+        let mut tool_dirs = fs::read_dir(&proto.tools_dir)?
+            .into_iter()
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
 
-    let tools_config = ToolsConfig::load_upwards()?;
+        tool_dirs.sort();
+
+        for tool_dir in tool_dirs {
+            // Rust installs are managed by rustup, outside of our tools directory
+            if tool_dir.ends_with("rust") {
+                info!("Skipping {}, use rustup instead", color::shell("rust"));
+                continue;
+            }
 
-    if !tools_config.plugins.is_empty() {
-        for plugin_name in tools_config.plugins.keys() {
-            let tool = create_tool(&ToolType::Plugin(plugin_name.to_owned())).await?;
-            clean_count += do_clean(tool, now, days, yes).await?;
+            result
+                .versions
+                .extend(do_clean(&tool_dir, &pinned, &options, now).await?);
         }
     }
 
-    if clean_count > 0 {
-        info!("Successfully cleaned up {} versions", clean_count);
+    if !options.versions_only {
+        info!("Finding plugins and temporary files to clean up...");
+
+        let referenced_plugins = load_referenced_plugins(&options)?;
+
+        result.plugins = remove_files(
+            "plugins",
+            find_stale_files(&proto.plugins_dir, days, now, |path| {
+                path.extension().is_some_and(|ext| ext == "wasm")
+                    && !get_cached_plugin_id(path)
+                        .is_some_and(|id| referenced_plugins.contains(&id))
+            }),
+            &options,
+        )?;
+
+        result.temp = remove_files(
+            "temporary files",
+            find_stale_files(&proto.temp_dir, days, now, |_| true),
+            &options,
+        )?;
+    }
+
+    result.total_size = result.versions.iter().map(|item| item.size).sum::<u64>()
+        + result.plugins.iter().map(|item| item.size).sum::<u64>()
+        + result.temp.iter().map(|item| item.size).sum::<u64>();
+
+    if options.json {
+        println!("{}", json::to_string_pretty(&result).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    let action = if options.dry_run {
+        "Would remove"
+    } else {
+        "Removed"
+    };
+
+    for item in &result.versions {
+        info!(
+            "{action} {} {} ({})",
+            color::id(&item.tool),
+            color::hash(&item.version),
+            item.reason
+        );
+    }
+
+    for item in result.plugins.iter().chain(&result.temp) {
+        info!("{action} {}", color::path(&item.path));
+    }
+
+    if !result.versions.is_empty() || !result.plugins.is_empty() || !result.temp.is_empty() {
+        info!(
+            "{} {} versions, {} plugins, and {} temporary files ({})",
+            if options.dry_run {
+                "Would clean up"
+            } else {
+                "Successfully cleaned up"
+            },
+            result.versions.len(),
+            result.plugins.len(),
+            result.temp.len(),
+            indicatif::HumanBytes(result.total_size),
+        );
     }
 
     Ok(())
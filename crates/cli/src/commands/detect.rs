@@ -0,0 +1,208 @@
+use crate::tools::{create_tool, ToolType};
+use miette::IntoDiagnostic;
+use proto_core::{
+    color, detect_version_with_trace, DetectStep, DetectTrace, ProtoError, ToolsConfig,
+};
+use starbase::SystemResult;
+use starbase_utils::json;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use tracing::debug;
+
+fn render_step(index: usize, step: &DetectStep) {
+    let (label, message) = match step {
+        DetectStep::CliArgument { version } => (
+            "Argument",
+            match version {
+                Some(version) => format!("using version {}", color::id(version)),
+                None => "no version provided".into(),
+            },
+        ),
+        DetectStep::EnvVar { name, version } => (
+            "Env var",
+            match version {
+                Some(version) => {
+                    format!("{} is set to {}", color::symbol(name), color::id(version))
+                }
+                None => format!("{} is not set", color::symbol(name)),
+            },
+        ),
+        DetectStep::Directory { dir } => ("Directory", format!("checking {}", color::path(dir))),
+        DetectStep::ConfigFile { file, version } => (
+            "Config",
+            match version {
+                Some(version) => format!("{} matched {}", color::path(file), color::id(version)),
+                None => format!("{} has no version", color::path(file)),
+            },
+        ),
+        DetectStep::EcosystemFile {
+            file,
+            version,
+            expanded,
+        } => (
+            "Ecosystem",
+            match expanded {
+                Some(expanded) => format!(
+                    "{} matched {}, expanded to {}",
+                    color::path(file),
+                    color::id(version),
+                    color::id(expanded)
+                ),
+                None => format!(
+                    "{} matched {}, but no installed version satisfies it",
+                    color::path(file),
+                    color::id(version)
+                ),
+            },
+        ),
+        DetectStep::GlobalDefault { file, version } => (
+            "Global",
+            match version {
+                Some(version) => {
+                    format!("{} defaults to {}", color::path(file), color::id(version))
+                }
+                None => format!("{} has no default version", color::path(file)),
+            },
+        ),
+    };
+
+    println!(
+        "  {} {} {}",
+        color::muted(format!("{}.", index + 1)),
+        color::muted_light(format!("{label}:")),
+        message
+    );
+}
+
+fn render_trace(trace: &DetectTrace, explain: bool) {
+    if let Some(error) = &trace.error {
+        println!(
+            "{} {}",
+            color::id(&trace.tool),
+            color::failure(format!("Failed to detect: {error}"))
+        );
+
+        if explain {
+            println!();
+        }
+
+        return;
+    }
+
+    if !explain {
+        if let Some(version) = &trace.version {
+            println!("{} {}", color::id(&trace.tool), version);
+        }
+
+        return;
+    }
+
+    println!("{}", color::id(&trace.tool));
+
+    for (index, step) in trace.steps.iter().enumerate() {
+        render_step(index, step);
+    }
+
+    match &trace.version {
+        Some(version) => println!("  Detected version {}", color::id(version)),
+        None => println!("  {}", color::failure("No version detected")),
+    };
+
+    println!();
+}
+
+fn create_failed_trace(tool_id: &str, error: ProtoError) -> DetectTrace {
+    DetectTrace {
+        tool: tool_id.to_owned(),
+        error: Some(error.to_string()),
+        ..DetectTrace::default()
+    }
+}
+
+pub async fn detect(
+    tool_type: Option<ToolType>,
+    forced_version: Option<String>,
+    explain: bool,
+    json: bool,
+) -> SystemResult {
+    let mut traces = vec![];
+
+    if let Some(tool_type) = tool_type {
+        let tool = create_tool(&tool_type).await?;
+        let trace = detect_version_with_trace(&tool, forced_version).await?;
+
+        // Match the behavior of `run` and `bin` when a single tool fails
+        if trace.version.is_none() && !explain && !json {
+            return Err(ProtoError::VersionDetectFailed(trace.tool))?;
+        }
+
+        traces.push(trace);
+    } else {
+        let tools_config = ToolsConfig::load_upwards()?;
+        let mut tool_types = ToolType::iter()
+            .filter(|tool_type| !matches!(tool_type, ToolType::Plugin(_)))
+            .map(|tool_type| (format!("{tool_type:?}").to_lowercase(), tool_type))
+            .collect::<Vec<_>>();
+
+        for tool_id in tools_config.tools.keys().chain(tools_config.plugins.keys()) {
+            match ToolType::from_str(tool_id) {
+                Ok(tool_type) => {
+                    if !tool_types
+                        .iter()
+                        .any(|(_, existing)| existing == &tool_type)
+                    {
+                        tool_types.push((tool_id.to_owned(), tool_type));
+                    }
+                }
+                Err(error) => {
+                    traces.push(create_failed_trace(tool_id, error));
+                }
+            };
+        }
+
+        // A single tool failing (like a plugin that can't be loaded)
+        // should not prevent detecting the other tools
+        for (tool_id, tool_type) in tool_types {
+            debug!("Detecting version for {:?}", tool_type);
+
+            let trace = match create_tool(&tool_type).await {
+                Ok(tool) => detect_version_with_trace(&tool, None).await,
+                Err(error) => Err(error),
+            };
+
+            traces.push(match trace {
+                Ok(trace) => trace,
+                Err(error) => {
+                    debug!(tool = &tool_id, "Failed to detect version: {}", error);
+
+                    create_failed_trace(&tool_id, error)
+                }
+            });
+        }
+
+        traces.sort_by(|a, d| a.tool.cmp(&d.tool));
+    }
+
+    if json {
+        println!("{}", json::to_string_pretty(&traces).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    // When not explaining a single tool, only print the version
+    if let [trace] = traces.as_slice() {
+        if !explain {
+            if let Some(version) = &trace.version {
+                println!("{version}");
+            }
+
+            return Ok(());
+        }
+    }
+
+    for trace in &traces {
+        render_trace(trace, explain);
+    }
+
+    Ok(())
+}
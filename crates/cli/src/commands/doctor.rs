@@ -0,0 +1,490 @@
+use crate::tools::{create_tool, ToolType};
+use proto_core::{color, Manifest, Proto, ProtoError, ShimRegistry, SHIM_VERSION};
+use proto_schema_plugin::Schema;
+use starbase::SystemResult;
+use starbase_utils::{fs, toml};
+use std::env;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::debug;
+
+const WASM_MAGIC: &[u8] = b"\0asm";
+
+enum Fix {
+    PruneManifest { manifest: PathBuf, version: String },
+    RegenerateShims { tool: String, version: String },
+    RemoveDir(PathBuf),
+    RemoveFile(PathBuf),
+    UpdatePerms(PathBuf),
+}
+
+struct Finding {
+    message: String,
+    hint: Option<String>,
+    fix: Option<Fix>,
+}
+
+impl Finding {
+    fn new(message: String) -> Self {
+        Finding {
+            message,
+            hint: None,
+            fix: None,
+        }
+    }
+
+    fn with_hint(mut self, hint: String) -> Self {
+        self.hint = Some(hint);
+        self
+    }
+
+    fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+fn is_proto_bin(name: &str) -> bool {
+    matches!(
+        name,
+        "proto"
+            | "proto.exe"
+            | "proto-old"
+            | "proto-old.exe"
+            | "proto-shim"
+            | "proto-shim.exe"
+            | "proto-shim-old"
+            | "proto-shim-old.exe"
+    )
+}
+
+fn read_dir_names(dir: &Path, want_dirs: bool) -> Result<Vec<String>, ProtoError> {
+    let mut names = vec![];
+
+    if !dir.exists() {
+        return Ok(names);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() == want_dirs {
+            names.push(fs::file_name(entry.path()));
+        }
+    }
+
+    names.sort();
+
+    Ok(names)
+}
+
+fn check_tools(proto: &Proto) -> Result<Vec<Finding>, ProtoError> {
+    let mut findings = vec![];
+
+    for id in read_dir_names(&proto.tools_dir, true)? {
+        // Rust installs are managed by rustup, outside of our tools directory
+        if id == "rust" {
+            continue;
+        }
+
+        let tool_dir = proto.tools_dir.join(&id);
+
+        let manifest = match Manifest::load_from(&tool_dir) {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                findings.push(
+                    Finding::new(format!(
+                        "{} manifest could not be read: {error}",
+                        color::id(&id)
+                    ))
+                    .with_hint(format!(
+                        "Inspect or delete {}, and reinstall the tool",
+                        color::path(tool_dir.join("manifest.json"))
+                    )),
+                );
+
+                continue;
+            }
+        };
+
+        // Versions in the manifest that no longer exist on disk
+        let mut stale_versions = manifest
+            .installed_versions
+            .iter()
+            .chain(manifest.versions.keys())
+            .filter(|version| !tool_dir.join(version).exists())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        stale_versions.sort();
+        stale_versions.dedup();
+
+        for version in &stale_versions {
+            findings.push(
+                Finding::new(format!(
+                    "{} {} is recorded in the manifest, but its directory is missing",
+                    color::id(&id),
+                    color::hash(version)
+                ))
+                .with_fix(Fix::PruneManifest {
+                    manifest: manifest.path.clone(),
+                    version: version.to_owned(),
+                }),
+            );
+        }
+
+        // Versions on disk that are not tracked in the manifest
+        for version in read_dir_names(&tool_dir, true)? {
+            if version == "globals"
+                || version.starts_with('.')
+                || manifest.installed_versions.contains(&version)
+            {
+                continue;
+            }
+
+            let version_dir = tool_dir.join(&version);
+            let finding = Finding::new(format!(
+                "{} {} exists on disk, but is not recorded in the manifest",
+                color::id(&id),
+                color::hash(&version)
+            ));
+
+            findings.push(if fs::read_dir(&version_dir)?.is_empty() {
+                finding.with_fix(Fix::RemoveDir(version_dir))
+            } else {
+                finding.with_hint(format!(
+                    "Run {} to repair it, or {} to remove it",
+                    color::shell(format!("proto install {id} {version}")),
+                    color::shell(format!("proto uninstall {id} {version}"))
+                ))
+            });
+        }
+
+        // Shims created by an older version of proto
+        let installed_versions = manifest
+            .installed_versions
+            .iter()
+            .filter(|version| !stale_versions.contains(version))
+            .collect::<Vec<_>>();
+
+        if !installed_versions.is_empty() && manifest.shim_version != SHIM_VERSION {
+            let version = manifest
+                .default_version
+                .as_ref()
+                .filter(|default| installed_versions.contains(default))
+                .or_else(|| installed_versions.iter().max().copied())
+                .unwrap();
+
+            findings.push(
+                Finding::new(format!(
+                    "{} shims are outdated (version {}, expected {})",
+                    color::id(&id),
+                    manifest.shim_version,
+                    SHIM_VERSION
+                ))
+                .with_fix(Fix::RegenerateShims {
+                    tool: id.clone(),
+                    version: version.to_owned(),
+                }),
+            );
+        }
+    }
+
+    Ok(findings)
+}
+
+fn check_path(proto: &Proto) -> Result<Vec<Finding>, ProtoError> {
+    let mut findings = vec![];
+    let paths = env::var_os("PATH")
+        .map(|path| env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let Some(bin_index) = paths.iter().position(|path| path == &proto.bin_dir) else {
+        findings.push(
+            Finding::new(format!(
+                "{} is not in {}",
+                color::path(&proto.bin_dir),
+                color::symbol("PATH")
+            ))
+            .with_hint(format!(
+                "Run {} to update your shell profile",
+                color::shell("proto setup")
+            )),
+        );
+
+        return Ok(findings);
+    };
+
+    for shim in read_dir_names(&proto.bin_dir, false)? {
+        if is_proto_bin(&shim) {
+            continue;
+        }
+
+        let bin = shim
+            .strip_suffix(".cmd")
+            .or_else(|| shim.strip_suffix(".ps1"))
+            .unwrap_or(&shim);
+
+        let candidates = if cfg!(windows) {
+            vec![
+                format!("{bin}.exe"),
+                format!("{bin}.cmd"),
+                format!("{bin}.bat"),
+            ]
+        } else {
+            vec![bin.to_owned()]
+        };
+
+        let shadow = paths[0..bin_index].iter().find_map(|dir| {
+            candidates
+                .iter()
+                .map(|candidate| dir.join(candidate))
+                .find(|path| path.is_file())
+        });
+
+        if let Some(shadow) = shadow {
+            findings.push(
+                Finding::new(format!(
+                    "{} is shadowed by {}, which appears earlier in {}",
+                    color::shell(bin),
+                    color::path(shadow),
+                    color::symbol("PATH")
+                ))
+                .with_hint(format!(
+                    "Move {} before other directories in your shell profile",
+                    color::path(&proto.bin_dir)
+                )),
+            );
+        }
+    }
+
+    Ok(findings)
+}
+
+fn check_shims(proto: &Proto) -> Result<Vec<Finding>, ProtoError> {
+    let registry = ShimRegistry::load_from(&proto.root)?;
+    let mut findings = vec![];
+
+    for file_name in read_dir_names(&proto.bin_dir, false)? {
+        let shim_path = proto.bin_dir.join(&file_name);
+
+        if is_proto_bin(&file_name) {
+            continue;
+        }
+
+        // Native shims are tracked in the registry, while script
+        // shims execute `proto run <tool>`
+        let registered_tool = shim_path
+            .file_stem()
+            .and_then(|name| registry.shims.get(name.to_string_lossy().as_ref()))
+            .map(|entry| entry.tool.clone());
+
+        let Some(tool) = registered_tool.or_else(|| {
+            let contents = fs::read_file(&shim_path).ok()?;
+
+            contents
+                .split("proto run ")
+                .nth(1)
+                .or_else(|| contents.split("proto.exe run ").nth(1))
+                .and_then(|rest| rest.split_whitespace().next())
+                .map(|tool| tool.to_owned())
+        }) else {
+            debug!(shim = ?shim_path, "Not a proto shim, skipping");
+            continue;
+        };
+
+        let manifest = match Manifest::load_from(proto.tools_dir.join(&tool)) {
+            Ok(manifest) => manifest,
+            Err(error) => {
+                findings.push(Finding::new(format!(
+                    "Shim {} points to {}, whose manifest could not be read: {error}",
+                    color::path(&shim_path),
+                    color::id(tool)
+                )));
+
+                continue;
+            }
+        };
+
+        if manifest.installed_versions.is_empty() {
+            findings.push(
+                Finding::new(format!(
+                    "Shim {} points to {}, which has no installed versions",
+                    color::path(&shim_path),
+                    color::id(tool)
+                ))
+                .with_fix(Fix::RemoveFile(shim_path)),
+            );
+
+            continue;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            if let Ok(metadata) = std::fs::metadata(&shim_path) {
+                if metadata.permissions().mode() & 0o111 == 0 {
+                    findings.push(
+                        Finding::new(format!(
+                            "Shim {} is not executable",
+                            color::path(&shim_path)
+                        ))
+                        .with_fix(Fix::UpdatePerms(shim_path)),
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+fn check_plugins(proto: &Proto) -> Result<Vec<Finding>, ProtoError> {
+    let mut findings = vec![];
+
+    for file_name in read_dir_names(&proto.plugins_dir, false)? {
+        let plugin_path = proto.plugins_dir.join(&file_name);
+
+        let is_valid = if file_name.ends_with(".wasm") {
+            let mut magic = [0; 4];
+
+            fs::open_file(&plugin_path)?
+                .read_exact(&mut magic)
+                .map(|_| magic == WASM_MAGIC)
+                .unwrap_or(false)
+        } else if file_name.ends_with(".toml") {
+            toml::read_file::<_, Schema>(&plugin_path).is_ok()
+        } else {
+            continue;
+        };
+
+        if !is_valid {
+            findings.push(
+                Finding::new(format!(
+                    "Cached plugin {} is corrupted",
+                    color::path(&plugin_path)
+                ))
+                .with_hint("It will be downloaded again when next used".into())
+                .with_fix(Fix::RemoveFile(plugin_path)),
+            );
+        }
+    }
+
+    Ok(findings)
+}
+
+async fn apply_fix(fix: &Fix) -> miette::Result<()> {
+    match fix {
+        Fix::PruneManifest { manifest, version } => {
+            let mut manifest = Manifest::load(manifest)?;
+
+            manifest.installed_versions.remove(version);
+            manifest.versions.remove(version);
+
+            if manifest.default_version.as_ref() == Some(version) {
+                manifest.default_version = None;
+            }
+
+            manifest.save()?;
+        }
+        Fix::RegenerateShims { tool, version } => {
+            let mut tool = create_tool(&ToolType::from_str(tool)?).await?;
+
+            tool.set_version(version);
+            tool.find_bin_path().await?;
+            tool.setup_shims(true).await?;
+        }
+        Fix::RemoveDir(dir) => {
+            fs::remove_dir_all(dir)?;
+        }
+        Fix::RemoveFile(file) => {
+            fs::remove_file(file)?;
+        }
+        Fix::UpdatePerms(file) => {
+            fs::update_perms(file, None)?;
+        }
+    };
+
+    Ok(())
+}
+
+pub async fn doctor(fix: bool) -> SystemResult {
+    let proto = Proto::new()?;
+    let checks = [
+        ("Tools", check_tools(&proto)?),
+        ("PATH", check_path(&proto)?),
+        ("Shims", check_shims(&proto)?),
+        ("Plugins", check_plugins(&proto)?),
+    ];
+
+    let mut issues = 0;
+    let mut fixed = 0;
+
+    for (label, findings) in checks {
+        println!("{}", color::label(label));
+
+        if findings.is_empty() {
+            println!("  {} No issues found", color::success("✔"));
+            println!();
+            continue;
+        }
+
+        for finding in findings {
+            let mut resolved = false;
+
+            if let (true, Some(fix)) = (fix, &finding.fix) {
+                match apply_fix(fix).await {
+                    Ok(_) => {
+                        resolved = true;
+                    }
+                    Err(error) => {
+                        debug!("Failed to apply fix: {error}");
+                    }
+                };
+            }
+
+            if resolved {
+                fixed += 1;
+                println!("  {} {}", color::success("✔"), finding.message);
+                println!("    {}", color::muted_light("Fixed"));
+                continue;
+            }
+
+            issues += 1;
+            println!("  {} {}", color::failure("✘"), finding.message);
+
+            if let Some(hint) = &finding.hint {
+                println!("    {}", color::muted_light(hint));
+            }
+
+            if finding.fix.is_some() {
+                println!(
+                    "    {}",
+                    color::muted_light(if fix {
+                        "Unable to fix automatically".to_owned()
+                    } else {
+                        format!("Run {} to repair", color::shell("proto doctor --fix"))
+                    })
+                );
+            }
+        }
+
+        println!();
+    }
+
+    if fixed > 0 {
+        println!("Fixed {} issue(s)", fixed);
+    }
+
+    if issues > 0 {
+        return Err(ProtoError::DoctorIssuesFound(issues))?;
+    }
+
+    println!("No issues found with your proto installation");
+
+    Ok(())
+}
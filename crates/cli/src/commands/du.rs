@@ -0,0 +1,301 @@
+use crate::helpers::format_time_ago;
+use indicatif::HumanBytes;
+use miette::IntoDiagnostic;
+use proto_core::{color, get_dir_size, now, Manifest, Proto};
+use serde::Serialize;
+use starbase::SystemResult;
+use starbase_utils::{fs, json};
+use std::path::Path;
+use tracing::debug;
+
+// Versions that haven't been used in this many days are considered unused
+const UNUSED_DAYS: u128 = 30;
+
+const MAX_UNUSED_ITEMS: usize = 10;
+
+#[derive(Serialize)]
+pub struct VersionUsage {
+    version: String,
+    size: u64,
+    default: bool,
+    installed_at: Option<u128>,
+    last_used_at: Option<u128>,
+}
+
+#[derive(Serialize)]
+pub struct ToolUsage {
+    id: String,
+    size: u64,
+    globals_size: u64,
+    versions: Vec<VersionUsage>,
+}
+
+#[derive(Serialize)]
+pub struct UnusedVersion {
+    tool: String,
+    version: String,
+    size: u64,
+    last_used_at: Option<u128>,
+}
+
+#[derive(Serialize)]
+pub struct DiskUsage {
+    tools: Vec<ToolUsage>,
+    tools_size: u64,
+    plugins_size: u64,
+    temp_size: u64,
+    versions_cache_size: u64,
+    total_size: u64,
+    unused: Vec<UnusedVersion>,
+}
+
+fn is_unused(now: u128, version: &VersionUsage) -> bool {
+    let threshold = UNUSED_DAYS * 24 * 60 * 60 * 1000;
+
+    if version.default {
+        return false;
+    }
+
+    // Never used, so fallback to when it was installed, to avoid
+    // flagging versions that were installed moments ago
+    match version.last_used_at.or(version.installed_at) {
+        Some(timestamp) => now.saturating_sub(timestamp) > threshold,
+        None => true,
+    }
+}
+
+fn format_last_used(last_used_at: Option<u128>) -> String {
+    match last_used_at {
+        Some(timestamp) => format!("last used {}", format_time_ago(timestamp)),
+        None => "never used".into(),
+    }
+}
+
+fn scan_tool(tool_dir: &Path) -> miette::Result<ToolUsage> {
+    let id = fs::file_name(tool_dir);
+    let manifest = Manifest::load_from(tool_dir)?;
+    let mut usage = ToolUsage {
+        id,
+        size: 0,
+        globals_size: 0,
+        versions: vec![],
+    };
+
+    for entry in fs::read_dir(tool_dir)? {
+        let path = entry.path();
+
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        let size = if file_type.is_dir() {
+            get_dir_size(&path)
+        } else {
+            entry.metadata().map(|meta| meta.len()).unwrap_or(0)
+        };
+
+        usage.size += size;
+
+        if !file_type.is_dir() {
+            continue;
+        }
+
+        let version = fs::file_name(&path);
+
+        if version == "globals" {
+            usage.globals_size = size;
+            continue;
+        }
+
+        let metadata = manifest.versions.get(&version);
+
+        usage.versions.push(VersionUsage {
+            default: manifest.default_version.as_ref() == Some(&version),
+            installed_at: metadata
+                .map(|meta| meta.installed_at)
+                .filter(|timestamp| *timestamp > 0),
+            last_used_at: metadata.and_then(|meta| meta.last_used_at),
+            version,
+            size,
+        });
+    }
+
+    usage.versions.sort_by(|a, b| b.size.cmp(&a.size));
+
+    Ok(usage)
+}
+
+fn scan_temp(temp_dir: &Path) -> miette::Result<(u64, u64)> {
+    let mut temp_size = 0;
+    let mut versions_cache_size = 0;
+
+    if !temp_dir.exists() {
+        return Ok((temp_size, versions_cache_size));
+    }
+
+    for entry in fs::read_dir(temp_dir)? {
+        let path = entry.path();
+
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            temp_size += get_dir_size(&path);
+            continue;
+        }
+
+        let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+
+        // Remote versions manifests are cached as JSON files (keyed by URL hash)
+        if path.extension().is_some_and(|ext| ext == "json") {
+            versions_cache_size += size;
+        } else {
+            temp_size += size;
+        }
+    }
+
+    Ok((temp_size, versions_cache_size))
+}
+
+pub async fn du(json: bool) -> SystemResult {
+    let proto = Proto::new()?;
+    let now = now();
+
+    debug!("Calculating disk usage of {}", color::path(&proto.root));
+
+    let mut tools = vec![];
+
+    if proto.tools_dir.exists() {
+        for entry in fs::read_dir(&proto.tools_dir)? {
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                tools.push(scan_tool(&entry.path())?);
+            }
+        }
+    }
+
+    tools.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.id.cmp(&b.id)));
+
+    let mut unused = tools
+        .iter()
+        .flat_map(|tool| {
+            tool.versions
+                .iter()
+                .filter(|version| is_unused(now, version))
+                .map(|version| UnusedVersion {
+                    tool: tool.id.clone(),
+                    version: version.version.clone(),
+                    size: version.size,
+                    last_used_at: version.last_used_at,
+                })
+        })
+        .collect::<Vec<_>>();
+
+    unused.sort_by(|a, b| b.size.cmp(&a.size));
+    unused.truncate(MAX_UNUSED_ITEMS);
+
+    let tools_size = tools.iter().map(|tool| tool.size).sum::<u64>();
+    let plugins_size = get_dir_size(&proto.plugins_dir);
+    let (temp_size, versions_cache_size) = scan_temp(&proto.temp_dir)?;
+
+    let usage = DiskUsage {
+        tools,
+        tools_size,
+        plugins_size,
+        temp_size,
+        versions_cache_size,
+        total_size: tools_size + plugins_size + temp_size + versions_cache_size,
+        unused,
+    };
+
+    if json {
+        println!("{}", json::to_string_pretty(&usage).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    println!("{}", color::label("Tools"));
+
+    if usage.tools.is_empty() {
+        println!("  {}", color::muted_light("No tools installed"));
+    }
+
+    for tool in &usage.tools {
+        println!(
+            "  {} {}",
+            color::id(&tool.id),
+            color::muted_light(HumanBytes(tool.size).to_string())
+        );
+
+        for version in &tool.versions {
+            println!(
+                "    {} {} {}{}",
+                color::hash(&version.version),
+                HumanBytes(version.size),
+                color::muted(format_last_used(version.last_used_at)),
+                if version.default {
+                    color::muted(" (default)")
+                } else {
+                    String::new()
+                }
+            );
+        }
+
+        if tool.globals_size > 0 {
+            println!(
+                "    {} {}",
+                color::muted_light("globals"),
+                HumanBytes(tool.globals_size)
+            );
+        }
+    }
+
+    println!();
+
+    for (label, size) in [
+        ("Tools", usage.tools_size),
+        ("Plugins", usage.plugins_size),
+        ("Temp", usage.temp_size),
+        ("Version manifests", usage.versions_cache_size),
+    ] {
+        println!(
+            "{} {}",
+            color::muted_light(format!("{label}:")),
+            HumanBytes(size)
+        );
+    }
+
+    println!(
+        "{} {}",
+        color::muted_light("Total:"),
+        color::success(HumanBytes(usage.total_size).to_string())
+    );
+
+    if !usage.unused.is_empty() {
+        println!();
+        println!(
+            "{}",
+            color::label(format!(
+                "Largest versions not used in the last {UNUSED_DAYS} days"
+            ))
+        );
+
+        for item in &usage.unused {
+            println!(
+                "  {} {} {} {}",
+                color::id(&item.tool),
+                color::hash(&item.version),
+                HumanBytes(item.size),
+                color::muted(format_last_used(item.last_used_at)),
+            );
+        }
+
+        println!();
+        println!(
+            "Run {} to remove them",
+            color::shell(format!("proto clean --days {UNUSED_DAYS}"))
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,398 @@
+use crate::app::EnvShell;
+use crate::helpers::{get_tool_bin_dir, resolve_installed_version};
+use crate::tools::{create_tool, ToolType};
+use miette::IntoDiagnostic;
+use proto_core::{color, detect_version, ProtoError, ToolsConfig};
+use serde::Serialize;
+use starbase::SystemResult;
+use starbase_utils::json::{self, JsonValue};
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use tracing::{debug, warn};
+
+// Paths and variables set by the previous activation, so they can be reverted
+const ACTIVE_PATHS_VAR: &str = "PROTO_ACTIVE_PATHS";
+const ACTIVE_VARS_VAR: &str = "PROTO_ACTIVE_VARS";
+const SAVED_VARS_VAR: &str = "PROTO_SAVED_VARS";
+
+#[derive(Serialize)]
+pub struct ActiveTool {
+    version: String,
+    bin_dir: PathBuf,
+    globals_dir: Option<PathBuf>,
+}
+
+#[derive(Default)]
+pub struct EnvChanges {
+    tools: BTreeMap<String, ActiveTool>,
+    /// Full value of `PATH`, with activated directories first.
+    path: Vec<PathBuf>,
+    /// Variables to set, or to unset when `None`.
+    vars: BTreeMap<String, Option<String>>,
+}
+
+// Only uses installed versions, and never resolves against the remote,
+// as this runs on every directory change
+async fn find_active_tool(tool_type: &ToolType) -> miette::Result<Option<(String, ActiveTool)>> {
+    let mut tool = create_tool(tool_type).await?;
+
+    let Ok(candidate) = detect_version(&tool, None).await else {
+        debug!(tool = tool.get_id(), "No version detected, skipping");
+
+        return Ok(None);
+    };
+
+    let Some(version) = resolve_installed_version(tool.get_manifest()?, &candidate) else {
+        warn!(
+            "{} {} is not installed, run {} to activate it",
+            tool.get_name(),
+            candidate,
+            color::shell(format!("proto install {} {}", tool.get_id(), candidate))
+        );
+
+        return Ok(None);
+    };
+
+    tool.set_version(&version);
+    tool.find_bin_path().await?;
+
+    let Some(bin_dir) = get_tool_bin_dir(tool.as_ref())? else {
+        return Ok(None);
+    };
+
+    Ok(Some((
+        tool.get_id().to_owned(),
+        ActiveTool {
+            version,
+            bin_dir,
+            globals_dir: tool
+                .get_globals_bin_dir()
+                .ok()
+                .flatten()
+                .filter(|dir| dir.exists()),
+        },
+    )))
+}
+
+async fn find_active_tools(tools_config: &ToolsConfig) -> BTreeMap<String, ActiveTool> {
+    let mut tool_types = ToolType::iter()
+        .filter(|tool_type| !matches!(tool_type, ToolType::Plugin(_)))
+        .collect::<Vec<_>>();
+
+    for id in tools_config.tools.keys() {
+        match ToolType::from_str(id) {
+            Ok(tool_type) => {
+                if !tool_types.contains(&tool_type) {
+                    tool_types.push(tool_type);
+                }
+            }
+            Err(error) => {
+                warn!("Unable to activate {}: {}", id, error);
+            }
+        };
+    }
+
+    let mut tools = BTreeMap::new();
+
+    for tool_type in tool_types {
+        match find_active_tool(&tool_type).await {
+            Ok(Some((id, tool))) => {
+                tools.insert(id, tool);
+            }
+            Ok(None) => {}
+            Err(error) => {
+                warn!("Unable to activate {:?}: {}", tool_type, error);
+            }
+        };
+    }
+
+    tools
+}
+
+fn split_list(value: Option<String>) -> Vec<String> {
+    value
+        .map(|value| {
+            value
+                .split(',')
+                .filter(|item| !item.is_empty())
+                .map(|item| item.to_owned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub async fn collect_env_changes() -> miette::Result<EnvChanges> {
+    let tools_config = ToolsConfig::load_upwards()?;
+    let tools = find_active_tools(&tools_config).await;
+    let mut changes = EnvChanges::default();
+
+    // Directories to prepend to PATH
+    let mut active_paths = vec![];
+
+    for tool in tools.values() {
+        active_paths.push(tool.bin_dir.clone());
+
+        if let Some(globals_dir) = &tool.globals_dir {
+            active_paths.push(globals_dir.clone());
+        }
+    }
+
+    active_paths.dedup();
+
+    // Remove directories from the previous activation, and re-add the current ones
+    let previous_paths = env::var_os(ACTIVE_PATHS_VAR)
+        .map(|value| env::split_paths(&value).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    changes.path.extend(active_paths.clone());
+
+    if let Some(path) = env::var_os("PATH") {
+        changes.path.extend(
+            env::split_paths(&path)
+                .filter(|dir| !previous_paths.contains(dir) && !active_paths.contains(dir)),
+        );
+    }
+
+    // Variables from `.prototools`, with tool specific variables taking precedence
+    let mut active_vars = BTreeMap::new();
+
+    active_vars.extend(tools_config.env.clone());
+
+    for id in tools.keys() {
+        active_vars.extend(tools_config.get_tool_env(id));
+    }
+
+    // Values that variables had before they were activated, so they can be restored
+    let previous_vars = split_list(env::var(ACTIVE_VARS_VAR).ok());
+    let mut saved_vars: BTreeMap<String, String> = env::var(SAVED_VARS_VAR)
+        .ok()
+        .and_then(|value| json::from_str(&value).ok())
+        .unwrap_or_default();
+
+    for key in active_vars.keys() {
+        if !previous_vars.contains(key) {
+            if let Ok(value) = env::var(key) {
+                saved_vars.insert(key.to_owned(), value);
+            }
+        }
+    }
+
+    for key in previous_vars {
+        if !active_vars.contains_key(&key) {
+            let value = saved_vars.remove(&key);
+
+            changes.vars.insert(key, value);
+        }
+    }
+
+    let is_active = !active_paths.is_empty() || !active_vars.is_empty();
+
+    changes.vars.insert(
+        SAVED_VARS_VAR.into(),
+        if saved_vars.is_empty() {
+            None
+        } else {
+            Some(json::to_string(&saved_vars).into_diagnostic()?)
+        },
+    );
+
+    changes.vars.insert(
+        ACTIVE_PATHS_VAR.into(),
+        if active_paths.is_empty() {
+            None
+        } else {
+            Some(join_path(&active_paths)?)
+        },
+    );
+
+    changes.vars.insert(
+        ACTIVE_VARS_VAR.into(),
+        if active_vars.is_empty() {
+            None
+        } else {
+            Some(active_vars.keys().cloned().collect::<Vec<_>>().join(","))
+        },
+    );
+
+    for (key, value) in active_vars {
+        changes.vars.insert(key, Some(value));
+    }
+
+    // Nothing was previously activated, and nothing is active now
+    if !is_active
+        && env::var_os(ACTIVE_PATHS_VAR).is_none()
+        && env::var_os(ACTIVE_VARS_VAR).is_none()
+        && env::var_os(SAVED_VARS_VAR).is_none()
+    {
+        changes.vars.clear();
+    }
+
+    changes.tools = tools;
+
+    Ok(changes)
+}
+
+fn quote_posix(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn quote_fish(value: &str) -> String {
+    format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn quote_pwsh(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn join_path(paths: &[PathBuf]) -> miette::Result<String> {
+    Ok(env::join_paths(paths)
+        .into_diagnostic()?
+        .to_string_lossy()
+        .to_string())
+}
+
+pub fn format_env_changes(shell: &EnvShell, changes: &EnvChanges) -> miette::Result<String> {
+    let mut lines = vec![];
+
+    if let EnvShell::Json = shell {
+        let mut env = BTreeMap::new();
+
+        env.insert("PATH".to_owned(), Some(join_path(&changes.path)?));
+        env.extend(changes.vars.clone());
+
+        return json::to_string_pretty(&json::json!({
+            "tools": changes.tools,
+            "env": env,
+        }))
+        .into_diagnostic();
+    }
+
+    if let EnvShell::Nu = shell {
+        let mut env = json::JsonMap::new();
+
+        env.insert(
+            "PATH".into(),
+            JsonValue::from_iter(
+                changes
+                    .path
+                    .iter()
+                    .map(|dir| dir.to_string_lossy().to_string()),
+            ),
+        );
+
+        for (key, value) in &changes.vars {
+            env.insert(
+                key.to_owned(),
+                value
+                    .clone()
+                    .map(JsonValue::from)
+                    .unwrap_or(JsonValue::Null),
+            );
+        }
+
+        return json::to_string(&env).into_diagnostic();
+    }
+
+    for (key, value) in &changes.vars {
+        lines.push(match (shell, value) {
+            (EnvShell::Bash | EnvShell::Zsh, Some(value)) => {
+                format!("export {key}={};", quote_posix(value))
+            }
+            (EnvShell::Bash | EnvShell::Zsh, None) => format!("unset {key};"),
+            (EnvShell::Fish, Some(value)) => format!("set -gx {key} {};", quote_fish(value)),
+            (EnvShell::Fish, None) => format!("set -e {key};"),
+            (EnvShell::Pwsh, Some(value)) => format!("$env:{key} = {};", quote_pwsh(value)),
+            (EnvShell::Pwsh, None) => {
+                format!("Remove-Item -Path Env:{key} -ErrorAction SilentlyContinue;")
+            }
+            _ => unreachable!(),
+        });
+    }
+
+    lines.push(match shell {
+        EnvShell::Bash | EnvShell::Zsh => {
+            format!("export PATH={};", quote_posix(&join_path(&changes.path)?))
+        }
+        EnvShell::Fish => format!(
+            "set -gx PATH {};",
+            changes
+                .path
+                .iter()
+                .map(|dir| quote_fish(&dir.to_string_lossy()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        EnvShell::Pwsh => format!("$env:PATH = {};", quote_pwsh(&join_path(&changes.path)?)),
+        _ => unreachable!(),
+    });
+
+    Ok(lines.join("\n"))
+}
+
+pub async fn env(shell: EnvShell) -> SystemResult {
+    let changes = collect_env_changes().await?;
+
+    println!("{}", format_env_changes(&shell, &changes)?);
+
+    Ok(())
+}
+
+pub async fn activate(shell: EnvShell) -> SystemResult {
+    let hook = match shell {
+        EnvShell::Bash => r#"
+_proto_hook() {
+  local previous_exit_status=$?;
+  if [[ "$PWD" != "$_PROTO_LAST_PWD" ]]; then
+    _PROTO_LAST_PWD="$PWD";
+    eval "$(proto env --shell bash)";
+  fi
+  return $previous_exit_status;
+};
+if [[ ";${PROMPT_COMMAND[*]:-};" != *";_proto_hook;"* ]]; then
+  PROMPT_COMMAND="_proto_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}";
+fi
+"#
+        .trim(),
+        EnvShell::Zsh => r#"
+_proto_hook() {
+  eval "$(proto env --shell zsh)";
+};
+autoload -Uz add-zsh-hook;
+add-zsh-hook chpwd _proto_hook;
+_proto_hook;
+"#
+        .trim(),
+        EnvShell::Fish => r#"
+function _proto_hook --on-variable PWD;
+  proto env --shell fish | source;
+end;
+_proto_hook;
+"#
+        .trim(),
+        EnvShell::Pwsh => r#"
+$ExecutionContext.SessionState.InvokeCommand.LocationChangedAction = {
+  (proto env --shell pwsh) | Out-String | Invoke-Expression;
+};
+(proto env --shell pwsh) | Out-String | Invoke-Expression;
+"#
+        .trim(),
+        EnvShell::Nu => r#"
+$env.config = ($env.config | upsert hooks.env_change.PWD { |config|
+  ($config | get -i hooks.env_change.PWD | default []) | append { |before, after|
+    proto env --shell nu | from json | load-env
+  }
+});
+"#
+        .trim(),
+        EnvShell::Json => {
+            return Err(ProtoError::UnsupportedActivateShell("JSON".into()))?;
+        }
+    };
+
+    println!("{hook}");
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use crate::commands::install::install;
+use crate::helpers::{get_tool_bin_dir, parse_tool_spec};
+use crate::tools::{create_tool, ToolType};
+use miette::IntoDiagnostic;
+use proto_core::{color, detect_version, ProtoError, ToolsConfig, UserConfig};
+use rustc_hash::FxHashMap;
+use starbase::SystemResult;
+use std::env;
+use std::path::PathBuf;
+use std::process::exit;
+use std::str::FromStr;
+use tokio::process::Command;
+use tracing::debug;
+
+pub async fn exec(tools: Vec<String>, command: Vec<String>) -> SystemResult {
+    let Some((bin, args)) = command.split_first() else {
+        return Err(ProtoError::Message(
+            "A command to execute is required, after --.".into(),
+        ))?;
+    };
+
+    let tools_config = ToolsConfig::load_upwards()?;
+    let user_config = UserConfig::load()?;
+    let mut tool_args = vec![];
+
+    if tools.is_empty() {
+        debug!("No tools provided, using tools from .prototools");
+
+        let mut ids = tools_config.tools.keys().collect::<Vec<_>>();
+        ids.sort();
+
+        for id in ids {
+            tool_args.push((ToolType::from_str(id)?, None));
+        }
+    } else {
+        for arg in &tools {
+            tool_args.push(parse_tool_spec(arg)?);
+        }
+    }
+
+    let mut paths: Vec<PathBuf> = vec![];
+    let mut env_vars = FxHashMap::default();
+
+    for (tool_type, forced_version) in tool_args {
+        let mut tool = create_tool(&tool_type).await?;
+        let version = detect_version(&tool, forced_version).await?;
+
+        if !tool.is_setup(&version).await? {
+            let auto_install = tools_config
+                .get_tool_config(tool.get_id())
+                .and_then(|config| config.auto_install)
+                .unwrap_or(user_config.auto_install);
+
+            if !auto_install {
+                return Err(ProtoError::MissingToolForRun(
+                    tool.get_name(),
+                    version.to_owned(),
+                    color::shell(format!("proto install {} {}", tool.get_id(), version)),
+                ))?;
+            }
+
+            debug!("Auto-install setting is configured, attempting to install");
+
+            install(
+                tool_type.clone(),
+                Some(tool.get_resolved_version().to_owned()),
+                false,
+                vec![],
+            )
+            .await?;
+
+            tool.find_bin_path().await?;
+        }
+
+        let bin_path = tool.get_bin_path()?.to_path_buf();
+
+        debug!(
+            tool = tool.get_id(),
+            version = tool.get_resolved_version(),
+            bin = ?bin_path,
+            "Adding tool to PATH"
+        );
+
+        if let Some(bin_dir) = get_tool_bin_dir(tool.as_ref())? {
+            paths.push(bin_dir);
+        }
+
+        if let Some(globals_dir) = tool.get_globals_bin_dir()? {
+            if globals_dir.exists() {
+                paths.push(globals_dir);
+            }
+        }
+
+        env_vars.extend(tools_config.get_tool_env(tool.get_id()));
+
+        env_vars.insert(
+            format!("PROTO_{}_VERSION", tool.get_id().to_uppercase()),
+            tool.get_resolved_version().to_owned(),
+        );
+
+        env_vars.insert(
+            format!("PROTO_{}_BIN", tool.get_id().to_uppercase()),
+            bin_path.to_string_lossy().to_string(),
+        );
+    }
+
+    // Tools take precedence over the existing PATH, in the order they were provided
+    let mut unique_paths = vec![];
+
+    for path in paths {
+        if !unique_paths.contains(&path) {
+            unique_paths.push(path);
+        }
+    }
+
+    if let Some(path) = env::var_os("PATH") {
+        unique_paths.extend(env::split_paths(&path));
+    }
+
+    let path = env::join_paths(unique_paths).into_diagnostic()?;
+
+    debug!(command = bin, args = ?args, "Executing command");
+
+    let handle_error = |error| ProtoError::ExecuteFailed {
+        bin: bin.to_owned(),
+        error,
+    };
+
+    let status = Command::new(bin)
+        .args(args)
+        .envs(env_vars)
+        .env("PATH", path)
+        .spawn()
+        .map_err(handle_error)?
+        .wait()
+        .await
+        .map_err(handle_error)?;
+
+    if !status.success() {
+        exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
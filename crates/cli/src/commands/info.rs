@@ -0,0 +1,168 @@
+use crate::helpers::format_time_ago;
+use crate::tools::{create_tool, ToolType};
+use indicatif::HumanBytes;
+use miette::IntoDiagnostic;
+use proto_core::{color, detect_version, ManifestVersion};
+use serde::Serialize;
+use starbase::SystemResult;
+use starbase_utils::json;
+use std::path::PathBuf;
+
+fn render_entry<V: AsRef<str>>(label: &str, value: V) {
+    println!(
+        "  {} {}",
+        color::muted_light(format!("{label}:")),
+        value.as_ref()
+    );
+}
+
+#[derive(Serialize)]
+pub struct InfoItem {
+    id: String,
+    name: String,
+    version: String,
+    installed: bool,
+    default_version: Option<String>,
+    install_dir: PathBuf,
+    bin_path: Option<PathBuf>,
+    shim_path: Option<PathBuf>,
+    globals_dir: Option<PathBuf>,
+    metadata: Option<ManifestVersion>,
+}
+
+pub async fn info(tool_type: ToolType, forced_version: Option<String>, json: bool) -> SystemResult {
+    let mut tool = create_tool(&tool_type).await?;
+    let version = detect_version(&tool, forced_version).await?;
+
+    // Avoid resolving against the remote when the version is already installed
+    if tool.get_manifest()?.installed_versions.contains(&version) {
+        tool.set_version(&version);
+    } else {
+        tool.resolve_version(&version).await?;
+    }
+
+    let version = tool.get_resolved_version().to_owned();
+    let manifest = tool.get_manifest()?;
+    let installed = manifest.installed_versions.contains(&version);
+    let default_version = manifest.default_version.clone();
+    let metadata = manifest.versions.get(&version).cloned();
+
+    let mut bin_path = None;
+    let mut shim_path = None;
+
+    if installed {
+        tool.find_bin_path().await?;
+        tool.create_shims(true).await?;
+
+        bin_path = tool.get_bin_path().ok().map(|path| path.to_path_buf());
+        shim_path = tool.get_shim_path().map(|path| path.to_path_buf());
+    }
+
+    let item = InfoItem {
+        id: tool.get_id().to_owned(),
+        name: tool.get_name(),
+        version,
+        installed,
+        default_version,
+        install_dir: tool.get_install_dir()?,
+        bin_path,
+        shim_path,
+        globals_dir: tool.get_globals_bin_dir().ok().flatten(),
+        metadata,
+    };
+
+    if json {
+        println!("{}", json::to_string_pretty(&item).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    println!(
+        "{} {} {}",
+        color::id(&item.id),
+        color::hash(&item.version),
+        color::muted(format!("({})", item.name))
+    );
+
+    render_entry(
+        "Installed",
+        if item.installed {
+            color::success("yes")
+        } else {
+            color::failure("no")
+        },
+    );
+
+    if let Some(default_version) = &item.default_version {
+        render_entry("Global default", color::hash(default_version));
+    }
+
+    render_entry("Install dir", color::path(&item.install_dir));
+
+    if let Some(bin_path) = &item.bin_path {
+        render_entry("Bin", color::path(bin_path));
+    }
+
+    if let Some(shim_path) = &item.shim_path {
+        render_entry("Shim", color::path(shim_path));
+    }
+
+    if let Some(globals_dir) = &item.globals_dir {
+        render_entry("Globals dir", color::path(globals_dir));
+    }
+
+    let Some(metadata) = &item.metadata else {
+        return Ok(());
+    };
+
+    println!();
+
+    if metadata.installed_at > 0 {
+        render_entry("Installed at", format_time_ago(metadata.installed_at));
+    }
+
+    if let Some(last_used_at) = metadata.last_used_at {
+        render_entry("Last used", format_time_ago(last_used_at));
+    }
+
+    if let Some(proto_version) = &metadata.proto_version {
+        render_entry("Installed by", format!("proto v{proto_version}"));
+    }
+
+    if let Some(url) = &metadata.download_url {
+        render_entry("Download URL", color::url(url));
+    }
+
+    if let Some(checksum) = &metadata.checksum {
+        render_entry(
+            "Checksum",
+            format!(
+                "{}{}",
+                metadata
+                    .checksum_algorithm
+                    .as_ref()
+                    .map(|algo| format!("{algo}:"))
+                    .unwrap_or_default(),
+                color::hash(checksum)
+            ),
+        );
+    }
+
+    if let Some(size) = metadata.archive_size {
+        render_entry("Archive size", HumanBytes(size).to_string());
+    }
+
+    if let Some(size) = metadata.installed_size {
+        render_entry("Installed size", HumanBytes(size).to_string());
+    }
+
+    if let Some(locator) = &metadata.plugin_locator {
+        render_entry("Plugin", color::label(locator.to_string()));
+    }
+
+    if let Some(hash) = &metadata.plugin_hash {
+        render_entry("Plugin hash", format!("sha256:{}", color::hash(hash)));
+    }
+
+    Ok(())
+}
@@ -1,20 +1,96 @@
-use crate::helpers::{create_progress_bar, disable_progress_bars};
+use crate::helpers::{
+    create_progress_bar, disable_progress_bars, enable_progress_bars, parse_tool_spec,
+};
 use crate::hooks::go as go_hooks;
 use crate::tools::{create_tool, ToolType};
-use async_recursion::async_recursion;
-use proto_core::color;
+use futures::future::join_all;
+use proto_core::{color, Proto, ProtoError, ToolsConfig, UserConfig};
+use proto_node as node;
 use starbase::SystemResult;
+use std::str::FromStr;
 use tracing::{debug, info};
 
-#[async_recursion]
+pub struct InstallOutcome {
+    pub version: String,
+    pub already_installed: bool,
+}
+
 pub async fn install(
     tool_type: ToolType,
     version: Option<String>,
     pin_version: bool,
     passthrough: Vec<String>,
 ) -> SystemResult {
+    let outcome =
+        install_tool(tool_type.clone(), version, pin_version, passthrough.clone()).await?;
+
+    if tool_type == ToolType::Node && !outcome.already_installed {
+        install_bundled_npm(&outcome.version, pin_version, passthrough).await?;
+    }
+
+    Ok(())
+}
+
+/// Inherit install args from .prototools when none were passed.
+pub fn get_install_args(id: &str, passthrough: Vec<String>) -> miette::Result<Vec<String>> {
+    if !passthrough.is_empty() {
+        return Ok(passthrough);
+    }
+
+    Ok(ToolsConfig::load_upwards()?
+        .get_tool_config(id)
+        .map(|config| config.install_args.clone())
+        .unwrap_or_default())
+}
+
+/// Return the npm version that comes bundled with the provided Node.js version,
+/// or `None` if it was disabled with `--no-bundled-npm` or could not be found.
+pub fn get_bundled_npm_version(
+    node_version: &str,
+    passthrough: &[String],
+) -> miette::Result<Option<String>> {
+    if passthrough.contains(&"--no-bundled-npm".to_string()) {
+        return Ok(None);
+    }
+
+    let npm_version = node::NodeLanguage::new(Proto::new()?).get_bundled_npm_version(node_version);
+
+    if npm_version.is_none() {
+        debug!("Unable to find the npm version bundled with Node.js v{node_version}, skipping");
+    }
+
+    Ok(npm_version)
+}
+
+/// Install the npm version that comes bundled with the provided Node.js version.
+/// Returns `None` when skipped.
+pub async fn install_bundled_npm(
+    node_version: &str,
+    pin_version: bool,
+    passthrough: Vec<String>,
+) -> miette::Result<Option<InstallOutcome>> {
+    let passthrough = get_install_args("node", passthrough)?;
+
+    let Some(npm_version) = get_bundled_npm_version(node_version, &passthrough)? else {
+        return Ok(None);
+    };
+
+    info!("Installing npm that comes bundled with Node.js v{node_version}");
+
+    install_tool(ToolType::Npm, Some(npm_version), pin_version, passthrough)
+        .await
+        .map(Some)
+}
+
+pub async fn install_tool(
+    tool_type: ToolType,
+    version: Option<String>,
+    pin_version: bool,
+    passthrough: Vec<String>,
+) -> miette::Result<InstallOutcome> {
     let version = version.unwrap_or_else(|| "latest".into());
     let mut tool = create_tool(&tool_type).await?;
+    let passthrough = get_install_args(tool.get_id(), passthrough)?;
 
     if tool.is_setup(&version).await? {
         info!(
@@ -23,7 +99,10 @@ pub async fn install(
             color::path(tool.get_install_dir()?),
         );
 
-        return Ok(());
+        return Ok(InstallOutcome {
+            version: tool.get_resolved_version().to_owned(),
+            already_installed: true,
+        });
     }
 
     // Rust doesn't download files but runs commands
@@ -46,10 +125,19 @@ pub async fn install(
     tool.setup(&version).await?;
     tool.cleanup().await?;
 
-    if pin_version {
+    {
         let version = tool.get_resolved_version().to_owned();
         let manifest = tool.get_manifest_mut()?;
-        manifest.default_version = Some(version);
+
+        // Record which version of proto installed the tool
+        if let Some(metadata) = manifest.versions.get_mut(&version) {
+            metadata.proto_version = Some(env!("CARGO_PKG_VERSION").to_owned());
+        }
+
+        if pin_version {
+            manifest.default_version = Some(version);
+        }
+
         manifest.save()?;
     }
 
@@ -63,31 +151,227 @@ pub async fn install(
 
     // Support post install actions that are not coupled to the
     // `Tool` trait. Right now we are hard-coding this, but we
-    // should provide a better API.
+    // should provide a better API. Node.js's bundled npm is installed
+    // by the caller, with `install_bundled_npm`.
+    if let ToolType::Plugin(name) = tool_type {
+        if name == "go" {
+            go_hooks::post_install(&passthrough)?;
+        }
+    }
+
+    Ok(InstallOutcome {
+        version: tool.get_resolved_version().to_owned(),
+        already_installed: false,
+    })
+}
+
+/// Whether the argument is the name of a built-in or configured tool.
+fn is_tool_name(arg: &str, tools_config: &ToolsConfig, user_config: &UserConfig) -> bool {
+    match ToolType::from_str(arg) {
+        Ok(ToolType::Plugin(name)) => {
+            tools_config.plugins.contains_key(&name)
+                || user_config.plugins.contains_key(&name)
+                || ToolsConfig::builtin_plugins().contains_key(&name)
+        }
+        Ok(_) => true,
+        Err(_) => false,
+    }
+}
+
+pub struct InstallSpec {
+    pub id: String,
+    pub tool_type: ToolType,
+    pub version: Option<String>,
+}
+
+impl InstallSpec {
+    pub fn label(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}@{version}", self.id),
+            None => self.id.clone(),
+        }
+    }
+}
+
+/// Parse `<tool>@<version>` and `<tool>` specs. For compatibility with
+/// `proto install <tool> <version>`, an argument that follows a tool without
+/// a version, and is not a tool itself, is used as that tool's version.
+pub fn parse_install_specs(args: &[String]) -> miette::Result<Vec<InstallSpec>> {
+    let tools_config = ToolsConfig::load_upwards()?;
+    let user_config = UserConfig::load()?;
+    let mut specs: Vec<InstallSpec> = vec![];
+
+    for arg in args {
+        if !arg.contains('@') {
+            if let Some(spec @ InstallSpec { version: None, .. }) = specs.last_mut() {
+                if !is_tool_name(arg, &tools_config, &user_config) {
+                    spec.version = Some(arg.to_owned());
+                    continue;
+                }
+            }
+        }
+
+        let (tool_type, version) = parse_tool_spec(arg)?;
+
+        specs.push(InstallSpec {
+            id: arg.split('@').next().unwrap_or(arg).to_owned(),
+            tool_type,
+            version,
+        });
+    }
+
+    Ok(specs)
+}
+
+/// Print the outcome of each install, and return the number that failed.
+pub fn print_install_summary(results: &[(String, miette::Result<InstallOutcome>)]) -> usize {
+    let width = results
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or_default();
+    let mut failed = 0;
+
+    println!();
+
+    for (label, result) in results {
+        let status = match result {
+            Ok(outcome) if outcome.already_installed => {
+                format!("{} {}", color::hash(&outcome.version), "already installed")
+            }
+            Ok(outcome) => format!(
+                "{} {}",
+                color::hash(&outcome.version),
+                color::success("installed")
+            ),
+            Err(error) => {
+                failed += 1;
+
+                format!(
+                    "{} {}",
+                    color::failure("failed"),
+                    color::muted_light(error.to_string())
+                )
+            }
+        };
+
+        println!("{} {status}", color::id(format!("{label:width$}")));
+    }
+
+    println!();
+
+    failed
+}
+
+/// Tools in the same group are installed sequentially. Node.js and its dependency
+/// managers are grouped, as installing Node.js also installs its bundled npm.
+fn get_install_group(tool_type: &ToolType) -> ToolType {
     match tool_type {
-        ToolType::Node => {
-            if !passthrough.contains(&"--no-bundled-npm".to_string()) {
-                info!("Installing npm that comes bundled with {}", tool.get_name());
+        ToolType::Npm | ToolType::Pnpm | ToolType::Yarn => ToolType::Node,
+        _ => tool_type.clone(),
+    }
+}
 
-                // This ensures that the correct version is used by the npm tool
-                std::env::set_var("PROTO_NODE_VERSION", tool.get_resolved_version());
+pub async fn install_many(
+    args: Vec<String>,
+    pin_version: bool,
+    passthrough: Vec<String>,
+) -> SystemResult {
+    let specs = parse_install_specs(&args)?;
 
-                install(
-                    ToolType::Npm,
-                    Some("bundled".into()),
+    if let [spec] = specs.as_slice() {
+        return install(
+            spec.tool_type.clone(),
+            spec.version.clone(),
+            pin_version,
+            passthrough,
+        )
+        .await;
+    }
+
+    let labels = specs.iter().map(|spec| spec.label()).collect::<Vec<_>>();
+
+    // Versions of the same tool are installed sequentially, as they share
+    // a manifest, while different tools are installed concurrently
+    let mut groups: Vec<Vec<InstallSpec>> = vec![];
+
+    for spec in specs {
+        match groups.iter_mut().find(|group| {
+            get_install_group(&group[0].tool_type) == get_install_group(&spec.tool_type)
+        }) {
+            Some(group) => group.push(spec),
+            None => groups.push(vec![spec]),
+        };
+    }
+
+    // Node.js installs its bundled npm, so must be installed before its dependency managers
+    for group in &mut groups {
+        group.sort_by_key(|spec| spec.tool_type != ToolType::Node);
+    }
+
+    let pb = create_progress_bar(format!(
+        "Installing {} tools: {}",
+        labels.len(),
+        labels.join(", ")
+    ));
+
+    disable_progress_bars();
+
+    let results = join_all(groups.into_iter().map(|group| {
+        let passthrough = passthrough.clone();
+
+        async move {
+            let mut results = vec![];
+
+            for spec in group {
+                let label = spec.label();
+                let result = install_tool(
+                    spec.tool_type.clone(),
+                    spec.version,
                     pin_version,
-                    passthrough,
+                    passthrough.clone(),
                 )
-                .await?;
-            }
-        }
-        ToolType::Plugin(name) => {
-            if name == "go" {
-                go_hooks::post_install(&passthrough)?;
+                .await;
+
+                let node_version = match &result {
+                    Ok(outcome)
+                        if spec.tool_type == ToolType::Node && !outcome.already_installed =>
+                    {
+                        Some(outcome.version.clone())
+                    }
+                    _ => None,
+                };
+
+                results.push((label, result));
+
+                if let Some(node_version) = node_version {
+                    if let Some(result) =
+                        install_bundled_npm(&node_version, pin_version, passthrough.clone())
+                            .await
+                            .transpose()
+                    {
+                        results.push(("npm@bundled".into(), result));
+                    }
+                }
             }
+
+            results
         }
-        _ => {}
+    }))
+    .await;
+
+    enable_progress_bars();
+
+    pb.finish_and_clear();
+
+    let results = results.into_iter().flatten().collect::<Vec<_>>();
+    let failed = print_install_summary(&results);
+
+    if failed > 0 {
+        return Err(ProtoError::InstallFailed(failed, results.len()))?;
     }
 
+    info!("Successfully installed {} tools", results.len());
+
     Ok(())
 }
@@ -1,21 +1,82 @@
 use crate::helpers::{disable_progress_bars, enable_progress_bars};
 use crate::tools::{create_plugin_from_locator, create_tool, ToolType};
-use crate::{commands::clean::clean, commands::install::install, helpers::create_progress_bar};
+use crate::{
+    commands::clean::{clean, CleanOptions},
+    commands::install::{
+        get_bundled_npm_version, get_install_args, install_tool, print_install_summary,
+        InstallOutcome,
+    },
+    helpers::create_progress_bar,
+};
 use futures::future::try_join_all;
-use proto_core::{expand_detected_version, Proto, ToolsConfig, UserConfig};
+use futures::{stream, StreamExt};
+use proto_core::{
+    expand_detected_version, ConfigTraversal, Proto, ProtoError, ToolsConfig, TraversedDir,
+    UserConfig,
+};
+use serde::Serialize;
 use starbase::SystemResult;
+use std::collections::BTreeMap;
 use std::env;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use strum::IntoEnumIterator;
 use tracing::{debug, info};
 
-pub async fn install_all() -> SystemResult {
-    let working_dir = env::current_dir().expect("Missing current directory.");
+const MAX_CONCURRENT_INSTALLS: usize = 4;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProjectToolSource {
+    /// Pinned in a `.prototools` file.
+    Config,
+    /// Detected from a version file in the tool's ecosystem.
+    Ecosystem,
+}
+
+/// A tool that is relevant to the current project, and where its version came from.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProjectTool {
+    pub spec: String,
+    pub source: ProjectToolSource,
+    pub source_file: PathBuf,
+}
+
+/// Find all tools that apply to the working directory, from the merged `.prototools`
+/// files, and the version files of each tool's ecosystem. Ecosystem versions
+/// take precedence, as they are detected from the working directory itself.
+pub async fn detect_project_tools(
+    working_dir: &Path,
+    config: &ToolsConfig,
+) -> miette::Result<BTreeMap<String, ProjectTool>> {
+    let mut tools = BTreeMap::new();
 
     // Inherit from .prototools
     debug!("Detecting tools and plugins from .prototools");
 
-    let mut config = ToolsConfig::load_upwards()?;
+    for (id, spec) in &config.tools {
+        let mut source_file = config.path.clone();
+
+        // The merged config does not track which file each tool came from,
+        // so find the closest file that pins it
+        for entry in ConfigTraversal::new().traverse_from(working_dir) {
+            let TraversedDir { config, .. } = entry?;
+
+            if config.tools.contains_key(id) {
+                source_file = config.path.clone();
+                break;
+            }
+        }
+
+        tools.insert(
+            id.to_owned(),
+            ProjectTool {
+                spec: spec.to_owned(),
+                source: ProjectToolSource::Config,
+                source_file,
+            },
+        );
+    }
 
     // Detect from working dir
     debug!("Detecting tools from environment");
@@ -27,16 +88,143 @@ pub async fn install_all() -> SystemResult {
 
         let tool = create_tool(&tool_type).await?;
 
-        if let Some(version) = tool.detect_version_from(&working_dir).await? {
+        if let Some((version, file)) = tool.detect_version_with_source(working_dir).await? {
             if let Some(version) = expand_detected_version(&version, tool.get_manifest()?)? {
                 debug!(version, "Detected version for {}", tool.get_name());
 
-                config.tools.insert(tool.get_id().to_owned(), version);
+                tools.insert(
+                    tool.get_id().to_owned(),
+                    ProjectTool {
+                        spec: version,
+                        source: ProjectToolSource::Ecosystem,
+                        source_file: file,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(tools)
+}
+
+/// Tools that must be installed before the provided tool, when both are being installed.
+fn get_dependencies(tool_type: &ToolType) -> Vec<ToolType> {
+    match tool_type {
+        // Dependency managers may use the bundled version, or the detected Node.js version
+        ToolType::Npm | ToolType::Pnpm | ToolType::Yarn => vec![ToolType::Node],
+        _ => vec![],
+    }
+}
+
+/// Install tools in waves, where each wave contains the tools whose dependencies
+/// have been installed. Tools within a wave are installed concurrently, except for
+/// versions of the same tool, which share a manifest. Installing Node.js queues
+/// its bundled npm as a dependent install.
+async fn install_tools(
+    tools: BTreeMap<String, String>,
+) -> miette::Result<Vec<(String, miette::Result<InstallOutcome>)>> {
+    let mut pending = vec![];
+
+    for (id, version) in tools {
+        pending.push((ToolType::from_str(&id)?, id, version));
+    }
+
+    let mut failed_tools: Vec<(ToolType, String)> = vec![];
+    let mut results = vec![];
+
+    while !pending.is_empty() {
+        let mut ready: Vec<(ToolType, String, String)> = vec![];
+        let mut waiting = vec![];
+
+        for item in pending.clone() {
+            let is_blocked = get_dependencies(&item.0).iter().any(|dep| {
+                pending
+                    .iter()
+                    .any(|(pending_type, _, _)| pending_type == dep)
+            }) || ready.iter().any(|(ready_type, _, _)| ready_type == &item.0);
+
+            if is_blocked {
+                waiting.push(item);
+            } else {
+                ready.push(item);
             }
         }
+
+        // Dependencies are not cyclical, but avoid looping forever
+        if ready.is_empty() {
+            break;
+        }
+
+        pending = waiting;
+
+        let wave = stream::iter(ready)
+            .map(|(tool_type, id, version)| {
+                let failed_dep = get_dependencies(&tool_type).into_iter().find_map(|dep| {
+                    failed_tools
+                        .iter()
+                        .find(|(failed_type, _)| failed_type == &dep)
+                        .map(|(_, failed_id)| failed_id.to_owned())
+                });
+
+                async move {
+                    let label = format!("{id}@{version}");
+
+                    if let Some(failed_dep) = failed_dep {
+                        debug!(
+                            tool = id,
+                            dependency = failed_dep,
+                            "Skipping install as a dependency failed"
+                        );
+
+                        let error = ProtoError::InstallDependencyFailed(failed_dep);
+
+                        return (tool_type, id, label, Err(error.into()));
+                    }
+
+                    let result =
+                        install_tool(tool_type.clone(), Some(version), false, vec![]).await;
+
+                    (tool_type, id, label, result)
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_INSTALLS)
+            .collect::<Vec<_>>()
+            .await;
+
+        for (tool_type, id, label, result) in wave {
+            match &result {
+                Ok(outcome) if tool_type == ToolType::Node && !outcome.already_installed => {
+                    if let Some(npm_version) = get_bundled_npm_version(
+                        &outcome.version,
+                        &get_install_args("node", vec![])?,
+                    )? {
+                        pending.insert(0, (ToolType::Npm, "npm".into(), npm_version));
+                    }
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    failed_tools.push((tool_type, id));
+                }
+            };
+
+            results.push((label, result));
+        }
     }
 
-    let tools = config.tools;
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(results)
+}
+
+pub async fn install_all() -> SystemResult {
+    let working_dir = env::current_dir().expect("Missing current directory.");
+    let config = ToolsConfig::load_upwards()?;
+
+    let tools = detect_project_tools(&working_dir, &config)
+        .await?
+        .into_iter()
+        .map(|(id, tool)| (id, tool.spec))
+        .collect::<BTreeMap<_, _>>();
     let plugins = config.plugins;
 
     if !plugins.is_empty() {
@@ -58,7 +246,6 @@ pub async fn install_all() -> SystemResult {
     }
 
     if !tools.is_empty() {
-        let mut futures = vec![];
         let pb = create_progress_bar(format!(
             "Installing {} tools: {}",
             tools.len(),
@@ -67,20 +254,17 @@ pub async fn install_all() -> SystemResult {
 
         disable_progress_bars();
 
-        for (tool, version) in &tools {
-            futures.push(install(
-                ToolType::from_str(tool)?,
-                Some(version.to_owned()),
-                false,
-                vec![],
-            ));
-        }
-
-        try_join_all(futures).await?;
+        let results = install_tools(tools.clone()).await?;
 
         enable_progress_bars();
 
         pb.finish_and_clear();
+
+        let failed = print_install_summary(&results);
+
+        if failed > 0 {
+            return Err(ProtoError::InstallFailed(failed, results.len()))?;
+        }
     }
 
     if tools.is_empty() && plugins.is_empty() {
@@ -91,7 +275,12 @@ pub async fn install_all() -> SystemResult {
 
     if UserConfig::load()?.auto_clean {
         debug!("Auto-clean enabled, starting clean");
-        clean(None, true).await?;
+        clean(CleanOptions {
+            versions_only: true,
+            yes: true,
+            ..CleanOptions::default()
+        })
+        .await?;
     }
 
     Ok(())
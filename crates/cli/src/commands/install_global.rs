@@ -97,7 +97,10 @@ pub async fn install_global(tool_type: ToolType, dependencies: Vec<String>) -> S
             .env("PROTO_INSTALL_GLOBAL", "true")
             .output()
             .await
-            .map_err(|e| ProtoError::Message(e.to_string()))?;
+            .map_err(|error| ProtoError::ExecuteFailed {
+                bin: command.as_std().get_program().to_string_lossy().to_string(),
+                error,
+            })?;
 
         pb.finish_and_clear();
 
@@ -1,10 +1,22 @@
 use crate::tools::{create_tool, ToolType};
 use human_sort::compare;
+use miette::IntoDiagnostic;
 use proto_core::color;
+use serde::Serialize;
 use starbase::SystemResult;
+use starbase_utils::json;
 use tracing::debug;
 
-pub async fn list(tool_type: ToolType) -> SystemResult {
+#[derive(Serialize)]
+pub struct InstalledVersion {
+    version: String,
+    default: bool,
+    aliases: Vec<String>,
+    installed_at: Option<u128>,
+    last_used_at: Option<u128>,
+}
+
+pub async fn list(tool_type: ToolType, json: bool) -> SystemResult {
     let tool = create_tool(&tool_type).await?;
     let manifest = tool.get_manifest()?;
 
@@ -12,9 +24,40 @@ pub async fn list(tool_type: ToolType) -> SystemResult {
 
     let mut versions = Vec::from_iter(manifest.installed_versions.clone());
 
-    if !versions.is_empty() {
-        versions.sort_by(|a, d| compare(a, d));
+    versions.sort_by(|a, d| compare(a, d));
+
+    if json {
+        let items = versions
+            .into_iter()
+            .map(|version| {
+                let metadata = manifest.versions.get(&version);
+                let mut aliases = manifest
+                    .aliases
+                    .iter()
+                    .filter(|(_, aliased)| **aliased == version)
+                    .map(|(alias, _)| alias.to_owned())
+                    .collect::<Vec<_>>();
+
+                aliases.sort();
 
+                InstalledVersion {
+                    default: manifest.default_version.as_ref() == Some(&version),
+                    installed_at: metadata
+                        .map(|meta| meta.installed_at)
+                        .filter(|timestamp| *timestamp > 0),
+                    last_used_at: metadata.and_then(|meta| meta.last_used_at),
+                    aliases,
+                    version,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        println!("{}", json::to_string_pretty(&items).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    if !versions.is_empty() {
         println!("{}", versions.join("\n"));
     }
 
@@ -1,14 +1,26 @@
 use crate::tools::{create_tool, ToolType};
 use human_sort::compare;
 use proto_core::color;
+use serde::Serialize;
 use starbase::{diagnostics::IntoDiagnostic, SystemResult};
-use starbase_utils::fs;
+use starbase_utils::{fs, json};
+use std::path::PathBuf;
 use tracing::debug;
 
-pub async fn list_global(tool_type: ToolType) -> SystemResult {
+#[derive(Serialize)]
+pub struct GlobalBin {
+    name: String,
+    path: PathBuf,
+}
+
+pub async fn list_global(tool_type: ToolType, json: bool) -> SystemResult {
     let tool = create_tool(&tool_type).await?;
 
     let Some(bin_dir) = tool.get_globals_bin_dir()? else {
+        if json {
+            println!("[]");
+        }
+
         return Ok(());
     };
 
@@ -40,18 +52,29 @@ pub async fn list_global(tool_type: ToolType) -> SystemResult {
                 }
             }
 
-            bins.push(format!(
-                "{} - {}",
-                file_name,
-                color::path(file_path.canonicalize().unwrap())
-            ));
+            bins.push(GlobalBin {
+                name: file_name,
+                path: file_path.canonicalize().unwrap(),
+            });
         }
     }
 
-    if !bins.is_empty() {
-        bins.sort_by(|a, d| compare(a, d));
+    bins.sort_by(|a, d| compare(&a.name, &d.name));
 
-        println!("{}", bins.join("\n"));
+    if json {
+        println!("{}", json::to_string_pretty(&bins).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    if !bins.is_empty() {
+        println!(
+            "{}",
+            bins.iter()
+                .map(|bin| format!("{} - {}", bin.name, color::path(&bin.path)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
     }
 
     Ok(())
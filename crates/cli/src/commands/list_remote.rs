@@ -1,23 +1,86 @@
 use crate::tools::{create_tool, ToolType};
 use human_sort::compare;
+use miette::IntoDiagnostic;
+use proto_core::{parse_version, remove_v_prefix};
+use serde::Serialize;
 use starbase::SystemResult;
+use starbase_utils::json;
 use std::io::{self, Write};
 use tracing::debug;
 
-// TODO: only show LTS, dont show pre-releases?
-pub async fn list_remote(tool_type: ToolType) -> SystemResult {
+#[derive(Serialize)]
+pub struct RemoteVersion {
+    version: String,
+    aliases: Vec<String>,
+    lts: Option<String>,
+    prerelease: bool,
+    installed: bool,
+}
+
+pub async fn list_remote(
+    tool_type: ToolType,
+    include_prereleases: bool,
+    since: Option<String>,
+    json: bool,
+) -> SystemResult {
     let tool = create_tool(&tool_type).await?;
+    let since = since.map(|since| remove_v_prefix(&since));
+    let since_version = match &since {
+        Some(since) => Some(parse_version(since)?),
+        None => None,
+    };
 
     debug!("Loading manifest");
 
     let manifest = tool.load_version_manifest().await?;
+    let installed_versions = &tool.get_manifest()?.installed_versions;
+    let mut releases = vec![];
 
-    let stdout = io::stdout();
-    let mut handle = io::BufWriter::new(stdout);
-    let mut releases = manifest.versions.values().collect::<Vec<_>>();
+    for release in manifest.versions.values() {
+        let parsed_version = parse_version(&release.version).ok();
+        let prerelease = parsed_version
+            .as_ref()
+            .is_some_and(|version| version.is_pre_release());
+
+        if prerelease && !include_prereleases {
+            continue;
+        }
+
+        if let Some(since_version) = &since_version {
+            if parsed_version.is_none() || parsed_version.as_ref() < Some(since_version) {
+                continue;
+            }
+        }
+
+        let mut aliases = manifest
+            .aliases
+            .iter()
+            .filter(|(_, version)| remove_v_prefix(version) == release.version)
+            .map(|(alias, _)| alias.to_owned())
+            .collect::<Vec<_>>();
+
+        aliases.sort();
+
+        releases.push(RemoteVersion {
+            version: release.version.clone(),
+            aliases,
+            lts: release.alias.clone(),
+            prerelease,
+            installed: installed_versions.contains(&release.version),
+        });
+    }
 
     releases.sort_by(|a, d| compare(&a.version, &d.version));
 
+    if json {
+        println!("{}", json::to_string_pretty(&releases).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    let stdout = io::stdout();
+    let mut handle = io::BufWriter::new(stdout);
+
     for release in releases {
         writeln!(handle, "{}", release.version).unwrap();
     }
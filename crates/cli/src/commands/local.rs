@@ -4,12 +4,36 @@ use starbase::SystemResult;
 use std::{env, path::PathBuf};
 use tracing::{debug, info};
 
-pub async fn local(tool_type: ToolType, version: String) -> SystemResult {
+pub async fn local(tool_type: ToolType, version: Option<String>, unset: bool) -> SystemResult {
     let tool = create_tool(&tool_type).await?;
 
     let local_path = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     let mut config = ToolsConfig::load_from(&local_path)?;
 
+    if unset {
+        let removed_version = config.tools.remove(tool.get_id());
+        let removed_config = config.tool_configs.remove(tool.get_id());
+
+        if removed_version.is_none() && removed_config.is_none() {
+            info!("No local {} version has been set", tool.get_name());
+
+            return Ok(());
+        }
+
+        config.save()?;
+
+        debug!(
+            "Removed the local version from {}",
+            color::path(&config.path)
+        );
+
+        info!("Unset the local {} version", tool.get_name());
+
+        return Ok(());
+    }
+
+    let version = version.unwrap_or_default();
+
     config
         .tools
         .insert(tool.get_id().to_owned(), version.clone());
@@ -0,0 +1,448 @@
+use crate::app::MigrateSource;
+use crate::tools::{create_tool, ToolType};
+use proto_core::{
+    color, expand_detected_version, get_home_dir, remove_v_prefix, Manifest, ProtoError,
+};
+use semver::Version;
+use starbase::SystemResult;
+use starbase_utils::fs::{self, FsError};
+use starbase_utils::json;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// A version that was installed by another version manager.
+pub struct ForeignInstall {
+    pub version: String,
+    pub path: PathBuf,
+}
+
+/// Node.js installs from another version manager, and the versions
+/// (which may be partial or aliases) that it has set as defaults.
+#[derive(Default)]
+pub struct ForeignToolchain {
+    pub installs: Vec<ForeignInstall>,
+    pub default_version: Option<String>,
+    pub aliases: BTreeMap<String, String>,
+}
+
+fn get_dir_from_env(var: &str, fallbacks: &[PathBuf]) -> Option<PathBuf> {
+    if let Some(dir) = env::var_os(var).filter(|value| !value.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+
+    fallbacks.iter().find(|dir| dir.exists()).cloned()
+}
+
+/// Find all directories within a directory that are named after a semantic version,
+/// with an optional `v` prefix. The install is located at `dir/<name>/<suffix>`.
+fn find_versioned_dirs(dir: &Path, suffix: &str) -> Result<Vec<ForeignInstall>, ProtoError> {
+    let mut installs = vec![];
+
+    if !dir.exists() {
+        return Ok(installs);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry.path();
+        let version = remove_v_prefix(&fs::file_name(&path));
+
+        if !path.is_dir() || Version::parse(&version).is_err() {
+            debug!(dir = ?path, "Not a versioned directory, skipping");
+            continue;
+        }
+
+        let path = if suffix.is_empty() {
+            path
+        } else {
+            path.join(suffix)
+        };
+
+        if path.exists() {
+            installs.push(ForeignInstall { version, path });
+        }
+    }
+
+    installs.sort_by_key(|install| Version::parse(&install.version).ok());
+
+    Ok(installs)
+}
+
+fn read_nvm_alias(alias_dir: &Path, name: &str) -> Result<Option<String>, ProtoError> {
+    let mut name = name.to_owned();
+
+    // Aliases may point to other aliases, like `default` -> `lts/hydrogen`
+    for _ in 0..5 {
+        let path = alias_dir.join(&name);
+
+        if !path.is_file() {
+            return Ok(Some(name));
+        }
+
+        name = fs::read_file(path)?.trim().to_owned();
+    }
+
+    Ok(None)
+}
+
+fn find_nvm() -> Result<ForeignToolchain, ProtoError> {
+    let Some(root) = get_dir_from_env("NVM_DIR", &[get_home_dir()?.join(".nvm")]) else {
+        return Ok(ForeignToolchain::default());
+    };
+
+    let alias_dir = root.join("alias");
+    let mut toolchain = ForeignToolchain {
+        installs: find_versioned_dirs(&root.join("versions/node"), "")?,
+        ..ForeignToolchain::default()
+    };
+
+    if alias_dir.exists() {
+        for entry in fs::read_dir(&alias_dir)? {
+            let path = entry.path();
+            let name = fs::file_name(&path);
+
+            // The `lts` directory is only used to resolve other aliases
+            if !path.is_file() {
+                continue;
+            }
+
+            if let Some(version) = read_nvm_alias(&alias_dir, &name)? {
+                if name == "default" {
+                    toolchain.default_version = Some(version);
+                } else {
+                    toolchain.aliases.insert(name, version);
+                }
+            }
+        }
+    }
+
+    Ok(toolchain)
+}
+
+fn find_volta() -> Result<ForeignToolchain, ProtoError> {
+    let Some(root) = get_dir_from_env("VOLTA_HOME", &[get_home_dir()?.join(".volta")]) else {
+        return Ok(ForeignToolchain::default());
+    };
+
+    let mut toolchain = ForeignToolchain {
+        installs: find_versioned_dirs(&root.join("tools/image/node"), "")?,
+        ..ForeignToolchain::default()
+    };
+
+    let platform_path = root.join("tools/user/platform.json");
+
+    if platform_path.exists() {
+        let platform: json::JsonValue = json::read_file(&platform_path)?;
+
+        if let Some(version) = platform["node"]["runtime"].as_str() {
+            toolchain.default_version = Some(version.to_owned());
+        }
+    }
+
+    Ok(toolchain)
+}
+
+fn find_asdf() -> Result<ForeignToolchain, ProtoError> {
+    let home_dir = get_home_dir()?;
+
+    let Some(root) = get_dir_from_env("ASDF_DATA_DIR", &[home_dir.join(".asdf")]) else {
+        return Ok(ForeignToolchain::default());
+    };
+
+    let mut toolchain = ForeignToolchain {
+        installs: find_versioned_dirs(&root.join("installs/nodejs"), "")?,
+        ..ForeignToolchain::default()
+    };
+
+    let tool_versions_path = home_dir.join(".tool-versions");
+
+    if tool_versions_path.exists() {
+        for line in fs::read_file(&tool_versions_path)?.lines() {
+            let mut parts = line.split_whitespace();
+
+            if parts.next() == Some("nodejs") {
+                // The first version listed takes precedence
+                toolchain.default_version = parts.next().map(|version| version.to_owned());
+                break;
+            }
+        }
+    }
+
+    Ok(toolchain)
+}
+
+fn find_fnm() -> Result<ForeignToolchain, ProtoError> {
+    let home_dir = get_home_dir()?;
+
+    let Some(root) = get_dir_from_env(
+        "FNM_DIR",
+        &[
+            home_dir.join(".local/share/fnm"),
+            home_dir.join("Library/Application Support/fnm"),
+            home_dir.join(".fnm"),
+        ],
+    ) else {
+        return Ok(ForeignToolchain::default());
+    };
+
+    let alias_dir = root.join("aliases");
+    let mut toolchain = ForeignToolchain {
+        installs: find_versioned_dirs(&root.join("node-versions"), "installation")?,
+        ..ForeignToolchain::default()
+    };
+
+    if alias_dir.exists() {
+        for entry in fs::read_dir(&alias_dir)? {
+            let path = entry.path();
+            let name = fs::file_name(&path);
+
+            // Aliases are symlinks to `node-versions/<version>/installation`
+            let Some(version) = std::fs::read_link(&path).ok().and_then(|target| {
+                target
+                    .parent()
+                    .map(|dir| remove_v_prefix(&fs::file_name(dir)))
+            }) else {
+                continue;
+            };
+
+            if name == "default" {
+                toolchain.default_version = Some(version);
+            } else {
+                toolchain.aliases.insert(name, version);
+            }
+        }
+    }
+
+    Ok(toolchain)
+}
+
+/// Copy a directory while preserving symlinks, as Node.js installs
+/// link binaries (like `npm`) to files within the install.
+fn copy_install(from: &Path, to: &Path) -> Result<(), ProtoError> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let path = entry.path();
+        let dest = to.join(entry.file_name());
+        let handle_error = |error: std::io::Error| {
+            ProtoError::from(FsError::Copy {
+                from: path.clone(),
+                to: dest.clone(),
+                error,
+            })
+        };
+        let file_type = entry.file_type().map_err(handle_error)?;
+
+        if file_type.is_symlink() {
+            let target = std::fs::read_link(&path).map_err(handle_error)?;
+
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(target, &dest).map_err(handle_error)?;
+
+            #[cfg(windows)]
+            {
+                let _ = target;
+                std::fs::copy(&path, &dest).map_err(handle_error)?;
+            }
+        } else if file_type.is_dir() {
+            copy_install(&path, &dest)?;
+        } else {
+            std::fs::copy(&path, &dest).map_err(handle_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn move_install(from: &Path, to: &Path) -> Result<(), ProtoError> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Renaming fails across file systems, so fallback to copying
+    if std::fs::rename(from, to).is_err() {
+        copy_install(from, to)?;
+        fs::remove_dir_all(from)?;
+    }
+
+    Ok(())
+}
+
+// https://github.com/nodejs/Release/blob/main/CODENAMES.md
+const LTS_CODENAMES: [(&str, u64); 11] = [
+    ("argon", 4),
+    ("boron", 6),
+    ("carbon", 8),
+    ("dubnium", 10),
+    ("erbium", 12),
+    ("fermium", 14),
+    ("gallium", 16),
+    ("hydrogen", 18),
+    ("iron", 20),
+    ("jod", 22),
+    ("krypton", 24),
+];
+
+/// Find the newest installed version of an LTS line, or of any LTS line for `*`.
+/// Other version managers resolve these from the remote release index, so rely on
+/// known codenames instead, as migrating should not require network access.
+fn resolve_installed_lts_version(name: &str, manifest: &Manifest) -> Option<String> {
+    let majors = LTS_CODENAMES
+        .iter()
+        .filter(|(codename, _)| name == "*" || name.eq_ignore_ascii_case(codename))
+        .map(|(_, major)| *major)
+        .collect::<Vec<_>>();
+
+    manifest
+        .installed_versions
+        .iter()
+        .filter_map(|version| Version::parse(version).ok())
+        .filter(|version| majors.contains(&version.major))
+        .max()
+        .map(|version| version.to_string())
+}
+
+/// Resolve a default or alias version to an installed version.
+fn resolve_installed_version(version: &str, manifest: &Manifest) -> Option<String> {
+    let version = version.trim();
+
+    if version.is_empty() {
+        return None;
+    }
+
+    if let Some(name) = version
+        .strip_prefix("lts/")
+        .or_else(|| version.strip_prefix("lts-"))
+    {
+        return resolve_installed_lts_version(name, manifest);
+    }
+
+    let version = match version {
+        // Latest installed version
+        "node" | "stable" | "latest" => "*",
+        version => version,
+    };
+
+    expand_detected_version(&remove_v_prefix(version), manifest)
+        .ok()
+        .flatten()
+        .filter(|resolved| manifest.installed_versions.contains(resolved))
+}
+
+pub async fn migrate(from: MigrateSource, move_files: bool) -> SystemResult {
+    let toolchain = match from {
+        MigrateSource::Asdf => find_asdf()?,
+        MigrateSource::Fnm => find_fnm()?,
+        MigrateSource::Nvm => find_nvm()?,
+        MigrateSource::Volta => find_volta()?,
+    };
+
+    if toolchain.installs.is_empty() {
+        info!("No Node.js installs found to migrate from {}", from);
+
+        return Ok(());
+    }
+
+    let mut tool = create_tool(&ToolType::Node).await?;
+    let mut migrated = 0;
+
+    for install in &toolchain.installs {
+        tool.set_version(&install.version);
+
+        let install_dir = tool.get_install_dir()?;
+
+        if install_dir.exists()
+            || tool
+                .get_manifest()?
+                .installed_versions
+                .contains(&install.version)
+        {
+            info!(
+                "{} {} has already been installed, skipping",
+                tool.get_name(),
+                install.version
+            );
+
+            continue;
+        }
+
+        debug!(
+            from = ?install.path,
+            to = ?install_dir,
+            "Migrating {} {}",
+            tool.get_name(),
+            install.version
+        );
+
+        if move_files {
+            move_install(&install.path, &install_dir)?;
+        } else {
+            copy_install(&install.path, &install_dir)?;
+        }
+
+        tool.get_manifest_mut()?
+            .insert_version(&install.version, None)?;
+
+        tool.find_bin_path().await?;
+        tool.setup_shims(true).await?;
+
+        println!(
+            "{} {} {}",
+            color::id(tool.get_id()),
+            color::hash(&install.version),
+            color::path(&install_dir)
+        );
+
+        migrated += 1;
+    }
+
+    // Carry over the default version and aliases
+    let manifest = tool.get_manifest_mut()?;
+
+    if let Some(default_version) = &toolchain.default_version {
+        match resolve_installed_version(default_version, manifest) {
+            Some(version) => {
+                debug!(version, "Setting default version");
+
+                manifest.default_version = Some(version);
+            }
+            None => {
+                warn!(
+                    "Default version {} does not match an installed version, skipping",
+                    default_version
+                );
+            }
+        };
+    }
+
+    for (alias, version) in &toolchain.aliases {
+        if manifest.aliases.contains_key(alias) {
+            debug!(alias, "Alias already exists, skipping");
+            continue;
+        }
+
+        match resolve_installed_version(version, manifest) {
+            Some(version) => {
+                manifest.aliases.insert(alias.to_owned(), version);
+            }
+            None => {
+                warn!(
+                    "Alias {} ({}) does not match an installed version, skipping",
+                    alias, version
+                );
+            }
+        };
+    }
+
+    manifest.save()?;
+
+    info!(
+        "Migrated {} {} versions from {}",
+        migrated,
+        tool.get_name(),
+        from
+    );
+
+    Ok(())
+}
@@ -2,7 +2,13 @@ mod alias;
 mod bin;
 mod clean;
 mod completions;
+mod detect;
+mod doctor;
+mod du;
+mod env;
+mod exec;
 mod global;
+mod info;
 mod install;
 mod install_all;
 mod install_global;
@@ -10,9 +16,13 @@ mod list;
 mod list_global;
 mod list_remote;
 mod local;
+mod migrate;
 mod plugins;
 mod run;
+mod schema;
 mod setup;
+mod shims;
+mod status;
 mod unalias;
 mod uninstall;
 mod upgrade;
@@ -21,7 +31,13 @@ pub use alias::*;
 pub use bin::*;
 pub use clean::*;
 pub use completions::*;
+pub use detect::*;
+pub use doctor::*;
+pub use du::*;
+pub use env::*;
+pub use exec::*;
 pub use global::*;
+pub use info::*;
 pub use install::*;
 pub use install_all::*;
 pub use install_global::*;
@@ -29,9 +45,13 @@ pub use list::*;
 pub use list_global::*;
 pub use list_remote::*;
 pub use local::*;
+pub use migrate::*;
 pub use plugins::*;
 pub use run::*;
+pub use schema::*;
 pub use setup::*;
+pub use shims::*;
+pub use status::*;
 pub use unalias::*;
 pub use uninstall::*;
 pub use upgrade::*;
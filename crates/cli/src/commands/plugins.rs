@@ -1,12 +1,21 @@
-use crate::tools::create_plugin_from_locator;
+use crate::helpers::format_time_ago;
+use crate::tools::{create_plugin_from_locator, find_plugin_locator, ToolType};
 use miette::IntoDiagnostic;
-use proto_core::{color, Proto, ToolsConfig, UserConfig};
+use proto_core::{
+    color, get_modified_time, get_root, Proto, ProtoError, ToolsConfig, UserConfig,
+    USER_CONFIG_NAME,
+};
+use proto_schema_plugin::{SchemaPlugin, SchemaToolType};
+use proto_wasm_plugin::WasmPlugin;
 use rustc_hash::FxHashMap;
 use serde::Serialize;
 use starbase::SystemResult;
 use starbase_utils::json;
-use tracing::debug;
-use warpgate::PluginLocator;
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::{debug, info};
+use warpgate::{PluginLoader, PluginLocator};
 
 fn render_entry<V: AsRef<str>>(label: &str, value: V) {
     println!(
@@ -82,3 +91,235 @@ pub async fn plugins(json: bool) -> SystemResult {
 
     Ok(())
 }
+
+/// Whether the locator points to the latest version of a plugin,
+/// and is therefore only cached temporarily.
+fn is_latest(locator: &PluginLocator) -> bool {
+    match locator {
+        PluginLocator::SourceFile { .. } => false,
+        PluginLocator::SourceUrl { url } => url.contains("latest"),
+        PluginLocator::GitHub(github) => github.tag.as_deref().unwrap_or("latest") == "latest",
+        PluginLocator::Wapm(wapm) => wapm.version.as_deref().unwrap_or("latest") == "latest",
+    }
+}
+
+fn validate_plugin_id(id: &str) -> Result<(), ProtoError> {
+    if !matches!(ToolType::from_str(id)?, ToolType::Plugin(_)) {
+        return Err(ProtoError::InvalidPluginId(id.to_owned()));
+    }
+
+    Ok(())
+}
+
+pub async fn plugins_add(id: String, locator: PluginLocator, global: bool) -> SystemResult {
+    validate_plugin_id(&id)?;
+
+    let config_path = if global {
+        UserConfig::update_plugin(&id, Some(&locator))?;
+
+        get_root()?.join(USER_CONFIG_NAME)
+    } else {
+        let mut config = ToolsConfig::load_from(env::current_dir().into_diagnostic()?)?;
+        config.plugins.insert(id.clone(), locator);
+        config.save()?;
+
+        config.path
+    };
+
+    info!(
+        "Added plugin {} to config {}",
+        color::id(id),
+        color::path(config_path)
+    );
+
+    Ok(())
+}
+
+pub async fn plugins_remove(id: String, global: bool) -> SystemResult {
+    let (removed, config_path) = if global {
+        (
+            UserConfig::update_plugin(&id, None)?,
+            get_root()?.join(USER_CONFIG_NAME),
+        )
+    } else {
+        let mut config = ToolsConfig::load_from(env::current_dir().into_diagnostic()?)?;
+        let removed = config.plugins.remove(&id).is_some();
+
+        if removed {
+            config.save()?;
+        }
+
+        (removed, config.path)
+    };
+
+    if !removed {
+        return Err(ProtoError::PluginNotConfigured(id, config_path))?;
+    }
+
+    info!(
+        "Removed plugin {} from config {}",
+        color::id(id),
+        color::path(config_path)
+    );
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+pub struct PluginMetadata {
+    name: String,
+    type_of: String,
+    env_vars: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct PluginInfo {
+    id: String,
+    locator: PluginLocator,
+    path: PathBuf,
+    /// Timestamp of when the plugin was downloaded, or `None` for local files.
+    fetched_at: Option<u128>,
+    metadata: PluginMetadata,
+}
+
+pub async fn plugins_info(id: String, json: bool) -> SystemResult {
+    validate_plugin_id(&id)?;
+
+    let proto = Proto::new()?;
+    let locator = find_plugin_locator(&id)?;
+    let tool = create_plugin_from_locator(&id, &proto, &locator).await?;
+    let path = tool
+        .get_plugin_source()
+        .map(|source| source.path.clone())
+        .unwrap_or_default();
+
+    let metadata = if let Some(plugin) = tool.as_any().downcast_ref::<WasmPlugin>() {
+        let metadata = plugin.get_metadata()?;
+
+        PluginMetadata {
+            name: metadata.name,
+            type_of: format!("{:?}", metadata.type_of),
+            env_vars: metadata.env_vars,
+        }
+    } else if let Some(plugin) = tool.as_any().downcast_ref::<SchemaPlugin>() {
+        PluginMetadata {
+            name: plugin.schema.name.clone(),
+            type_of: match plugin.schema.type_of {
+                SchemaToolType::Language => "Language",
+                SchemaToolType::DependencyManager => "DependencyManager",
+                SchemaToolType::Cli => "CLI",
+            }
+            .to_owned(),
+            env_vars: vec![],
+        }
+    } else {
+        PluginMetadata {
+            name: tool.get_name(),
+            type_of: String::new(),
+            env_vars: vec![],
+        }
+    };
+
+    let info = PluginInfo {
+        fetched_at: if matches!(locator, PluginLocator::SourceFile { .. }) {
+            None
+        } else {
+            get_modified_time(&path).map(|time| time.as_millis())
+        },
+        id,
+        locator,
+        path,
+        metadata,
+    };
+
+    if json {
+        println!("{}", json::to_string_pretty(&info).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    println!(
+        "{} {} {}",
+        color::id(&info.id),
+        color::muted("-"),
+        info.metadata.name
+    );
+
+    render_entry("Locator", info.locator.to_string());
+    render_entry(
+        if info.fetched_at.is_some() {
+            "Cached file"
+        } else {
+            "Source file"
+        },
+        color::path(&info.path),
+    );
+
+    if let Some(fetched_at) = info.fetched_at {
+        render_entry("Last fetched", format_time_ago(fetched_at));
+    }
+
+    if !info.metadata.type_of.is_empty() {
+        render_entry("Type", &info.metadata.type_of);
+    }
+
+    if !info.metadata.env_vars.is_empty() {
+        render_entry("Env vars", info.metadata.env_vars.join(", "));
+    }
+
+    Ok(())
+}
+
+pub async fn plugins_refresh(id: Option<String>) -> SystemResult {
+    let mut plugins = vec![];
+
+    if let Some(id) = id {
+        validate_plugin_id(&id)?;
+
+        let locator = find_plugin_locator(&id)?;
+        plugins.push((id, locator));
+    } else {
+        let user_config = UserConfig::load()?;
+        let mut tools_config = ToolsConfig::load_upwards()?;
+        tools_config.inherit_builtin_plugins();
+
+        let mut all_plugins = FxHashMap::default();
+        all_plugins.extend(user_config.plugins);
+        all_plugins.extend(tools_config.plugins);
+
+        plugins.extend(all_plugins);
+        plugins.sort_by(|a, d| a.0.cmp(&d.0));
+    }
+
+    let proto = Proto::new()?;
+    let mut loader = PluginLoader::new(&proto.plugins_dir, &proto.temp_dir);
+    loader.set_refresh(true);
+
+    let mut refreshed = 0;
+
+    for (id, locator) in plugins {
+        if !is_latest(&locator) {
+            debug!(plugin = &id, locator = %locator, "Plugin is not using a latest version, skipping");
+            continue;
+        }
+
+        let path = loader.load_plugin(&id, &locator).await.map_err(|error| {
+            ProtoError::PluginLoadFailed {
+                id: id.clone(),
+                error,
+            }
+        })?;
+
+        println!("{} {}", color::id(&id), color::path(path));
+
+        refreshed += 1;
+    }
+
+    if refreshed == 0 {
+        info!("No plugins using a latest version to refresh");
+    } else {
+        info!("Refreshed {} plugins", refreshed);
+    }
+
+    Ok(())
+}
@@ -1,7 +1,12 @@
 use crate::commands::install::install;
 use crate::hooks::node as node_hooks;
 use crate::tools::{create_tool, ToolType};
-use proto_core::{color, detect_version, ProtoError, UserConfig};
+use miette::IntoDiagnostic;
+use proto_core::{
+    color, detect_version_with_trace, DetectCache, DetectCacheEntry, ProtoError, ToolsConfig,
+    UserConfig,
+};
+use rustc_hash::FxHashMap;
 use starbase::SystemResult;
 use std::env;
 use std::process::exit;
@@ -19,11 +24,51 @@ pub async fn run(
     args: Vec<String>,
 ) -> SystemResult {
     let mut tool = create_tool(&tool_type).await?;
-    let version = detect_version(&tool, forced_version).await?;
+    let working_dir = env::current_dir().into_diagnostic()?;
+    let mut detect_cache = DetectCache::load_for(tool.get_id())?;
+
+    // Skip detection, resolution, and loading configs when the
+    // version and environment for this directory has been cached
+    let cached_entry = if forced_version.is_none() {
+        detect_cache
+            .get(&working_dir, tool.get_manifest()?)
+            .cloned()
+    } else {
+        None
+    };
+
+    let mut tools_config = None;
+    let mut detect_trace = None;
+
+    let version = if let Some(entry) = &cached_entry {
+        tool.set_version(&entry.version);
+        entry.version.clone()
+    } else {
+        let trace = detect_version_with_trace(&tool, forced_version).await?;
+
+        let Some(version) = trace.version.clone() else {
+            return Err(ProtoError::VersionDetectFailed(tool.get_id().to_owned()))?;
+        };
+
+        tools_config = Some(ToolsConfig::load_upwards()?);
+        detect_trace = Some(trace);
+        version
+    };
+
     let user_config = UserConfig::load()?;
 
     if !tool.is_setup(&version).await? {
-        if !user_config.auto_install {
+        if tools_config.is_none() {
+            tools_config = Some(ToolsConfig::load_upwards()?);
+        }
+
+        let auto_install = tools_config
+            .as_ref()
+            .and_then(|config| config.get_tool_config(tool.get_id()))
+            .and_then(|config| config.auto_install)
+            .unwrap_or(user_config.auto_install);
+
+        if !auto_install {
             return Err(ProtoError::MissingToolForRun(
                 tool.get_name(),
                 version.to_owned(),
@@ -48,6 +93,34 @@ pub async fn run(
 
     let resolved_version = tool.get_resolved_version().to_owned();
 
+    let tool_env = if let Some(entry) = cached_entry {
+        FxHashMap::from_iter(entry.env)
+    } else {
+        let tools_config = match tools_config {
+            Some(config) => config,
+            None => ToolsConfig::load_upwards()?,
+        };
+        let tool_env = tools_config.get_tool_env(tool.get_id());
+
+        if let Some(trace) = detect_trace {
+            if let Some(entry) = DetectCacheEntry::from_trace(
+                &tool,
+                &trace,
+                &working_dir,
+                &resolved_version,
+                &tool_env,
+            )? {
+                detect_cache.insert(&working_dir, entry);
+
+                // Ignore errors in case of race conditions,
+                // the version will be detected again next time
+                let _ = detect_cache.save();
+            }
+        }
+
+        tool_env
+    };
+
     // Update the last used timestamp
     if env::var("PROTO_SKIP_USED_AT").is_err() {
         let manifest = tool.get_manifest_mut()?;
@@ -69,10 +142,7 @@ pub async fn run(
         if alt_bin_path.exists() {
             bin_path = alt_bin_path;
         } else {
-            return Err(ProtoError::Message(format!(
-                "Alternate binary {} does not exist.",
-                color::file(&alt_bin)
-            )))?;
+            return Err(ProtoError::MissingAltBin(alt_bin))?;
         }
     } else if let Some(shim_path) = tool.get_shim_path() {
         bin_path = shim_path.to_path_buf();
@@ -88,6 +158,11 @@ pub async fn run(
     }
 
     // Run the command
+    let bin = bin_path.to_string_lossy().to_string();
+    let handle_error = |error| ProtoError::ExecuteFailed {
+        bin: bin.clone(),
+        error,
+    };
     let mut command = if is_windows_script(bin_path.to_str().unwrap_or_default()) {
         let mut cmd = Command::new("powershell.exe");
         cmd.arg("-C").arg(bin_path);
@@ -98,6 +173,7 @@ pub async fn run(
 
     let status = command
         .args(&args)
+        .envs(tool_env)
         .env(
             format!("PROTO_{}_VERSION", tool.get_id().to_uppercase()),
             tool.get_resolved_version(),
@@ -107,10 +183,10 @@ pub async fn run(
             tool.get_bin_path()?.to_string_lossy().to_string(),
         )
         .spawn()
-        .map_err(|e| ProtoError::Message(e.to_string()))?
+        .map_err(handle_error)?
         .wait()
         .await
-        .map_err(|e| ProtoError::Message(e.to_string()))?;
+        .map_err(handle_error)?;
 
     if !status.success() {
         exit(status.code().unwrap_or(1));
@@ -0,0 +1,41 @@
+use crate::app::SchemaKind;
+use miette::IntoDiagnostic;
+use proto_core::{ToolsConfig, UserConfig};
+use proto_schema_plugin::Schema;
+use schemars::gen::SchemaSettings;
+use schemars::schema::{InstanceType, SchemaObject};
+use starbase::SystemResult;
+use starbase_utils::json;
+
+pub async fn schema(kind: SchemaKind) -> SystemResult {
+    // Optional fields are omitted in TOML, they can't be null
+    let mut generator = SchemaSettings::draft07()
+        .with(|settings| {
+            settings.option_add_null_type = false;
+            settings.option_nullable = false;
+        })
+        .into_generator();
+
+    let schema = match kind {
+        SchemaKind::Prototools => {
+            let mut schema = generator.root_schema_for::<ToolsConfig>();
+
+            // Tools can also be mapped to a version at the root
+            schema.schema.object().additional_properties = Some(Box::new(
+                SchemaObject {
+                    instance_type: Some(InstanceType::String.into()),
+                    ..SchemaObject::default()
+                }
+                .into(),
+            ));
+
+            schema
+        }
+        SchemaKind::UserConfig => generator.root_schema_for::<UserConfig>(),
+        SchemaKind::Plugin => generator.root_schema_for::<Schema>(),
+    };
+
+    println!("{}", json::to_string_pretty(&schema).into_diagnostic()?);
+
+    Ok(())
+}
@@ -93,7 +93,10 @@ fn do_setup(shell: Shell, bin_dir: PathBuf, print_profile: bool) -> Result<(), P
 
     let output = command
         .output()
-        .map_err(|e| ProtoError::Message(e.to_string()))?;
+        .map_err(|error| ProtoError::ExecuteFailed {
+            bin: "setx".into(),
+            error,
+        })?;
 
     if !output.status.success() {
         warn!("Failed to update PATH");
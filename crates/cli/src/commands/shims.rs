@@ -0,0 +1,254 @@
+use crate::tools::{create_tool, ToolType};
+use miette::IntoDiagnostic;
+use proto_core::{color, Manifest, Proto, ProtoError, ShimRegistry};
+use serde::Serialize;
+use starbase::SystemResult;
+use starbase_utils::{fs, json};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tracing::{debug, info};
+
+#[derive(Serialize)]
+pub struct ShimItem {
+    name: String,
+    /// Path to the shim file, or `None` if it does not exist.
+    path: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alt_bin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_bin: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct LocalShimItem {
+    path: PathBuf,
+    version: Option<String>,
+    exists: bool,
+}
+
+#[derive(Default, Serialize)]
+pub struct ToolShims {
+    shims: Vec<ShimItem>,
+    local_shims: Vec<LocalShimItem>,
+}
+
+fn find_shim_path(registry: &ShimRegistry, name: &str) -> Option<PathBuf> {
+    registry
+        .get_shim_paths(name)
+        .into_iter()
+        .find(|path| path.exists())
+}
+
+fn is_tool_installed(tools_dir: &Path, id: &str) -> Result<bool, ProtoError> {
+    let tool_dir = tools_dir.join(id);
+
+    if !tool_dir.exists() {
+        return Ok(false);
+    }
+
+    Ok(!Manifest::load_from(tool_dir)?.installed_versions.is_empty())
+}
+
+pub async fn shims_list(json: bool) -> SystemResult {
+    let proto = Proto::new()?;
+    let registry = ShimRegistry::load_from(&proto.root)?;
+    let mut tools: BTreeMap<String, ToolShims> = BTreeMap::new();
+
+    for (name, entry) in &registry.shims {
+        tools
+            .entry(entry.tool.clone())
+            .or_default()
+            .shims
+            .push(ShimItem {
+                name: name.to_owned(),
+                path: find_shim_path(&registry, name),
+                alt_bin: entry.alt_bin.clone(),
+                parent_bin: entry.parent_bin.clone(),
+            });
+    }
+
+    for (path, entry) in &registry.local_shims {
+        let path = PathBuf::from(path);
+
+        tools
+            .entry(entry.tool.clone())
+            .or_default()
+            .local_shims
+            .push(LocalShimItem {
+                exists: path.exists(),
+                version: entry.version.clone(),
+                path,
+            });
+    }
+
+    if json {
+        println!("{}", json::to_string_pretty(&tools).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    if tools.is_empty() {
+        info!("No shims have been created");
+
+        return Ok(());
+    }
+
+    for (id, tool) in tools {
+        println!("{}", color::id(id));
+
+        for shim in tool.shims {
+            println!(
+                "  {} {}{}",
+                shim.name,
+                match shim.path {
+                    Some(path) => color::path(path),
+                    None => color::failure("missing"),
+                },
+                match shim.alt_bin {
+                    Some(alt_bin) => color::muted(format!(" ({alt_bin})")),
+                    None => String::new(),
+                }
+            );
+        }
+
+        for shim in tool.local_shims {
+            println!(
+                "  {} {}{}",
+                color::hash(shim.version.as_deref().unwrap_or("local")),
+                color::path(&shim.path),
+                if shim.exists {
+                    String::new()
+                } else {
+                    format!(" {}", color::failure("missing"))
+                }
+            );
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
+
+async fn regen_tool_shims(tool_type: &ToolType) -> miette::Result<bool> {
+    let mut tool = create_tool(tool_type).await?;
+    let installed_versions = tool.get_manifest()?.installed_versions.clone();
+
+    if installed_versions.is_empty() {
+        debug!(tool = tool.get_id(), "No versions installed, skipping");
+
+        return Ok(false);
+    }
+
+    // Local shims are created within each version's install directory
+    for version in &installed_versions {
+        debug!(tool = tool.get_id(), version, "Regenerating shims");
+
+        tool.set_version(version);
+        tool.find_bin_path().await?;
+        tool.setup_shims(true).await?;
+    }
+
+    info!("Regenerated {} shims", tool.get_name());
+
+    Ok(true)
+}
+
+pub async fn shims_regen(tool_type: Option<ToolType>) -> SystemResult {
+    let proto = Proto::new()?;
+    let mut tool_types = vec![];
+
+    if let Some(tool_type) = tool_type {
+        tool_types.push(tool_type);
+    } else if proto.tools_dir.exists() {
+        for entry in fs::read_dir(&proto.tools_dir)? {
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                let id = fs::file_name(entry.path());
+
+                if is_tool_installed(&proto.tools_dir, &id)? {
+                    tool_types.push(ToolType::from_str(&id)?);
+                }
+            }
+        }
+    }
+
+    let mut regenerated = false;
+
+    for tool_type in &tool_types {
+        if regen_tool_shims(tool_type).await? {
+            regenerated = true;
+        }
+    }
+
+    if !regenerated {
+        info!("No installed tools to regenerate shims for");
+    }
+
+    Ok(())
+}
+
+pub async fn shims_prune() -> SystemResult {
+    let proto = Proto::new()?;
+    let mut registry = ShimRegistry::load_from(&proto.root)?;
+    let mut stale_names = vec![];
+    let mut removed = vec![];
+
+    // Global shims that belong to tools that are no longer installed,
+    // or that have been deleted from the file system
+    for (name, entry) in &registry.shims {
+        if !is_tool_installed(&proto.tools_dir, &entry.tool)? {
+            debug!(tool = &entry.tool, shim = name, "Tool is not installed");
+
+            stale_names.push(name.to_owned());
+        } else if find_shim_path(&registry, name).is_none() {
+            debug!(tool = &entry.tool, shim = name, "Shim file does not exist");
+
+            stale_names.push(name.to_owned());
+        }
+    }
+
+    removed.extend(registry.remove_shims(&stale_names)?);
+
+    let mut local_count = registry.local_shims.len();
+
+    if registry.path.exists() {
+        registry.update(|registry| {
+            local_count = registry.local_shims.len();
+
+            registry
+                .local_shims
+                .retain(|path, _| Path::new(path).exists());
+
+            let mut stale_tools = vec![];
+
+            for id in registry.tools.keys() {
+                if !is_tool_installed(&proto.tools_dir, id)? {
+                    stale_tools.push(id.to_owned());
+                }
+            }
+
+            for id in &stale_tools {
+                registry.tools.remove(id);
+            }
+
+            Ok(registry.local_shims.len() != local_count || !stale_tools.is_empty())
+        })?;
+    }
+
+    for path in &removed {
+        println!("{}", color::path(path));
+    }
+
+    if stale_names.is_empty() && registry.local_shims.len() == local_count {
+        info!("No stale shims found");
+    } else {
+        info!(
+            "Pruned {} stale shims, and removed {} files",
+            stale_names.len() + local_count - registry.local_shims.len(),
+            removed.len()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use crate::commands::install_all::{detect_project_tools, ProjectTool, ProjectToolSource};
+use crate::tools::{create_tool, ToolType};
+use miette::IntoDiagnostic;
+use proto_core::{color, ToolsConfig};
+use serde::Serialize;
+use starbase::SystemResult;
+use starbase_utils::json;
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::{debug, info};
+
+#[derive(Serialize)]
+pub struct StatusItem {
+    tool: String,
+    name: String,
+    spec: String,
+    source: ProjectToolSource,
+    source_file: PathBuf,
+    /// The fully resolved version, or `None` if the spec could not be resolved.
+    version: Option<String>,
+    installed: bool,
+    bin_path: Option<PathBuf>,
+}
+
+async fn get_status(id: String, project_tool: ProjectTool) -> miette::Result<StatusItem> {
+    let spec = project_tool.spec.as_str();
+    let mut tool = create_tool(&ToolType::from_str(&id)?).await?;
+    let name = tool.get_name();
+
+    let version = match tool.resolve_version(spec).await {
+        Ok(version) => Some(version),
+        Err(error) => {
+            debug!(tool = &id, spec, "Failed to resolve version: {}", error);
+
+            None
+        }
+    };
+
+    let mut installed = false;
+    let mut bin_path = None;
+
+    if let Some(version) = &version {
+        // Only check the file system, as `is_setup` would also create shims
+        if tool.get_manifest()?.installed_versions.contains(version)
+            && tool.get_install_dir()?.exists()
+        {
+            installed = true;
+
+            if tool.find_bin_path().await.is_ok() {
+                bin_path = tool.get_bin_path().ok().map(|path| path.to_path_buf());
+            }
+        }
+    }
+
+    Ok(StatusItem {
+        tool: id,
+        name,
+        spec: project_tool.spec.clone(),
+        source: project_tool.source,
+        source_file: project_tool.source_file,
+        version,
+        installed,
+        bin_path,
+    })
+}
+
+pub async fn status(json: bool) -> SystemResult {
+    let working_dir = env::current_dir().into_diagnostic()?;
+    let config = ToolsConfig::load_upwards()?;
+    let mut items = vec![];
+
+    for (id, project_tool) in detect_project_tools(&working_dir, &config).await? {
+        items.push(get_status(id, project_tool).await?);
+    }
+
+    if json {
+        println!("{}", json::to_string_pretty(&items).into_diagnostic()?);
+
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        info!("No tools have been configured or detected for this directory");
+
+        return Ok(());
+    }
+
+    for item in items {
+        println!(
+            "{} {}",
+            color::id(&item.tool),
+            match &item.version {
+                Some(version) => color::hash(version),
+                None => color::failure("unresolved"),
+            }
+        );
+
+        println!(
+            "  {} {} from {}",
+            color::muted_light("Requested:"),
+            item.spec,
+            color::path(&item.source_file)
+        );
+
+        match (&item.version, &item.bin_path) {
+            (Some(_), Some(bin_path)) => {
+                println!(
+                    "  {} {}",
+                    color::muted_light("Binary:"),
+                    color::path(bin_path)
+                );
+            }
+            (Some(version), None) if !item.installed => {
+                println!(
+                    "  {} run {} to install",
+                    color::failure("Not installed,"),
+                    color::shell(format!("proto install {} {}", item.tool, version))
+                );
+            }
+            _ => {}
+        };
+
+        println!();
+    }
+
+    Ok(())
+}
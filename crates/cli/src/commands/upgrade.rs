@@ -1,60 +1,270 @@
 use crate::helpers::download_to_temp_with_progress_bar;
-use proto_core::{color, get_bin_dir, get_temp_dir, is_offline, load_git_tags, unpack, ProtoError};
+use proto_core::{
+    color, download_from_url, get_bin_dir, get_native_shim_file_name, get_sha256_hash_of_file,
+    get_temp_dir, is_musl, is_offline, remove_v_prefix, unpack, ProtoError,
+};
 use semver::Version;
 use starbase::SystemResult;
-use starbase_utils::fs;
+use starbase_utils::{fs, json};
 use std::env::consts;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use tracing::{debug, info};
 
-async fn fetch_version() -> Result<String, ProtoError> {
-    let tags = load_git_tags("https://github.com/moonrepo/proto")
-        .await?
-        .into_iter()
-        .filter(|t| t.starts_with('v'))
-        .collect::<Vec<_>>();
+fn get_bin_name(name: &str) -> String {
+    if cfg!(windows) {
+        format!("{name}.exe")
+    } else {
+        name.to_owned()
+    }
+}
+
+#[tracing::instrument]
+async fn fetch_latest_version() -> Result<String, ProtoError> {
+    let url = "https://api.github.com/repos/moonrepo/proto/releases/latest";
+    let handle_error = |error: reqwest::Error| ProtoError::Http {
+        url: url.to_owned(),
+        error,
+    };
 
-    let latest = tags.last().unwrap().strip_prefix('v').unwrap().to_owned();
+    // Only published releases have artifacts, unlike tags
+    let release: json::JsonValue = reqwest::Client::new()
+        .get(url)
+        .header("User-Agent", "proto")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(handle_error)?
+        .json()
+        .await
+        .map_err(handle_error)?;
+
+    let Some(latest) = release["tag_name"].as_str().map(remove_v_prefix) else {
+        return Err(ProtoError::UpgradeUnknownLatestVersion);
+    };
 
     debug!("Found latest version {}", color::id(&latest));
 
     Ok(latest)
 }
 
-pub async fn upgrade() -> SystemResult {
+fn get_target_triple() -> Result<String, ProtoError> {
+    Ok(match (consts::OS, consts::ARCH) {
+        ("linux", arch) => {
+            if is_musl() {
+                format!("{arch}-unknown-linux-musl")
+            } else {
+                format!("{arch}-unknown-linux-gnu")
+            }
+        }
+        ("macos", arch) => format!("{arch}-apple-darwin"),
+        ("windows", "x86_64") => "x86_64-pc-windows-msvc".to_owned(),
+        (_, arch) => {
+            return Err(ProtoError::UnsupportedArchitecture(
+                "proto".to_owned(),
+                arch.to_owned(),
+            ));
+        }
+    })
+}
+
+/// Verify the downloaded archive against the checksum published alongside it,
+/// which is in the format of `<hash>  <file>`.
+fn verify_checksum(archive_file: &Path, checksum_file: &Path) -> Result<(), ProtoError> {
+    let checksum = fs::read_file(checksum_file)?;
+    let expected = checksum.split_whitespace().next().unwrap_or_default();
+    let actual = get_sha256_hash_of_file(archive_file)?;
+
+    debug!(expected, actual, "Verifying checksum of download");
+
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(ProtoError::VerifyInvalidChecksum(
+            archive_file.to_path_buf(),
+            checksum_file.to_path_buf(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Run the binary with `--version`, to ensure that it can execute on this machine.
+fn smoke_test(bin_path: &Path, expected_version: Option<&str>) -> Result<(), ProtoError> {
+    debug!(bin = ?bin_path, "Running smoke test");
+
+    let output = Command::new(bin_path)
+        .arg("--version")
+        .output()
+        .map_err(|error| ProtoError::ExecuteFailed {
+            bin: bin_path.to_string_lossy().to_string(),
+            error,
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if !output.status.success() || expected_version.is_some_and(|version| !stdout.contains(version))
+    {
+        return Err(ProtoError::UpgradeSmokeTestFailed(
+            bin_path.to_path_buf(),
+            format!(
+                "{}{}",
+                stdout.trim(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Swap the current file with its previous version, so that the swap itself can be
+/// reverted. Renaming (instead of writing) works while the binary is running.
+fn swap_files(path: &Path, old_path: &Path, swap_path: &Path) -> Result<(), ProtoError> {
+    if path.exists() {
+        fs::rename(path, swap_path)?;
+    }
+
+    fs::rename(old_path, path)?;
+
+    if swap_path.exists() {
+        fs::rename(swap_path, old_path)?;
+    }
+
+    Ok(())
+}
+
+pub async fn rollback() -> SystemResult {
+    let bin_dir = get_bin_dir()?;
+    let bin_path = bin_dir.join(get_bin_name("proto"));
+    let old_bin_path = bin_dir.join(get_bin_name("proto-old"));
+
+    if !old_bin_path.exists() {
+        return Err(ProtoError::UpgradeMissingRollback(old_bin_path))?;
+    }
+
+    smoke_test(&old_bin_path, None)?;
+
+    swap_files(
+        &bin_path,
+        &old_bin_path,
+        &bin_dir.join(get_bin_name("proto-swap")),
+    )?;
+
+    // The shim is only backed up by upgrades that replaced it
+    let shim_path = bin_dir.join(get_native_shim_file_name("proto-shim"));
+    let old_shim_path = bin_dir.join(get_native_shim_file_name("proto-shim-old"));
+
+    if old_shim_path.exists() {
+        swap_files(
+            &shim_path,
+            &old_shim_path,
+            &bin_dir.join(get_native_shim_file_name("proto-shim-swap")),
+        )?;
+    }
+
+    info!("Rolled back proto to the previous version!");
+
+    Ok(())
+}
+
+fn replace_binary(new_bin_path: &Path, bin_dir: &Path) -> Result<(), ProtoError> {
+    let bin_path = bin_dir.join(get_bin_name("proto"));
+    let old_bin_path = bin_dir.join(get_bin_name("proto-old"));
+
+    // Keep the current binary for rollbacks
+    if bin_path.exists() {
+        if old_bin_path.exists() {
+            fs::remove_file(&old_bin_path)?;
+        }
+
+        fs::rename(&bin_path, &old_bin_path)?;
+    }
+
+    let result =
+        fs::copy_file(new_bin_path, &bin_path).and_then(|_| fs::update_perms(&bin_path, None));
+
+    if let Err(error) = result {
+        // Restore the previous binary so that proto is still usable
+        if old_bin_path.exists() {
+            let _ = fs::rename(&old_bin_path, &bin_path);
+        }
+
+        return Err(error.into());
+    }
+
+    // Native shims are hard linked to this binary, so replace the file instead
+    // of writing to it, as it may be in use, and keep it for rollbacks
+    let shim_name = get_native_shim_file_name("proto-shim");
+    let shim_temp_path = new_bin_path.parent().unwrap().join(&shim_name);
+    let shim_path = bin_dir.join(&shim_name);
+    let old_shim_path = bin_dir.join(get_native_shim_file_name("proto-shim-old"));
+
+    if shim_temp_path.exists() {
+        if old_shim_path.exists() {
+            fs::remove_file(&old_shim_path)?;
+        }
+
+        if shim_path.exists() {
+            fs::rename(&shim_path, &old_shim_path)?;
+        }
+
+        fs::copy_file(shim_temp_path, &shim_path)?;
+        fs::update_perms(&shim_path, None)?;
+    }
+
+    Ok(())
+}
+
+pub async fn upgrade(version: Option<String>, rollback_version: bool) -> SystemResult {
+    if rollback_version {
+        return rollback().await;
+    }
+
+    let version = version.map(|version| remove_v_prefix(&version));
+
+    if let Some(version) = &version {
+        Version::parse(version).map_err(|error| ProtoError::Semver {
+            version: version.to_owned(),
+            error,
+        })?;
+    }
+
     if is_offline() {
-        return Err(ProtoError::Message(
-            "Upgrading proto requires an internet connection!".into(),
-        ))?;
+        return Err(ProtoError::InternetConnectionRequired)?;
     }
 
-    let version = env!("CARGO_PKG_VERSION");
-    let new_version = fetch_version().await?;
+    let current_version = env!("CARGO_PKG_VERSION");
+    let new_version = match &version {
+        Some(version) => version.to_owned(),
+        None => fetch_latest_version().await?,
+    };
+
+    let parsed_version = Version::parse(&new_version).map_err(|error| ProtoError::Semver {
+        version: new_version.clone(),
+        error,
+    })?;
 
     debug!(
-        "Comparing latest version {} to local version {}",
+        "Comparing target version {} to local version {}",
         color::id(&new_version),
-        color::id(version),
+        color::id(current_version),
     );
 
-    if Version::parse(&new_version).unwrap() <= Version::parse(version).unwrap() {
+    let parsed_current_version = Version::parse(current_version).unwrap();
+
+    if version.is_none() && parsed_version <= parsed_current_version {
         info!("You're already on the latest version of proto!");
 
         return Ok(());
     }
 
+    if parsed_version == parsed_current_version {
+        info!("You're already on version {} of proto!", new_version);
+
+        return Ok(());
+    }
+
     // Determine the download file based on target
-    let target = match (consts::OS, consts::ARCH) {
-        ("linux", arch) => format!("{arch}-unknown-linux-gnu"),
-        ("macos", arch) => format!("{arch}-apple-darwin"),
-        ("windows", "x86_64") => "x86_64-pc-windows-msvc".to_owned(),
-        (_, arch) => {
-            return Err(ProtoError::UnsupportedArchitecture(
-                "proto".to_owned(),
-                arch.to_owned(),
-            ))?;
-        }
-    };
+    let target = get_target_triple()?;
     let target_ext = if cfg!(windows) { "zip" } else { "tar.xz" };
     let target_file = format!("proto_cli-{target}");
 
@@ -67,47 +277,66 @@ pub async fn upgrade() -> SystemResult {
     );
     let temp_file = download_to_temp_with_progress_bar(&download_url, &download_file).await?;
     let temp_dir = get_temp_dir()?;
+    let checksum_file = temp_dir.join(format!("{download_file}.sha256"));
+    let unpack_dir = temp_dir.join(format!("proto-upgrade-{new_version}"));
 
-    // Unpack the downloaded file
-    unpack(temp_file, &temp_dir, None)?;
+    let cleanup = || {
+        let _ = fs::remove_file(&temp_file);
+        let _ = fs::remove_file(&checksum_file);
+        let _ = fs::remove_dir_all(&unpack_dir);
+    };
 
-    // Move the old binary
-    let bin_dir = get_bin_dir()?;
-    let bin_name = if cfg!(windows) { "proto.exe" } else { "proto" };
-    let bin_path = bin_dir.join(bin_name);
+    // Verify the download before unpacking it
+    let verified = match download_from_url(format!("{download_url}.sha256"), &checksum_file).await {
+        Ok(_) => verify_checksum(&temp_file, &checksum_file),
+        Err(error) => Err(error),
+    };
 
-    if bin_path.exists() {
-        fs::rename(
-            &bin_path,
-            bin_dir.join(if cfg!(windows) {
-                "proto-old.exe"
-            } else {
-                "proto-old"
-            }),
-        )?;
+    if let Err(error) = verified {
+        cleanup();
+
+        return Err(error)?;
     }
 
-    // Move the new binary to the bins directory
-    let lookup_paths = vec![
-        PathBuf::from(target_file).join(bin_name),
-        PathBuf::from(bin_name),
-    ];
+    // Unpack the downloaded file into an isolated directory
+    if unpack_dir.exists() {
+        fs::remove_dir_all(&unpack_dir)?;
+    }
 
-    for lookup_path in lookup_paths {
-        let temp_path = temp_dir.join(lookup_path);
+    if let Err(error) = unpack(&temp_file, &unpack_dir, None) {
+        cleanup();
 
-        if temp_path.exists() {
-            fs::copy_file(temp_path, &bin_path)?;
-            fs::update_perms(&bin_path, None)?;
+        return Err(error)?;
+    }
 
-            info!("Upgraded proto to v{}!", new_version);
+    let bin_name = get_bin_name("proto");
+    let Some(new_bin_path) = [
+        PathBuf::from(&target_file).join(&bin_name),
+        PathBuf::from(&bin_name),
+    ]
+    .into_iter()
+    .map(|lookup_path| unpack_dir.join(lookup_path))
+    .find(|path| path.exists()) else {
+        cleanup();
 
-            return Ok(());
-        }
-    }
+        return Err(ProtoError::UpgradeMissingBinary(bin_name))?;
+    };
+
+    // Ensure the new binary runs before replacing the current one
+    let result = fs::update_perms(&new_bin_path, None)
+        .map_err(ProtoError::from)
+        .and_then(|_| smoke_test(&new_bin_path, Some(&new_version)))
+        .and_then(|_| replace_binary(&new_bin_path, &get_bin_dir()?));
+
+    cleanup();
+
+    result?;
+
+    info!(
+        "Upgraded proto to v{}! Run {} to revert.",
+        new_version,
+        color::shell("proto upgrade --rollback")
+    );
 
-    Err(ProtoError::Message(format!(
-        "Failed to upgrade proto, {} could not be located after download!",
-        color::shell("proto.exe")
-    )))?
+    Ok(())
 }
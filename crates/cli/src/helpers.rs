@@ -1,12 +1,92 @@
+use crate::tools::ToolType;
 use futures::StreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
-use proto_core::{get_temp_dir, ProtoError};
+use indicatif::{HumanDuration, ProgressBar, ProgressStyle};
+use proto_core::{get_temp_dir, now, Manifest, ProtoError, ShimRegistry, Tool};
+use semver::{Version, VersionReq};
 use starbase_utils::fs;
 use std::cmp;
 use std::env;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::time::Duration;
+use warpgate::WarpgateError;
+
+/// Return the directory to prepend to `PATH` for running a tool. Tools that are ran
+/// through a parent binary (npm, pnpm, and yarn through node) are installed as scripts
+/// that can't be executed directly, so the shims directory is used instead, as shims
+/// respect the `PROTO_<TOOL>_VERSION` environment variable.
+pub fn get_tool_bin_dir<'l, T: Tool<'l> + ?Sized>(tool: &T) -> Result<Option<PathBuf>, ProtoError> {
+    let registry = ShimRegistry::load_default()?;
+
+    if registry
+        .shims
+        .get(tool.get_id())
+        .is_some_and(|shim| shim.parent_bin.is_some())
+    {
+        return Ok(Some(registry.get_bin_dir()));
+    }
+
+    Ok(tool.get_bin_path()?.parent().map(Path::to_path_buf))
+}
+
+/// Find the installed version that a version, alias, or requirement would use,
+/// without resolving against the remote.
+pub fn resolve_installed_version(manifest: &Manifest, candidate: &str) -> Option<String> {
+    let candidate = manifest
+        .aliases
+        .get(candidate)
+        .map(|alias| alias.as_str())
+        .unwrap_or(candidate);
+
+    if manifest.installed_versions.contains(candidate) {
+        return Some(candidate.to_owned());
+    }
+
+    let req = VersionReq::parse(candidate).ok()?;
+
+    manifest
+        .installed_versions
+        .iter()
+        .filter_map(|version| Version::parse(version).ok())
+        .filter(|version| req.matches(version))
+        .max()
+        .map(|version| version.to_string())
+}
+
+/// Map an error to a stable exit code, see [`ProtoError::exit_code`].
+pub fn get_exit_code(error: &miette::Report) -> i32 {
+    if let Some(error) = error.downcast_ref::<ProtoError>() {
+        return error.exit_code();
+    }
+
+    if error.downcast_ref::<WarpgateError>().is_some() {
+        return 4;
+    }
+
+    1
+}
+
+/// Parse a tool argument in the format of `<tool>` or `<tool>@<version>`.
+pub fn parse_tool_spec(arg: &str) -> Result<(ToolType, Option<String>), ProtoError> {
+    let (id, version) = match arg.split_once('@') {
+        Some((id, version)) => (id, Some(version.to_owned())),
+        None => (arg, None),
+    };
+
+    if version.as_ref().is_some_and(|version| version.is_empty()) {
+        return Err(ProtoError::InvalidToolSpec(id.to_owned()));
+    }
+
+    Ok((ToolType::from_str(id)?, version))
+}
+
+/// Format a timestamp in milliseconds as the time elapsed since, like "3 days ago".
+pub fn format_time_ago(timestamp: u128) -> String {
+    let elapsed = Duration::from_millis(now().saturating_sub(timestamp) as u64);
+
+    format!("{} ago", HumanDuration(elapsed))
+}
 
 pub fn enable_progress_bars() {
     env::remove_var("PROTO_NO_PROGRESS");
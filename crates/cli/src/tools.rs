@@ -5,7 +5,7 @@ use proto_rust as rust;
 use proto_schema_plugin as schema_plugin;
 use proto_wasm_plugin as wasm_plugin;
 use starbase_utils::toml;
-use std::{env, path::Path, str::FromStr};
+use std::{env, str::FromStr};
 use strum::EnumIter;
 use tracing::debug;
 use warpgate::{PluginLoader, PluginLocator};
@@ -52,50 +52,53 @@ pub async fn create_plugin_from_locator(
     let plugin_path = PluginLoader::new(&proto.plugins_dir, &proto.temp_dir)
         .load_plugin(plugin, locator)
         .await
-        .map_err(|e| ProtoError::Message(e.to_string()))?;
+        .map_err(|error| ProtoError::PluginLoadFailed {
+            id: plugin.to_owned(),
+            error,
+        })?;
     let is_toml = plugin_path
         .extension()
         .map(|e| e == "toml")
         .unwrap_or(false);
 
+    let source = PluginSource {
+        locator: locator.to_owned(),
+        path: plugin_path.clone(),
+    };
+
     if is_toml {
         debug!(source = ?plugin_path, "Loading TOML plugin");
 
-        return Ok(Box::new(schema_plugin::SchemaPlugin::new(
+        let mut tool = schema_plugin::SchemaPlugin::new(
             proto,
             plugin.to_owned(),
-            toml::read_file(plugin_path)?,
-        )));
+            toml::read_file(&plugin_path)?,
+        );
+        tool.source = Some(source);
+
+        return Ok(Box::new(tool));
     }
 
     debug!(source = ?plugin_path, "Loading WASM plugin");
 
-    Ok(Box::new(wasm_plugin::WasmPlugin::new(
-        proto,
-        plugin.to_owned(),
-        plugin_path,
-    )?))
+    let mut tool = wasm_plugin::WasmPlugin::new(proto, plugin.to_owned(), &plugin_path)?;
+    tool.source = Some(source);
+
+    Ok(Box::new(tool))
 }
 
-pub async fn create_plugin_tool(
-    plugin: &str,
-    proto: Proto,
-) -> Result<Box<dyn Tool<'static>>, ProtoError> {
+/// Find the locator for a plugin, from the closest `.prototools`, the user's config,
+/// or the builtin plugins, in that order.
+pub fn find_plugin_locator(plugin: &str) -> Result<PluginLocator, ProtoError> {
     let mut locator = None;
 
     // Traverse upwards checking each `.prototools` for a plugin
     if let Ok(working_dir) = env::current_dir() {
-        let mut current_dir: Option<&Path> = Some(&working_dir);
-
-        while let Some(dir) = &current_dir {
-            let tools_config = ToolsConfig::load_from(dir)?;
-
-            if let Some(maybe_locator) = tools_config.plugins.get(plugin) {
+        for entry in ConfigTraversal::new().traverse_from(working_dir) {
+            if let Some(maybe_locator) = entry?.config.plugins.get(plugin) {
                 locator = Some(maybe_locator.to_owned());
                 break;
             }
-
-            current_dir = dir.parent();
         }
     }
 
@@ -117,9 +120,14 @@ pub async fn create_plugin_tool(
         }
     }
 
-    let Some(locator) = locator else {
-        return Err(ProtoError::MissingPlugin(plugin.to_owned()));
-    };
+    locator.ok_or_else(|| ProtoError::MissingPlugin(plugin.to_owned()))
+}
+
+pub async fn create_plugin_tool(
+    plugin: &str,
+    proto: Proto,
+) -> Result<Box<dyn Tool<'static>>, ProtoError> {
+    let locator = find_plugin_locator(plugin)?;
 
     create_plugin_from_locator(plugin, proto, locator).await
 }
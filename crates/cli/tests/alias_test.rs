@@ -3,6 +3,7 @@ mod utils;
 use proto_core::Manifest;
 use rustc_hash::FxHashMap;
 use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json::{self, JsonValue};
 use utils::*;
 
 #[test]
@@ -117,3 +118,36 @@ fn errors_when_aliasing_self() {
 
     assert.stderr(predicate::str::contains("Cannot map an alias to itself."));
 }
+
+#[test]
+fn prints_aliases_as_json() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.arg("alias")
+        .arg("node")
+        .arg("work")
+        .arg("18.0.0")
+        .assert()
+        .success();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .arg("alias")
+        .arg("node")
+        .arg("example")
+        .arg("19.0.0")
+        .arg("--json")
+        .assert();
+
+    let output: JsonValue = json::from_str(&output_to_string(&assert.get_output().stdout))
+        .expect("Output should be JSON");
+
+    assert_eq!(output["tool"], "node");
+    assert_eq!(output["alias"], "example");
+    assert_eq!(output["version"], "19.0.0");
+    assert_eq!(
+        output["aliases"],
+        json::json!({ "example": "19.0.0", "work": "18.0.0" })
+    );
+}
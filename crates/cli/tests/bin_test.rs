@@ -1,6 +1,8 @@
 mod utils;
 
+use proto_core::Manifest;
 use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json::{self, JsonValue};
 use utils::*;
 
 #[test]
@@ -77,3 +79,31 @@ fn returns_path_for_plugin() {
         ));
     }
 }
+
+#[test]
+fn returns_json_if_installed() {
+    let temp = create_empty_sandbox();
+    temp.create_file("tools/node/18.0.0/bin/node", "");
+    temp.create_file("tools/node/18.0.0/node.exe", "");
+
+    let mut manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+    manifest.insert_version("18.0.0", None).unwrap();
+    manifest.save().unwrap();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .env("PROTO_OFFLINE", "1")
+        .arg("bin")
+        .arg("node")
+        .arg("18.0.0")
+        .arg("--json")
+        .assert();
+
+    let output: JsonValue = json::from_str(&output_to_string(&assert.get_output().stdout))
+        .expect("Output should be JSON");
+
+    assert_eq!(output["tool"], "node");
+    assert_eq!(output["version"], "18.0.0");
+    assert!(output["path"].as_str().unwrap().contains("18.0.0"));
+    assert!(output["shim"].is_null());
+}
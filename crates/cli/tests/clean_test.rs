@@ -0,0 +1,220 @@
+mod utils;
+
+use proto_core::{LocalShimEntry, Manifest, ManifestVersion, ShimRegistry};
+use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json;
+use utils::*;
+
+fn create_node_manifest(temp: &Sandbox, versions: &[&str], default_version: Option<&str>) {
+    let mut manifest = create_manifest(
+        temp,
+        "node",
+        versions,
+        ManifestVersion {
+            last_used_at: Some(1),
+            ..ManifestVersion::default()
+        },
+    );
+
+    manifest.default_version = default_version.map(|version| version.to_owned());
+    manifest.save().unwrap();
+}
+
+fn run_clean(temp: &Sandbox, args: &[&str]) -> json::JsonValue {
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("clean").arg("--json").args(args).assert().success();
+
+    json::from_str(&String::from_utf8(assert.get_output().stdout.clone()).unwrap()).unwrap()
+}
+
+fn get_cleaned_versions(result: &json::JsonValue) -> Vec<String> {
+    result["versions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["version"].as_str().unwrap().to_owned())
+        .collect()
+}
+
+#[test]
+fn requires_yes_or_dry_run_for_json() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("clean").arg("--json").assert();
+
+    assert
+        .failure()
+        .stderr(predicate::str::contains("requires --yes or --dry-run"));
+}
+
+#[test]
+fn removes_unused_versions_but_keeps_default() {
+    let temp = create_empty_sandbox();
+    create_node_manifest(&temp, &["17.0.0", "18.0.0"], Some("18.0.0"));
+
+    let result = run_clean(&temp, &["--yes"]);
+
+    assert_eq!(get_cleaned_versions(&result), vec!["17.0.0"]);
+    assert!(!temp.path().join("tools/node/17.0.0").exists());
+    assert!(temp.path().join("tools/node/18.0.0").exists());
+
+    let manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+
+    assert!(!manifest.installed_versions.contains("17.0.0"));
+    assert!(manifest.installed_versions.contains("18.0.0"));
+}
+
+#[test]
+fn removes_untracked_versions() {
+    let temp = create_empty_sandbox();
+    create_node_manifest(&temp, &[], None);
+    temp.create_file("tools/node/16.0.0/bin/node", "");
+
+    let result = run_clean(&temp, &["--yes"]);
+
+    assert_eq!(get_cleaned_versions(&result), vec!["16.0.0"]);
+    assert!(!temp.path().join("tools/node/16.0.0").exists());
+}
+
+#[test]
+fn doesnt_remove_anything_in_dry_run() {
+    let temp = create_empty_sandbox();
+    create_node_manifest(&temp, &["17.0.0", "18.0.0"], None);
+
+    let result = run_clean(&temp, &["--dry-run"]);
+
+    assert_eq!(result["dry_run"], true);
+    assert_eq!(get_cleaned_versions(&result), vec!["17.0.0", "18.0.0"]);
+    assert!(temp.path().join("tools/node/17.0.0").exists());
+    assert!(temp.path().join("tools/node/18.0.0").exists());
+}
+
+#[test]
+fn keeps_latest_versions_per_major() {
+    let temp = create_empty_sandbox();
+    create_node_manifest(
+        &temp,
+        &["18.0.0", "18.1.0", "18.2.0", "19.0.0", "19.1.0"],
+        None,
+    );
+
+    let result = run_clean(&temp, &["--dry-run", "--keep-latest", "1"]);
+
+    assert_eq!(
+        get_cleaned_versions(&result),
+        vec!["18.0.0", "18.1.0", "19.0.0"]
+    );
+}
+
+#[test]
+fn keeps_versions_pinned_in_projects() {
+    let temp = create_empty_sandbox();
+    create_node_manifest(&temp, &["17.0.0", "18.0.0", "18.1.0"], None);
+    temp.create_file("project-a/.prototools", "node = \"17.0.0\"");
+    temp.create_file("project-b/.prototools", "node = \"18\"");
+
+    let result = run_clean(
+        &temp,
+        &[
+            "--dry-run",
+            "--keep-pinned",
+            temp.path().join("project-a").to_str().unwrap(),
+            "--keep-pinned",
+            temp.path().join("project-b").to_str().unwrap(),
+        ],
+    );
+
+    assert_eq!(get_cleaned_versions(&result), vec!["18.0.0"]);
+}
+
+#[test]
+fn scans_all_tool_directories() {
+    let temp = create_empty_sandbox();
+    create_node_manifest(&temp, &[], None);
+    temp.create_file("tools/custom-plugin/1.0.0/bin/custom", "");
+
+    let result = run_clean(&temp, &["--dry-run"]);
+
+    assert_eq!(result["versions"][0]["tool"], "custom-plugin");
+    assert_eq!(result["versions"][0]["version"], "1.0.0");
+}
+
+#[test]
+fn removes_stale_plugins_and_temp_files() {
+    let temp = create_empty_sandbox();
+    temp.create_file("plugins/old_plugin.wasm", "");
+    temp.create_file("plugins/schema.toml", "");
+    temp.create_file("temp/download.tar.xz", "");
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+
+    let result = run_clean(&temp, &["--yes", "--days", "0"]);
+
+    assert_eq!(result["plugins"].as_array().unwrap().len(), 1);
+    assert_eq!(result["temp"].as_array().unwrap().len(), 1);
+    assert!(!temp.path().join("plugins/old_plugin.wasm").exists());
+    assert!(temp.path().join("plugins/schema.toml").exists());
+    assert!(!temp.path().join("temp/download.tar.xz").exists());
+}
+
+#[test]
+fn keeps_plugins_referenced_by_configs() {
+    let temp = create_empty_sandbox();
+    let hash = "a".repeat(64);
+    temp.create_file(
+        ".prototools",
+        "[plugins]\nmoon-test = \"source:./plugin.wasm\"",
+    );
+    temp.create_file(format!("plugins/moon-test-{hash}.wasm"), "");
+    temp.create_file(format!("plugins/moon-test-latest-{hash}.wasm"), "");
+    temp.create_file(format!("plugins/unused-{hash}.wasm"), "");
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+
+    let result = run_clean(&temp, &["--yes", "--days", "0"]);
+
+    assert_eq!(result["plugins"].as_array().unwrap().len(), 1);
+    assert!(temp
+        .path()
+        .join(format!("plugins/moon-test-{hash}.wasm"))
+        .exists());
+    assert!(temp
+        .path()
+        .join(format!("plugins/moon-test-latest-{hash}.wasm"))
+        .exists());
+    assert!(!temp
+        .path()
+        .join(format!("plugins/unused-{hash}.wasm"))
+        .exists());
+}
+
+#[test]
+fn removes_shims_of_cleaned_versions() {
+    let temp = create_empty_sandbox();
+    create_node_manifest(&temp, &["17.0.0", "18.0.0"], Some("18.0.0"));
+
+    let mut registry = ShimRegistry::load_from(temp.path()).unwrap();
+
+    for version in ["17.0.0", "18.0.0"] {
+        registry
+            .register_local(
+                &temp.path().join(format!("tools/node/{version}/shims/npx")),
+                LocalShimEntry {
+                    tool: "node".into(),
+                    ..LocalShimEntry::default()
+                },
+            )
+            .unwrap();
+    }
+
+    run_clean(&temp, &["--yes"]);
+
+    let registry = ShimRegistry::load_from(temp.path()).unwrap();
+
+    assert_eq!(registry.local_shims.len(), 1);
+    assert!(registry
+        .local_shims
+        .keys()
+        .all(|path| path.contains("18.0.0")));
+}
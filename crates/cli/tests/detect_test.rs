@@ -0,0 +1,95 @@
+mod utils;
+
+use starbase_sandbox::predicates::prelude::*;
+use utils::*;
+
+#[test]
+fn errors_if_no_version_detected() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("detect").arg("node").assert();
+
+    assert.stderr(predicate::str::contains(
+        "Failed to detect an applicable version",
+    ));
+}
+
+#[test]
+fn detects_version_from_config() {
+    let temp = create_empty_sandbox();
+    temp.create_file(".prototools", "node = \"18.0.0\"");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("detect").arg("node").assert();
+
+    assert.success().stdout(predicate::eq("18.0.0\n"));
+}
+
+#[test]
+fn prefers_env_var_over_config() {
+    let temp = create_empty_sandbox();
+    temp.create_file(".prototools", "node = \"18.0.0\"");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .arg("detect")
+        .arg("node")
+        .env("PROTO_NODE_VERSION", "19.0.0")
+        .assert();
+
+    assert.success().stdout(predicate::eq("19.0.0\n"));
+}
+
+#[test]
+fn explains_each_step() {
+    let temp = create_empty_sandbox();
+    temp.create_file(".prototools", "npm = \"9.0.0\"");
+    temp.create_file("nested/.nvmrc", "18.0.0");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .arg("detect")
+        .arg("node")
+        .arg("--explain")
+        .current_dir(temp.path().join("nested"))
+        .assert();
+
+    let output = output_to_string(&assert.get_output().stdout);
+
+    assert!(predicate::str::contains("PROTO_NODE_VERSION is not set").eval(&output));
+    assert!(predicate::str::contains(".nvmrc matched 18.0.0, expanded to 18.0.0").eval(&output));
+    assert!(predicate::str::contains("Detected version 18.0.0").eval(&output));
+}
+
+#[test]
+fn explains_as_json() {
+    let temp = create_empty_sandbox();
+    temp.create_file(".prototools", "node = \"18.0.0\"");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("detect").arg("node").arg("--json").assert();
+
+    let output = output_to_string(&assert.get_output().stdout);
+
+    assert!(predicate::str::contains("\"step\": \"config-file\"").eval(&output));
+    assert!(predicate::str::contains("\"version\": \"18.0.0\"").eval(&output));
+}
+
+#[test]
+fn continues_when_a_plugin_fails_to_load() {
+    let temp = create_empty_sandbox();
+    temp.create_file(
+        ".prototools",
+        "node = \"18.0.0\"\n\n[plugins]\nbroken = \"source:./missing.toml\"",
+    );
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("detect").arg("--json").assert().success();
+
+    let output = output_to_string(&assert.get_output().stdout);
+
+    assert!(predicate::str::contains("\"tool\": \"broken\"").eval(&output));
+    assert!(predicate::str::contains("\"error\":").eval(&output));
+    assert!(predicate::str::contains("\"version\": \"18.0.0\"").eval(&output));
+}
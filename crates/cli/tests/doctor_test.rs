@@ -0,0 +1,152 @@
+mod utils;
+
+use proto_core::{Manifest, ManifestVersion};
+use starbase_sandbox::predicates::prelude::*;
+use std::env;
+use utils::*;
+
+fn create_doctor_command(temp: &Sandbox) -> starbase_sandbox::assert_cmd::Command {
+    let mut cmd = create_proto_command(temp.path());
+    cmd.arg("doctor")
+        .env("PATH", env::join_paths([temp.path().join("bin")]).unwrap());
+    cmd
+}
+
+#[test]
+fn passes_for_empty_installation() {
+    let temp = create_empty_sandbox();
+
+    let assert = create_doctor_command(&temp).assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("No issues found"));
+}
+
+#[test]
+fn reports_missing_bin_dir_in_path() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("doctor").env("PATH", "").assert();
+
+    assert
+        .failure()
+        .stdout(predicate::str::contains("is not in"));
+}
+
+#[test]
+fn prunes_stale_manifest_versions() {
+    let temp = create_empty_sandbox();
+    create_manifest(
+        &temp,
+        "node",
+        &["18.0.0", "19.0.0"],
+        ManifestVersion::default(),
+    );
+    std::fs::remove_dir_all(temp.path().join("tools/node/18.0.0")).unwrap();
+
+    let assert = create_doctor_command(&temp).assert();
+
+    assert.failure().stdout(predicate::str::contains(
+        "is recorded in the manifest, but its directory is missing",
+    ));
+
+    let assert = create_doctor_command(&temp).arg("--fix").assert();
+
+    assert.success().stdout(predicate::str::contains("Fixed"));
+
+    let manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+
+    assert!(!manifest.installed_versions.contains("18.0.0"));
+    assert!(manifest.installed_versions.contains("19.0.0"));
+    assert_eq!(manifest.default_version, None);
+}
+
+#[test]
+fn removes_empty_untracked_versions() {
+    let temp = create_empty_sandbox();
+    create_manifest(&temp, "node", &[], ManifestVersion::default());
+    std::fs::create_dir_all(temp.path().join("tools/node/18.0.0")).unwrap();
+
+    let assert = create_doctor_command(&temp).arg("--fix").assert();
+
+    assert.success();
+    assert!(!temp.path().join("tools/node/18.0.0").exists());
+}
+
+#[test]
+fn reports_non_empty_untracked_versions() {
+    let temp = create_empty_sandbox();
+    create_manifest(&temp, "node", &[], ManifestVersion::default());
+    temp.create_file("tools/node/18.0.0/bin/node", "");
+
+    let assert = create_doctor_command(&temp).arg("--fix").assert();
+
+    assert
+        .failure()
+        .stdout(predicate::str::contains("proto install node 18.0.0"));
+    assert!(temp.path().join("tools/node/18.0.0").exists());
+}
+
+#[test]
+fn keeps_old_proto_binary() {
+    let temp = create_empty_sandbox();
+    temp.create_file("bin/proto-old", "");
+    temp.create_file("bin/proto-shim-old", "");
+
+    // Required by `proto upgrade --rollback`
+    create_doctor_command(&temp).arg("--fix").assert().success();
+
+    assert!(temp.path().join("bin/proto-old").exists());
+    assert!(temp.path().join("bin/proto-shim-old").exists());
+}
+
+#[test]
+fn removes_orphaned_shims() {
+    let temp = create_empty_sandbox();
+    temp.create_file(
+        "bin/node",
+        "#!/usr/bin/env bash\nexec proto run node -- \"$@\"",
+    );
+
+    let assert = create_doctor_command(&temp).arg("--fix").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("has no installed versions"));
+    assert!(!temp.path().join("bin/node").exists());
+}
+
+#[test]
+fn reports_shims_with_unreadable_manifests() {
+    let temp = create_empty_sandbox();
+    temp.create_file(
+        "bin/node",
+        "#!/usr/bin/env bash\nexec proto run node -- \"$@\"",
+    );
+    temp.create_file("tools/node/manifest.json", "{");
+
+    let assert = create_doctor_command(&temp).assert();
+
+    assert
+        .code(3)
+        .stderr(predicate::str::contains("proto::doctor::issues"))
+        .stdout(predicate::str::contains("whose manifest could not be read"))
+        .stdout(predicate::str::contains("Plugins"));
+}
+
+#[test]
+fn removes_corrupted_plugins() {
+    let temp = create_empty_sandbox();
+    temp.create_file("plugins/broken.wasm", "not wasm");
+    temp.create_file("plugins/valid.wasm", "\0asm\x01\0\0\0");
+
+    let assert = create_doctor_command(&temp).arg("--fix").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("is corrupted"));
+    assert!(!temp.path().join("plugins/broken.wasm").exists());
+    assert!(temp.path().join("plugins/valid.wasm").exists());
+}
@@ -0,0 +1,79 @@
+mod utils;
+
+use proto_core::ManifestVersion;
+use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json;
+use utils::*;
+
+fn create_node_installs(temp: &Sandbox) {
+    create_manifest(temp, "node", &["19.0.0"], ManifestVersion::default());
+    create_manifest(
+        temp,
+        "node",
+        &["18.0.0"],
+        ManifestVersion {
+            last_used_at: Some(1),
+            ..ManifestVersion::default()
+        },
+    );
+
+    temp.create_file("tools/node/18.0.0/bin/node", "a".repeat(2048));
+    temp.create_file("tools/node/19.0.0/bin/node", "a".repeat(1024));
+}
+
+#[test]
+fn reports_empty_installation() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("du").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("No tools installed"))
+        .stdout(predicate::str::contains("Total:"));
+}
+
+#[test]
+fn reports_usage_per_version() {
+    let temp = create_empty_sandbox();
+    create_node_installs(&temp);
+    temp.create_file("plugins/example.wasm", "a".repeat(100));
+    temp.create_file("temp/abc.json", "a".repeat(50));
+    temp.create_file("temp/node.tar.xz", "a".repeat(25));
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("du").arg("--json").assert();
+
+    let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let usage: json::JsonValue = json::from_str(&output).unwrap();
+
+    assert_eq!(usage["plugins_size"], 100);
+    assert_eq!(usage["temp_size"], 25);
+    assert_eq!(usage["versions_cache_size"], 50);
+
+    let node = &usage["tools"][0];
+
+    assert_eq!(node["id"], "node");
+    assert_eq!(node["versions"][0]["version"], "18.0.0");
+    assert_eq!(node["versions"][0]["size"], 2048);
+    assert_eq!(node["versions"][1]["version"], "19.0.0");
+    assert_eq!(node["versions"][1]["default"], true);
+}
+
+#[test]
+fn highlights_unused_versions() {
+    let temp = create_empty_sandbox();
+    create_node_installs(&temp);
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("du").arg("--json").assert();
+
+    let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let usage: json::JsonValue = json::from_str(&output).unwrap();
+    let unused = usage["unused"].as_array().unwrap();
+
+    assert_eq!(unused.len(), 1);
+    assert_eq!(unused[0]["tool"], "node");
+    assert_eq!(unused[0]["version"], "18.0.0");
+}
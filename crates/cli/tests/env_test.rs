@@ -0,0 +1,305 @@
+mod utils;
+
+use proto_core::Manifest;
+use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json;
+use utils::*;
+
+fn create_node_sandbox() -> Sandbox {
+    let temp = create_empty_sandbox();
+
+    let mut manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+    manifest.insert_version("18.0.0", None).unwrap();
+    manifest.save().unwrap();
+
+    temp.create_file("tools/node/18.0.0/bin/node", "");
+    temp.create_file(
+        ".prototools",
+        r#"
+node = "18.0.0"
+
+[env]
+FOO = "bar"
+"#,
+    );
+
+    temp
+}
+
+fn bin_dir(temp: &Sandbox) -> String {
+    temp.path()
+        .join("tools/node/18.0.0/bin")
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(unix)]
+mod env {
+    use super::*;
+
+    #[test]
+    fn prints_bash_exports() {
+        let temp = create_node_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("bash")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("export FOO='bar';"))
+            .stdout(predicate::str::contains(format!(
+                "export PATH='{}:/usr/bin';",
+                bin_dir(&temp)
+            )));
+    }
+
+    #[test]
+    fn prints_fish_exports() {
+        let temp = create_node_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("fish")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("set -gx FOO 'bar';"))
+            .stdout(predicate::str::contains(format!(
+                "set -gx PATH '{}' '/usr/bin';",
+                bin_dir(&temp)
+            )));
+    }
+
+    #[test]
+    fn prints_pwsh_exports() {
+        let temp = create_node_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("pwsh")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("$env:FOO = 'bar';"));
+    }
+
+    #[test]
+    fn prints_nu_record() {
+        let temp = create_node_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("nu")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let record: json::JsonValue = json::from_str(&output).unwrap();
+
+        assert_eq!(record["FOO"], "bar");
+        assert_eq!(record["PATH"][0], bin_dir(&temp));
+        assert_eq!(record["PATH"][1], "/usr/bin");
+    }
+
+    #[test]
+    fn prints_json() {
+        let temp = create_node_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("json")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let result: json::JsonValue = json::from_str(&output).unwrap();
+
+        assert_eq!(result["tools"]["node"]["version"], "18.0.0");
+        assert_eq!(result["tools"]["node"]["bin_dir"], bin_dir(&temp));
+        assert_eq!(result["env"]["PROTO_ACTIVE_PATHS"], bin_dir(&temp));
+        assert_eq!(result["env"]["PROTO_ACTIVE_VARS"], "FOO");
+    }
+
+    #[test]
+    fn reverts_previous_activation() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("bash")
+            .env("PATH", "/old/bin:/usr/bin")
+            .env("PROTO_ACTIVE_PATHS", "/old/bin")
+            .env("PROTO_ACTIVE_VARS", "FOO")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("unset FOO;"))
+            .stdout(predicate::str::contains("unset PROTO_ACTIVE_PATHS;"))
+            .stdout(predicate::str::contains("export PATH='/usr/bin';"));
+    }
+
+    #[test]
+    fn saves_and_restores_previous_values() {
+        let temp = create_node_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("bash")
+            .env("FOO", "original")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("export FOO='bar';"))
+            .stdout(predicate::str::contains(
+                r#"export PROTO_SAVED_VARS='{"FOO":"original"}';"#,
+            ));
+
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("bash")
+            .env("FOO", "bar")
+            .env("PROTO_ACTIVE_VARS", "FOO")
+            .env("PROTO_SAVED_VARS", r#"{"FOO":"original"}"#)
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("export FOO='original';"))
+            .stdout(predicate::str::contains("unset PROTO_SAVED_VARS;"));
+    }
+
+    #[test]
+    fn skips_tools_that_fail_to_load() {
+        let temp = create_node_sandbox();
+        temp.create_file(
+            ".prototools",
+            r#"
+node = "18.0.0"
+unknown-tool = "1.0.0"
+"#,
+        );
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("json")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let result: json::JsonValue = json::from_str(&output).unwrap();
+
+        assert_eq!(result["tools"]["node"]["version"], "18.0.0");
+    }
+
+    #[test]
+    fn uses_installed_versions_only() {
+        let temp = create_node_sandbox();
+        temp.create_file(".prototools", "node = \"18\"");
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("json")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let result: json::JsonValue = json::from_str(&output).unwrap();
+
+        assert_eq!(result["tools"]["node"]["version"], "18.0.0");
+    }
+
+    #[test]
+    fn uses_shims_dir_for_dependency_managers() {
+        let temp = create_node_sandbox();
+        create_fake_npm(&temp);
+        temp.create_file(".prototools", "node = \"18.0.0\"\nnpm = \"9.0.0\"");
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("env")
+            .arg("--shell")
+            .arg("json")
+            .env("PATH", "/usr/bin")
+            .assert();
+
+        let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+        let result: json::JsonValue = json::from_str(&output).unwrap();
+
+        assert_eq!(
+            result["tools"]["npm"]["bin_dir"],
+            temp.path().join("bin").to_string_lossy().to_string()
+        );
+    }
+}
+
+mod activate {
+    use super::*;
+
+    #[test]
+    fn prints_bash_hook() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("activate").arg("bash").assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("proto env --shell bash"))
+            .stdout(predicate::str::contains("PROMPT_COMMAND"));
+    }
+
+    #[test]
+    fn prints_zsh_hook() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("activate").arg("zsh").assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("add-zsh-hook chpwd _proto_hook"));
+    }
+
+    #[test]
+    fn errors_for_json() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("activate").arg("json").assert();
+
+        assert
+            .code(8)
+            .stderr(predicate::str::contains("proto::unsupported::activate"));
+    }
+}
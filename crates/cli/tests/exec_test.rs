@@ -0,0 +1,154 @@
+mod utils;
+
+use starbase_sandbox::predicates::prelude::*;
+use utils::*;
+
+#[test]
+fn requires_a_command() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("exec").arg("node").assert();
+
+    assert.failure();
+}
+
+#[test]
+fn errors_for_missing_version() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("exec").arg("node@").arg("--").arg("node").assert();
+
+    assert
+        .code(2)
+        .stderr(predicate::str::contains("proto::tool::invalid_spec"))
+        .stderr(predicate::str::contains("Missing version for node"));
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+
+    #[test]
+    fn prepends_tools_to_path() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+        create_fake_node(&temp, &["19.0.0"]);
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("exec")
+            .arg("node@18.0.0")
+            .arg("--")
+            .arg("node")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("node 18.0.0"));
+    }
+
+    #[test]
+    fn sets_tool_env_vars() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("exec")
+            .arg("node@18.0.0")
+            .arg("--")
+            .arg("sh")
+            .arg("-c")
+            .arg("echo \"$PROTO_NODE_VERSION $PROTO_NODE_BIN\"")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("18.0.0 "))
+            .stdout(predicate::str::contains("tools/node/18.0.0/bin/node"));
+    }
+
+    #[test]
+    fn uses_tools_from_config() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["19.0.0"]);
+        temp.create_file(".prototools", "node = \"19.0.0\"");
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("exec")
+            .arg("--")
+            .arg("node")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("node 19.0.0"));
+    }
+
+    #[test]
+    fn uses_shims_dir_for_dependency_managers() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+        create_fake_npm(&temp);
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("exec")
+            .arg("npm@9.0.0")
+            .arg("node@18.0.0")
+            .arg("--")
+            .arg("sh")
+            .arg("-c")
+            .arg("echo \"$PATH\"")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::starts_with(format!(
+                "{}:",
+                temp.path().join("bin").display()
+            )))
+            .stdout(predicate::str::contains("tools/npm/9.0.0/bin").not());
+    }
+
+    #[test]
+    fn errors_if_not_installed() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("exec")
+            .arg("node@19.0.0")
+            .arg("--")
+            .arg("node")
+            .assert();
+
+        assert
+            .failure()
+            .stderr(predicate::str::contains("has not been installed"));
+    }
+
+    #[test]
+    fn exits_with_command_code() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .arg("exec")
+            .arg("--")
+            .arg("sh")
+            .arg("-c")
+            .arg("exit 3")
+            .assert();
+
+        assert.code(3);
+    }
+}
@@ -0,0 +1,60 @@
+mod utils;
+
+use proto_core::ManifestVersion;
+use starbase_sandbox::predicates::prelude::*;
+use utils::*;
+
+fn create_node_install(temp: &Sandbox) {
+    create_manifest(
+        temp,
+        "node",
+        &["18.0.0"],
+        ManifestVersion {
+            download_url: Some("https://nodejs.org/dist/v18.0.0/node.tar.xz".into()),
+            checksum: Some("abc123".into()),
+            checksum_algorithm: Some("sha256".into()),
+            archive_size: Some(1024),
+            installed_size: Some(4096),
+            proto_version: Some("0.14.0".into()),
+            ..ManifestVersion::default()
+        },
+    );
+}
+
+#[test]
+fn displays_install_provenance() {
+    let temp = create_empty_sandbox();
+    create_node_install(&temp);
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("info").arg("node").arg("18.0.0").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("Installed: yes"))
+        .stdout(predicate::str::contains(
+            "Download URL: https://nodejs.org/dist/v18.0.0/node.tar.xz",
+        ))
+        .stdout(predicate::str::contains("Checksum: sha256:abc123"))
+        .stdout(predicate::str::contains("Installed by: proto v0.14.0"));
+}
+
+#[test]
+fn displays_info_as_json() {
+    let temp = create_empty_sandbox();
+    create_node_install(&temp);
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .arg("info")
+        .arg("node")
+        .arg("18.0.0")
+        .arg("--json")
+        .assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("\"installed\": true"))
+        .stdout(predicate::str::contains("\"checksum\": \"abc123\""))
+        .stdout(predicate::str::contains("\"archive_size\": 1024"));
+}
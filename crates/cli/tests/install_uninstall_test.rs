@@ -122,3 +122,100 @@ fn can_pin_when_installing() {
         FxHashSet::from_iter(["18.0.0".into(), "19.0.0".into()])
     );
 }
+
+mod install_many {
+    use super::*;
+
+    #[test]
+    fn supports_tool_and_version_args() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("install")
+            .arg("node")
+            .arg("18.0.0")
+            .assert();
+
+        assert.success().stderr(predicate::str::contains(
+            "Node.js has already been installed",
+        ));
+    }
+
+    #[test]
+    fn installs_multiple_specs() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+        create_fake_node(&temp, &["19.0.0"]);
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("install")
+            .arg("node@18.0.0")
+            .arg("node@19.0.0")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("node@18.0.0"))
+            .stdout(predicate::str::contains("node@19.0.0"))
+            .stdout(predicate::str::contains("already installed"));
+    }
+
+    #[test]
+    fn installs_node_with_dependency_managers() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+        create_fake_npm(&temp);
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("install")
+            .arg("npm@9.0.0")
+            .arg("node@18.0.0")
+            .assert();
+
+        // Node.js is installed first, as it also installs its bundled npm
+        assert.success().stdout(predicate::str::contains(
+            "node@18.0.0 18.0.0 already installed\nnpm@9.0.0   9.0.0 already installed",
+        ));
+    }
+
+    #[test]
+    fn continues_after_a_failure() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .arg("install")
+            .arg("unknown-tool@1.0.0")
+            .arg("node@18.0.0")
+            .assert();
+
+        assert
+            .code(7)
+            .stdout(predicate::str::contains("unknown-tool@1.0.0"))
+            .stdout(predicate::str::contains("failed"))
+            .stdout(predicate::str::contains("18.0.0 already installed"))
+            .stderr(predicate::str::contains("Failed to install 1 of 2 tools"));
+    }
+
+    #[test]
+    fn errors_for_missing_version() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("install").arg("node@").assert();
+
+        assert
+            .code(2)
+            .stderr(predicate::str::contains("proto::tool::invalid_spec"))
+            .stderr(predicate::str::contains("Missing version for node"));
+    }
+}
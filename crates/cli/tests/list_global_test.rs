@@ -0,0 +1,54 @@
+mod utils;
+
+use starbase_utils::json::{self, JsonValue};
+use utils::*;
+
+#[test]
+fn lists_globals() {
+    let temp = create_empty_sandbox();
+    temp.create_file("tools/node/globals/bin/tsc", "");
+    temp.create_file("tools/node/globals/bin/eslint", "");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("list-global").arg("node").assert();
+
+    let output = output_to_string(&assert.get_output().stdout);
+
+    assert!(output.starts_with("eslint - "));
+    assert!(output.contains("tsc - "));
+}
+
+#[test]
+fn lists_globals_as_json() {
+    let temp = create_empty_sandbox();
+    temp.create_file("tools/node/globals/bin/tsc", "");
+    temp.create_file("tools/node/globals/bin/eslint", "");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("list-global").arg("node").arg("--json").assert();
+
+    let output: JsonValue = json::from_str(&output_to_string(&assert.get_output().stdout))
+        .expect("Output should be JSON");
+    let bins = output.as_array().unwrap();
+
+    assert_eq!(bins.len(), 2);
+    assert_eq!(bins[0]["name"], "eslint");
+    assert!(bins[0]["path"]
+        .as_str()
+        .unwrap()
+        .ends_with("globals/bin/eslint"));
+    assert_eq!(bins[1]["name"], "tsc");
+}
+
+#[test]
+fn lists_no_globals_as_json() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("list-global").arg("node").arg("--json").assert();
+
+    let output: JsonValue = json::from_str(&output_to_string(&assert.get_output().stdout))
+        .expect("Output should be JSON");
+
+    assert_eq!(output, json::json!([]));
+}
@@ -1,5 +1,6 @@
 mod utils;
 
+use starbase_utils::json::{self, JsonValue};
 use utils::*;
 
 #[test]
@@ -25,3 +26,32 @@ fn lists_remote_versions_for_plugin() {
 
     assert!(output.split('\n').collect::<Vec<_>>().len() > 1);
 }
+
+#[test]
+fn lists_remote_versions_as_json() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .arg("list-remote")
+        .arg("node")
+        .arg("--since")
+        .arg("18")
+        .arg("--json")
+        .assert();
+
+    let output: JsonValue = json::from_str(&output_to_string(&assert.get_output().stdout))
+        .expect("Output should be JSON");
+    let versions = output.as_array().unwrap();
+
+    assert!(!versions.is_empty());
+    assert!(versions
+        .iter()
+        .all(|version| !version["version"].as_str().unwrap().starts_with("17.")));
+    assert!(versions
+        .iter()
+        .any(|version| version["lts"] == "hydrogen" && version["version"] == "18.12.0"));
+    assert!(versions
+        .iter()
+        .all(|version| version["prerelease"] == false));
+}
@@ -1,6 +1,7 @@
 mod utils;
 
 use proto_core::Manifest;
+use starbase_utils::json::{self, JsonValue};
 use utils::*;
 
 #[test]
@@ -21,3 +22,32 @@ fn lists_local_versions() {
 
     assert_eq!(output.split('\n').collect::<Vec<_>>().len(), 4); // includes header
 }
+
+#[test]
+fn lists_local_versions_as_json() {
+    let temp = create_empty_sandbox();
+
+    let mut manifest = Manifest::load(temp.path().join("tools/node/manifest.json")).unwrap();
+    manifest.default_version = Some("19.0.0".into());
+    manifest.insert_version("19.0.0", None).unwrap();
+    manifest.insert_version("18.0.0", None).unwrap();
+    manifest.aliases.insert("work".into(), "18.0.0".into());
+    manifest.track_used_at("18.0.0");
+    manifest.save().unwrap();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("list").arg("node").arg("--json").assert();
+
+    let output: JsonValue = json::from_str(&output_to_string(&assert.get_output().stdout))
+        .expect("Output should be JSON");
+    let versions = output.as_array().unwrap();
+
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0]["version"], "18.0.0");
+    assert_eq!(versions[0]["default"], false);
+    assert_eq!(versions[0]["aliases"], json::json!(["work"]));
+    assert!(versions[0]["last_used_at"].is_number());
+    assert_eq!(versions[1]["version"], "19.0.0");
+    assert_eq!(versions[1]["default"], true);
+    assert!(versions[1]["last_used_at"].is_null());
+}
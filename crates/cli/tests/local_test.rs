@@ -111,3 +111,57 @@ fn can_set_aliases() {
         "npm = \"bundled\"\n"
     )
 }
+
+#[test]
+fn preserves_comments_and_order() {
+    let temp = create_empty_sandbox();
+    let version_file = temp.path().join(".prototools");
+
+    temp.create_file(
+        ".prototools",
+        r#"# Tools
+npm = "9.0.0" # Pinned
+node = "16.0.0"
+"#,
+    );
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.arg("local")
+        .arg("node")
+        .arg("19.0.0")
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(version_file).unwrap(),
+        r#"# Tools
+npm = "9.0.0" # Pinned
+node = "19.0.0"
+"#
+    )
+}
+
+#[test]
+fn can_unset_a_tool() {
+    let temp = create_empty_sandbox();
+    let version_file = temp.path().join(".prototools");
+
+    temp.create_file(
+        ".prototools",
+        r#"node = "16.0.0"
+npm = "9.0.0"
+"#,
+    );
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.arg("local")
+        .arg("node")
+        .arg("--unset")
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(version_file).unwrap(),
+        "npm = \"9.0.0\"\n"
+    )
+}
@@ -0,0 +1,196 @@
+mod utils;
+
+use proto_core::Manifest;
+use starbase_sandbox::predicates::prelude::*;
+use utils::*;
+
+fn create_node_install(temp: &Sandbox, dir: &str) {
+    temp.create_file(format!("{dir}/bin/node"), "");
+    temp.create_file(format!("{dir}/lib/node_modules/npm/bin/npm-cli.js"), "");
+}
+
+fn load_manifest(temp: &Sandbox) -> Manifest {
+    Manifest::load_from(temp.path().join("tools/node")).unwrap()
+}
+
+#[test]
+fn does_nothing_without_installs() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .env("NVM_DIR", temp.path().join("nvm"))
+        .arg("migrate")
+        .arg("--from")
+        .arg("nvm")
+        .assert();
+
+    assert
+        .success()
+        .stderr(predicate::str::contains("No Node.js installs found"));
+}
+
+#[test]
+fn copies_installs_from_nvm() {
+    let temp = create_empty_sandbox();
+    create_node_install(&temp, "nvm/versions/node/v18.0.0");
+    create_node_install(&temp, "nvm/versions/node/v19.0.0");
+    temp.create_file("nvm/alias/default", "18");
+    temp.create_file("nvm/alias/work", "v19.0.0");
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.env("NVM_DIR", temp.path().join("nvm"))
+        .arg("migrate")
+        .arg("--from")
+        .arg("nvm")
+        .assert()
+        .success();
+
+    let manifest = load_manifest(&temp);
+
+    assert!(temp.path().join("tools/node/18.0.0/bin/node").exists());
+    assert!(temp.path().join("tools/node/19.0.0/bin/node").exists());
+    assert!(temp.path().join("nvm/versions/node/v18.0.0").exists());
+    assert!(manifest.installed_versions.contains("18.0.0"));
+    assert!(manifest.installed_versions.contains("19.0.0"));
+    assert_eq!(manifest.default_version, Some("18.0.0".into()));
+    assert_eq!(manifest.aliases.get("work"), Some(&"19.0.0".to_owned()));
+}
+
+#[test]
+fn resolves_lts_aliases_from_nvm() {
+    let temp = create_empty_sandbox();
+    create_node_install(&temp, "nvm/versions/node/v18.0.0");
+    create_node_install(&temp, "nvm/versions/node/v20.1.0");
+    create_node_install(&temp, "nvm/versions/node/v21.0.0");
+    temp.create_file("nvm/alias/default", "lts/*");
+    temp.create_file("nvm/alias/old", "lts/hydrogen");
+    temp.create_file("nvm/alias/empty", "");
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.env("NVM_DIR", temp.path().join("nvm"))
+        .arg("migrate")
+        .arg("--from")
+        .arg("nvm")
+        .assert()
+        .success();
+
+    let manifest = load_manifest(&temp);
+
+    assert_eq!(manifest.default_version, Some("20.1.0".into()));
+    assert_eq!(manifest.aliases.get("old"), Some(&"18.0.0".to_owned()));
+    assert!(!manifest.aliases.contains_key("empty"));
+}
+
+#[test]
+fn moves_installs_from_volta() {
+    let temp = create_empty_sandbox();
+    create_node_install(&temp, "volta/tools/image/node/18.0.0");
+    create_node_install(&temp, "volta/tools/image/node/19.0.0");
+    temp.create_file(
+        "volta/tools/user/platform.json",
+        r#"{ "node": { "runtime": "19.0.0", "npm": null } }"#,
+    );
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.env("VOLTA_HOME", temp.path().join("volta"))
+        .arg("migrate")
+        .arg("--from")
+        .arg("volta")
+        .arg("--move")
+        .assert()
+        .success();
+
+    let manifest = load_manifest(&temp);
+
+    assert!(temp.path().join("tools/node/19.0.0/bin/node").exists());
+    assert!(!temp.path().join("volta/tools/image/node/19.0.0").exists());
+    assert_eq!(manifest.default_version, Some("19.0.0".into()));
+}
+
+#[test]
+fn uses_global_tool_versions_for_asdf() {
+    let temp = create_empty_sandbox();
+    create_node_install(&temp, "asdf/installs/nodejs/18.0.0");
+    create_node_install(&temp, "asdf/installs/nodejs/19.0.0");
+    temp.create_file(
+        "home/.tool-versions",
+        "python 3.11.0\nnodejs 19.0.0 18.0.0\nnodejs 18.0.0\n",
+    );
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.env("ASDF_DATA_DIR", temp.path().join("asdf"))
+        .env("HOME", temp.path().join("home"))
+        .arg("migrate")
+        .arg("--from")
+        .arg("asdf")
+        .assert()
+        .success();
+
+    let manifest = load_manifest(&temp);
+
+    assert_eq!(manifest.installed_versions.len(), 2);
+    assert_eq!(manifest.default_version, Some("19.0.0".into()));
+}
+
+#[test]
+fn skips_versions_already_installed() {
+    let temp = create_empty_sandbox();
+    create_node_install(&temp, "nvm/versions/node/v18.0.0");
+    temp.create_file("tools/node/18.0.0/bin/node", "existing");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .env("NVM_DIR", temp.path().join("nvm"))
+        .arg("migrate")
+        .arg("--from")
+        .arg("nvm")
+        .assert();
+
+    assert
+        .success()
+        .stderr(predicate::str::contains("has already been installed"));
+
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("tools/node/18.0.0/bin/node")).unwrap(),
+        "existing"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn preserves_symlinks_and_aliases_from_fnm() {
+    use std::os::unix::fs::symlink;
+
+    let temp = create_empty_sandbox();
+    create_node_install(&temp, "fnm/node-versions/v18.0.0/installation");
+
+    let install_dir = temp.path().join("fnm/node-versions/v18.0.0/installation");
+
+    symlink(
+        "../lib/node_modules/npm/bin/npm-cli.js",
+        install_dir.join("bin/npm"),
+    )
+    .unwrap();
+
+    std::fs::create_dir_all(temp.path().join("fnm/aliases")).unwrap();
+    symlink(&install_dir, temp.path().join("fnm/aliases/default")).unwrap();
+
+    let mut cmd = create_proto_command(temp.path());
+    cmd.env("FNM_DIR", temp.path().join("fnm"))
+        .arg("migrate")
+        .arg("--from")
+        .arg("fnm")
+        .assert()
+        .success();
+
+    let npm_path = temp.path().join("tools/node/18.0.0/bin/npm");
+
+    assert!(npm_path
+        .symlink_metadata()
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(npm_path.exists());
+    assert_eq!(load_manifest(&temp).default_version, Some("18.0.0".into()));
+}
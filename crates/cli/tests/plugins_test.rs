@@ -3,9 +3,13 @@ mod utils;
 use futures::Future;
 use proto::{tools::create_plugin_from_locator, ProtoError};
 use proto_core::{PluginLocator, Proto, Tool};
+use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json::{self, JsonValue};
 use std::env;
+use std::fs;
 use std::path::{Path, PathBuf};
 use utils::*;
+use warpgate::WarpgateError;
 
 async fn run_tests<F, Fut>(factory: F)
 where
@@ -54,21 +58,29 @@ async fn downloads_and_installs_plugin_from_file() {
 }
 
 #[tokio::test]
-#[should_panic(expected = "does not exist")]
 async fn errors_for_missing_file() {
-    run_tests(|root| {
-        let root_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fixture = create_empty_sandbox();
+    let root_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
 
-        create_plugin_from_locator(
-            "moon",
-            Proto::from(root),
-            PluginLocator::SourceFile {
-                file: "./some/fake/path.toml".into(),
-                path: root_dir.join("./some/fake/path.toml"),
-            },
-        )
-    })
-    .await;
+    let error = create_plugin_from_locator(
+        "moon",
+        Proto::from(fixture.path()),
+        PluginLocator::SourceFile {
+            file: "./some/fake/path.toml".into(),
+            path: root_dir.join("./some/fake/path.toml"),
+        },
+    )
+    .await
+    .err()
+    .unwrap();
+
+    assert!(matches!(
+        error,
+        ProtoError::PluginLoadFailed {
+            error: WarpgateError::SourceFileMissing(_),
+            ..
+        }
+    ));
 }
 
 #[tokio::test]
@@ -87,18 +99,27 @@ async fn downloads_and_installs_plugin_from_url() {
 }
 
 #[tokio::test]
-#[should_panic(expected = "does not exist")]
 async fn errors_for_broken_url() {
-    run_tests(|root| {
-        create_plugin_from_locator(
-            "moon",
-            Proto::from(root),
-            PluginLocator::SourceUrl {
-                url: "https://raw.githubusercontent.com/moonrepo/moon/some/fake/path.toml".into(),
-            },
-        )
-    })
-    .await;
+    let fixture = create_empty_sandbox();
+
+    let error = create_plugin_from_locator(
+        "moon",
+        Proto::from(fixture.path()),
+        PluginLocator::SourceUrl {
+            url: "https://raw.githubusercontent.com/moonrepo/moon/some/fake/path.toml".into(),
+        },
+    )
+    .await
+    .err()
+    .unwrap();
+
+    assert!(matches!(
+        error,
+        ProtoError::PluginLoadFailed {
+            error: WarpgateError::DownloadNotFound { .. },
+            ..
+        }
+    ));
 }
 
 mod builtins {
@@ -136,3 +157,212 @@ mod builtins {
         assert.success();
     }
 }
+
+fn create_sandbox_with_schema() -> Sandbox {
+    let temp = create_empty_sandbox();
+    let root_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    temp.create_file(
+        "moon.toml",
+        fs::read_to_string(root_dir.join("tests/fixtures/moon-schema.toml")).unwrap(),
+    );
+
+    temp
+}
+
+mod add {
+    use super::*;
+
+    #[test]
+    fn adds_to_local_config() {
+        let temp = create_empty_sandbox();
+        temp.create_file(".prototools", "# Tools\nnode = \"20.0.0\"\n");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "add", "moon", "source:./moon.toml"])
+            .assert()
+            .success();
+
+        let config = fs::read_to_string(temp.path().join(".prototools")).unwrap();
+
+        assert!(config.contains("# Tools"));
+        assert!(config.contains("[plugins]\nmoon = \"source:./moon.toml\""));
+    }
+
+    #[test]
+    fn adds_to_global_config() {
+        let temp = create_empty_sandbox();
+        temp.create_file("config.toml", "# User\nauto-install = true\n");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args([
+            "plugins",
+            "add",
+            "moon",
+            "github:moonrepo/moon@v1.0.0",
+            "--global",
+        ])
+        .assert()
+        .success();
+
+        let config = fs::read_to_string(temp.path().join("config.toml")).unwrap();
+
+        assert!(config.contains("# User\nauto-install = true"));
+        assert!(config.contains("[plugins]\nmoon = \"github:moonrepo/moon@v1.0.0\""));
+        assert!(!temp.path().join(".prototools").exists());
+    }
+
+    #[test]
+    fn errors_for_invalid_locator() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "add", "moon", "unknown:moon"])
+            .assert()
+            .failure();
+
+        assert!(!temp.path().join(".prototools").exists());
+    }
+
+    #[test]
+    fn errors_for_builtin_tool() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "add", "node", "source:./moon.toml"])
+            .assert()
+            .code(3)
+            .stderr(predicate::str::contains("proto::config::invalid_plugin_id"))
+            .stderr(predicate::str::contains("is a built-in tool"));
+    }
+}
+
+mod remove {
+    use super::*;
+
+    #[test]
+    fn removes_from_local_config() {
+        let temp = create_empty_sandbox();
+        temp.create_file(
+            ".prototools",
+            "node = \"20.0.0\"\n\n[plugins]\nmoon = \"source:./moon.toml\"\n",
+        );
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "remove", "moon"]).assert().success();
+
+        let config = fs::read_to_string(temp.path().join(".prototools")).unwrap();
+
+        assert!(config.contains("node = \"20.0.0\""));
+        assert!(!config.contains("moon"));
+    }
+
+    #[test]
+    fn removes_from_global_config() {
+        let temp = create_empty_sandbox();
+        temp.create_file(
+            "config.toml",
+            "auto-install = true\n\n[plugins]\nmoon = \"source:./moon.toml\"\n",
+        );
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "remove", "moon", "--global"])
+            .assert()
+            .success();
+
+        let config = fs::read_to_string(temp.path().join("config.toml")).unwrap();
+
+        assert!(config.contains("auto-install = true"));
+        assert!(!config.contains("plugins"));
+    }
+
+    #[test]
+    fn errors_when_not_configured() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "remove", "moon"])
+            .assert()
+            .code(4)
+            .stderr(predicate::str::contains("proto::plugin::not_configured"))
+            .stderr(predicate::str::contains("does not exist"));
+    }
+}
+
+mod info {
+    use super::*;
+
+    #[test]
+    fn shows_locator_and_metadata() {
+        let temp = create_sandbox_with_schema();
+        temp.create_file(".prototools", "[plugins]\nmoon = \"source:./moon.toml\"\n");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "info", "moon"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("moon-test"))
+            .stdout(predicate::str::contains("source:./moon.toml"))
+            .stdout(predicate::str::contains("CLI"));
+    }
+
+    #[test]
+    fn prints_json() {
+        let temp = create_sandbox_with_schema();
+        temp.create_file(".prototools", "[plugins]\nmoon = \"source:./moon.toml\"\n");
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.args(["plugins", "info", "moon", "--json"]).assert();
+
+        let info: JsonValue =
+            json::from_str(&output_to_string(&assert.get_output().stdout)).unwrap();
+
+        assert_eq!(info["id"], "moon");
+        assert_eq!(info["locator"], "source:./moon.toml");
+        assert_eq!(info["fetched_at"], JsonValue::Null);
+        assert_eq!(info["metadata"]["name"], "moon-test");
+        assert_eq!(info["metadata"]["type_of"], "CLI");
+    }
+
+    #[test]
+    fn errors_for_unknown_plugin() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "info", "unknown"])
+            .assert()
+            .code(4)
+            .stderr(predicate::str::contains("proto::plugin::missing"));
+    }
+
+    #[test]
+    fn errors_for_invalid_locator() {
+        let temp = create_empty_sandbox();
+        temp.create_file(".prototools", "[plugins]\nmoon = \"unknown:moon\"\n");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "info", "moon"])
+            .assert()
+            .code(3)
+            .stderr(predicate::str::contains("proto::config::invalid_plugin"))
+            .stderr(predicate::str::contains("Unknown plugin scope"));
+    }
+}
+
+mod refresh {
+    use super::*;
+
+    #[test]
+    fn skips_plugins_not_using_latest() {
+        let temp = create_sandbox_with_schema();
+        temp.create_file(".prototools", "[plugins]\nmoon = \"source:./moon.toml\"\n");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.args(["plugins", "refresh", "moon"])
+            .assert()
+            .success()
+            .stderr(predicate::str::contains(
+                "No plugins using a latest version",
+            ));
+    }
+}
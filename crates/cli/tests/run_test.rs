@@ -161,6 +161,26 @@ fn runs_a_tool_alt_bin() {
     assert.stdout(predicate::str::contains("8.19.2"));
 }
 
+#[test]
+fn errors_if_alt_bin_missing() {
+    let temp = create_empty_sandbox();
+    create_fake_node(&temp, &["18.0.0"]);
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .env("PROTO_OFFLINE", "1")
+        .arg("run")
+        .arg("node")
+        .arg("18.0.0")
+        .arg("--bin")
+        .arg("bin/unknown")
+        .assert();
+
+    assert
+        .code(7)
+        .stderr(predicate::str::contains("proto::execute::missing_alt_bin"));
+}
+
 #[test]
 fn updates_last_used_at() {
     let temp = create_empty_sandbox();
@@ -289,3 +309,168 @@ fn errors_if_plugin_not_configured() {
         "plugin-name is not a built-in tool and has not been configured",
     ));
 }
+
+#[cfg(unix)]
+mod detect_cache {
+    use super::*;
+    use proto_core::DetectCache;
+
+    fn run_node(temp: &Sandbox) -> starbase_sandbox::assert_cmd::assert::Assert {
+        let mut cmd = create_proto_command(temp.path());
+        cmd.env("PROTO_OFFLINE", "1")
+            .env("PROTO_SKIP_USED_AT", "1")
+            .arg("run")
+            .arg("node")
+            .assert()
+    }
+
+    fn load_cache(temp: &Sandbox) -> DetectCache {
+        DetectCache::load_from(temp.path().join("temp/detect"), "node").unwrap()
+    }
+
+    #[test]
+    fn caches_detected_version() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+        temp.create_file(".prototools", "node = \"18.0.0\"");
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 18.0.0"));
+
+        let cache = load_cache(&temp);
+        let entry = cache.entries.get(temp.path()).unwrap();
+
+        assert_eq!(entry.version, "18.0.0");
+        assert!(entry
+            .files
+            .iter()
+            .any(|file| file.path == temp.path().join(".prototools")));
+    }
+
+    #[test]
+    fn uses_cached_version() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0", "19.0.0"]);
+        temp.create_file(".prototools", "node = \"18.0.0\"");
+
+        run_node(&temp).success();
+
+        // Tamper with the entry to verify that detection was skipped
+        let mut cache = load_cache(&temp);
+        cache.entries.get_mut(temp.path()).unwrap().version = "19.0.0".into();
+        cache.save().unwrap();
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 19.0.0"));
+    }
+
+    #[test]
+    fn invalidates_when_config_changes() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0", "19.0.0"]);
+        temp.create_file(".prototools", "node = \"18.0.0\"");
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 18.0.0"));
+
+        temp.create_file(".prototools", "node = \"19.0.0\"");
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 19.0.0"));
+    }
+
+    #[test]
+    fn invalidates_when_version_file_is_created() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0", "19.0.0"]);
+
+        let mut manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+        manifest.default_version = Some("18.0.0".into());
+        manifest.save().unwrap();
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 18.0.0"));
+
+        temp.create_file(".nvmrc", "19.0.0");
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 19.0.0"));
+    }
+
+    #[test]
+    fn invalidates_when_manifest_changes() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0", "19.0.0"]);
+
+        let mut manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+        manifest.default_version = Some("18.0.0".into());
+        manifest.save().unwrap();
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 18.0.0"));
+
+        let mut manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+        manifest.default_version = Some("19.0.0".into());
+        manifest.save().unwrap();
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 19.0.0"));
+    }
+
+    #[test]
+    fn applies_inline_env_vars() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+        temp.create_file(
+            ".prototools",
+            "node = \"18.0.0\"\nenv = { KEY = \"value\" }",
+        );
+        temp.create_file(
+            "tools/node/18.0.0/bin/node",
+            "#!/bin/sh\necho \"node 18.0.0 $KEY\"\n",
+        );
+
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 18.0.0 value"));
+
+        let cache = load_cache(&temp);
+        let entry = cache.entries.get(temp.path()).unwrap();
+
+        assert_eq!(entry.env.get("KEY").unwrap(), "value");
+
+        // Second run is resolved from the cache
+        run_node(&temp)
+            .success()
+            .stdout(predicate::str::contains("node 18.0.0 value"));
+    }
+
+    #[test]
+    fn skips_cache_for_env_var() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0", "19.0.0"]);
+        temp.create_file(".prototools", "node = \"18.0.0\"");
+
+        run_node(&temp).success();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd
+            .env("PROTO_OFFLINE", "1")
+            .env("PROTO_NODE_VERSION", "19.0.0")
+            .arg("run")
+            .arg("node")
+            .assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("node 19.0.0"));
+    }
+}
@@ -0,0 +1,57 @@
+mod utils;
+
+use starbase_sandbox::predicates::prelude::*;
+use utils::*;
+
+#[test]
+fn generates_prototools_schema() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("schema").arg("prototools").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains(r#""title": "ToolsConfig""#))
+        .stdout(predicate::str::contains(r#""install-args""#))
+        .stdout(predicate::str::contains(r#""PluginLocator""#));
+}
+
+#[test]
+fn generates_user_config_schema() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("schema").arg("user-config").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains(r#""title": "UserConfig""#))
+        .stdout(predicate::str::contains(r#""node-intercept-globals""#));
+}
+
+#[test]
+fn generates_plugin_schema() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("schema").arg("plugin").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains(r#""PlatformMapper""#))
+        .stdout(predicate::str::contains(r#""InstallSchema""#))
+        .stdout(predicate::str::contains(r#""ResolveSchema""#))
+        .stdout(predicate::str::contains(r#""ShimSchema""#))
+        .stdout(predicate::str::contains(r#""git-tag-pattern""#));
+}
+
+#[test]
+fn errors_for_unknown_kind() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("schema").arg("unknown").assert();
+
+    assert.failure();
+}
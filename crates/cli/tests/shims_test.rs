@@ -0,0 +1,161 @@
+mod utils;
+
+use proto_core::{ManifestVersion, ShimEntry, ShimRegistry};
+use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json::{self, JsonValue};
+use utils::*;
+
+fn register_shim(temp: &Sandbox, name: &str, tool: &str) {
+    let mut registry = ShimRegistry::load_from(temp.path()).unwrap();
+
+    registry
+        .register(
+            name,
+            ShimEntry {
+                tool: tool.into(),
+                ..ShimEntry::default()
+            },
+        )
+        .unwrap();
+
+    temp.create_file(format!("bin/{name}"), "");
+}
+
+mod shims_list {
+    use super::*;
+
+    #[test]
+    fn lists_shims_by_tool() {
+        let temp = create_empty_sandbox();
+        register_shim(&temp, "node", "node");
+        register_shim(&temp, "npm", "npm");
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("shims").arg("list").assert();
+
+        assert
+            .success()
+            .stdout(predicate::str::contains("node"))
+            .stdout(predicate::str::contains("npm"));
+    }
+
+    #[test]
+    fn marks_missing_shims_in_json() {
+        let temp = create_empty_sandbox();
+        register_shim(&temp, "node", "node");
+        register_shim(&temp, "npx", "node");
+
+        std::fs::remove_file(temp.path().join("bin/npx")).unwrap();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("shims").arg("list").arg("--json").assert();
+
+        let output: JsonValue = json::from_str(&output_to_string(&assert.get_output().stdout))
+            .expect("Output should be JSON");
+        let shims = output["node"]["shims"].as_array().unwrap();
+
+        assert_eq!(shims.len(), 2);
+        assert_eq!(shims[0]["name"], "node");
+        assert!(shims[0]["path"].is_string());
+        assert_eq!(shims[1]["name"], "npx");
+        assert!(shims[1]["path"].is_null());
+    }
+}
+
+mod shims_prune {
+    use super::*;
+
+    #[test]
+    fn removes_shims_for_uninstalled_tools() {
+        let temp = create_empty_sandbox();
+        create_manifest(&temp, "node", &["18.0.0"], ManifestVersion::default());
+        register_shim(&temp, "node", "node");
+        register_shim(&temp, "npm", "npm");
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("shims").arg("prune").assert();
+
+        assert.success().stdout(predicate::str::contains("bin/npm"));
+
+        let registry = ShimRegistry::load_from(temp.path()).unwrap();
+
+        assert!(temp.path().join("bin/node").exists());
+        assert!(!temp.path().join("bin/npm").exists());
+        assert!(registry.shims.contains_key("node"));
+        assert!(!registry.shims.contains_key("npm"));
+    }
+
+    #[test]
+    fn unregisters_missing_shims() {
+        let temp = create_empty_sandbox();
+        create_manifest(&temp, "node", &["18.0.0"], ManifestVersion::default());
+        register_shim(&temp, "node", "node");
+
+        std::fs::remove_file(temp.path().join("bin/node")).unwrap();
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.arg("shims").arg("prune").assert().success();
+
+        let registry = ShimRegistry::load_from(temp.path()).unwrap();
+
+        assert!(registry.shims.is_empty());
+    }
+}
+
+mod shims_regen {
+    use super::*;
+
+    #[test]
+    fn does_nothing_without_tools() {
+        let temp = create_empty_sandbox();
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("shims").arg("regen").assert();
+
+        assert
+            .success()
+            .stderr(predicate::str::contains("No installed tools"));
+    }
+}
+
+mod uninstall {
+    use super::*;
+
+    #[test]
+    fn removes_global_shims_after_last_version() {
+        let temp = create_empty_sandbox();
+        create_manifest(
+            &temp,
+            "node",
+            &["18.0.0", "19.0.0"],
+            ManifestVersion::default(),
+        );
+        register_shim(&temp, "node", "node");
+        register_shim(&temp, "npx", "node");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.env("PROTO_OFFLINE", "1")
+            .arg("uninstall")
+            .arg("node")
+            .arg("18.0.0")
+            .assert()
+            .success();
+
+        assert!(temp.path().join("bin/node").exists());
+        assert!(temp.path().join("bin/npx").exists());
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.env("PROTO_OFFLINE", "1")
+            .arg("uninstall")
+            .arg("node")
+            .arg("19.0.0")
+            .assert()
+            .success();
+
+        let registry = ShimRegistry::load_from(temp.path()).unwrap();
+
+        assert!(!temp.path().join("bin/node").exists());
+        assert!(!temp.path().join("bin/npx").exists());
+        assert!(registry.shims.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+mod utils;
+
+use proto_core::Manifest;
+use starbase_sandbox::predicates::prelude::*;
+use starbase_utils::json::{self, JsonValue};
+use utils::*;
+
+fn install_fake_node(temp: &Sandbox, version: &str) {
+    temp.create_file(format!("tools/node/{version}/bin/node"), "");
+    temp.create_file(format!("tools/node/{version}/node.exe"), "");
+
+    let mut manifest = Manifest::load_from(temp.path().join("tools/node")).unwrap();
+    manifest.insert_version(version, None).unwrap();
+    manifest.save().unwrap();
+}
+
+fn get_status_json(temp: &Sandbox) -> JsonValue {
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .env("PROTO_OFFLINE", "1")
+        .arg("status")
+        .arg("--json")
+        .assert()
+        .success();
+
+    json::from_str(&output_to_string(&assert.get_output().stdout)).expect("Output should be JSON")
+}
+
+#[test]
+fn prints_message_when_nothing_configured() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("status").assert();
+
+    assert
+        .success()
+        .stderr(predicate::str::contains("No tools have been configured"));
+}
+
+#[test]
+fn includes_installed_tools_from_config() {
+    let temp = create_empty_sandbox();
+    temp.create_file(".prototools", "node = \"18.0.0\"");
+    install_fake_node(&temp, "18.0.0");
+
+    let output = get_status_json(&temp);
+    let item = &output[0];
+
+    assert_eq!(item["tool"], "node");
+    assert_eq!(item["spec"], "18.0.0");
+    assert_eq!(item["version"], "18.0.0");
+    assert_eq!(item["source"], "config");
+    assert!(item["source_file"]
+        .as_str()
+        .unwrap()
+        .ends_with(".prototools"));
+    assert_eq!(item["installed"], true);
+    assert!(item["bin_path"].as_str().unwrap().contains("18.0.0"));
+}
+
+#[test]
+fn includes_tools_from_ecosystem() {
+    let temp = create_empty_sandbox();
+    temp.create_file(".nvmrc", "19.0.0");
+
+    let output = get_status_json(&temp);
+    let item = &output[0];
+
+    assert_eq!(item["tool"], "node");
+    assert_eq!(item["version"], "19.0.0");
+    assert_eq!(item["source"], "ecosystem");
+    assert!(item["source_file"].as_str().unwrap().ends_with(".nvmrc"));
+    assert_eq!(item["installed"], false);
+    assert!(item["bin_path"].is_null());
+}
+
+#[test]
+fn suggests_install_when_missing() {
+    let temp = create_empty_sandbox();
+    temp.create_file(".prototools", "node = \"19.0.0\"");
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.env("PROTO_OFFLINE", "1").arg("status").assert();
+
+    assert
+        .success()
+        .stdout(predicate::str::contains("Not installed"))
+        .stdout(predicate::str::contains("proto install node 19.0.0"));
+}
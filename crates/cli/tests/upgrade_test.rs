@@ -0,0 +1,127 @@
+mod utils;
+
+use starbase_sandbox::predicates::prelude::*;
+use utils::*;
+
+#[test]
+fn errors_for_invalid_version() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("upgrade").arg("--version").arg("1.2").assert();
+
+    assert
+        .code(6)
+        .stderr(predicate::str::contains("Invalid version 1.2"));
+}
+
+#[test]
+fn errors_if_rollback_with_version() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd
+        .arg("upgrade")
+        .arg("--version")
+        .arg("1.0.0")
+        .arg("--rollback")
+        .assert();
+
+    assert.failure();
+}
+
+#[test]
+fn errors_if_nothing_to_rollback() {
+    let temp = create_empty_sandbox();
+
+    let mut cmd = create_proto_command(temp.path());
+    let assert = cmd.arg("upgrade").arg("--rollback").assert();
+
+    assert
+        .code(7)
+        .stderr(predicate::str::contains("No previous version of proto"));
+}
+
+#[cfg(unix)]
+mod rollback {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn create_fake_proto(temp: &Sandbox, name: &str, script: &str) {
+        let path = temp.path().join("bin").join(name);
+
+        temp.create_file(format!("bin/{name}"), format!("#!/bin/sh\n{script}\n"));
+
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn swaps_with_previous_binary() {
+        let temp = create_empty_sandbox();
+        create_fake_proto(&temp, "proto", "echo \"proto 0.2.0\"");
+        create_fake_proto(&temp, "proto-old", "echo \"proto 0.1.0\"");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.arg("upgrade").arg("--rollback").assert().success();
+
+        let bin = fs::read_to_string(temp.path().join("bin/proto")).unwrap();
+        let old_bin = fs::read_to_string(temp.path().join("bin/proto-old")).unwrap();
+
+        assert!(bin.contains("0.1.0"));
+        assert!(old_bin.contains("0.2.0"));
+        assert!(!temp.path().join("bin/proto-swap").exists());
+    }
+
+    #[test]
+    fn swaps_with_previous_shim() {
+        let temp = create_empty_sandbox();
+        create_fake_proto(&temp, "proto", "echo \"proto 0.2.0\"");
+        create_fake_proto(&temp, "proto-old", "echo \"proto 0.1.0\"");
+        temp.create_file("bin/proto-shim", "0.2.0");
+        temp.create_file("bin/proto-shim-old", "0.1.0");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.arg("upgrade").arg("--rollback").assert().success();
+
+        let shim = fs::read_to_string(temp.path().join("bin/proto-shim")).unwrap();
+        let old_shim = fs::read_to_string(temp.path().join("bin/proto-shim-old")).unwrap();
+
+        assert_eq!(shim, "0.1.0");
+        assert_eq!(old_shim, "0.2.0");
+        assert!(!temp.path().join("bin/proto-shim-swap").exists());
+    }
+
+    #[test]
+    fn keeps_shim_without_previous_shim() {
+        let temp = create_empty_sandbox();
+        create_fake_proto(&temp, "proto", "echo \"proto 0.2.0\"");
+        create_fake_proto(&temp, "proto-old", "echo \"proto 0.1.0\"");
+        temp.create_file("bin/proto-shim", "0.2.0");
+
+        let mut cmd = create_proto_command(temp.path());
+        cmd.arg("upgrade").arg("--rollback").assert().success();
+
+        let shim = fs::read_to_string(temp.path().join("bin/proto-shim")).unwrap();
+
+        assert_eq!(shim, "0.2.0");
+    }
+
+    #[test]
+    fn aborts_if_previous_binary_fails() {
+        let temp = create_empty_sandbox();
+        create_fake_proto(&temp, "proto", "echo \"proto 0.2.0\"");
+        create_fake_proto(&temp, "proto-old", "exit 1");
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.arg("upgrade").arg("--rollback").assert();
+
+        assert
+            .code(7)
+            .stderr(predicate::str::contains("Smoke test failed"));
+
+        let bin = fs::read_to_string(temp.path().join("bin/proto")).unwrap();
+
+        assert!(bin.contains("0.2.0"));
+    }
+}
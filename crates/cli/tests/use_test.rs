@@ -27,6 +27,9 @@ deno = "1.30.0"
     assert!(node_path.exists());
     assert!(npm_path.exists());
     assert!(deno_path.exists());
+
+    // Installed after Node.js, alongside the configured version
+    assert!(temp.path().join("tools/npm/8.19.2").exists());
 }
 
 #[test]
@@ -56,3 +59,54 @@ fn installs_tool_via_detection() {
 
     assert!(node_path.exists());
 }
+
+mod failures {
+    use super::*;
+    use starbase_sandbox::predicates::prelude::*;
+
+    #[test]
+    fn reports_each_tool_instead_of_aborting() {
+        let temp = create_empty_sandbox();
+        create_fake_node(&temp, &["18.0.0"]);
+
+        temp.create_file(
+            ".prototools",
+            r#"node = "18.0.0"
+unknown-tool = "1.0.0"
+"#,
+        );
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.env("PROTO_OFFLINE", "1").arg("use").assert();
+
+        assert
+            .failure()
+            .stdout(predicate::str::contains("node@18.0.0"))
+            .stdout(predicate::str::contains("already installed"))
+            .stdout(predicate::str::contains("unknown-tool@1.0.0"))
+            .stderr(predicate::str::contains("Failed to install 1 of 2 tools"));
+    }
+
+    #[test]
+    fn skips_dependency_managers_when_language_fails() {
+        let temp = create_empty_sandbox();
+
+        temp.create_file(
+            ".prototools",
+            r#"node = "unknown"
+npm = "bundled"
+"#,
+        );
+
+        let mut cmd = create_proto_command(temp.path());
+        let assert = cmd.env("PROTO_OFFLINE", "1").arg("use").assert();
+
+        assert
+            .failure()
+            .stdout(predicate::str::contains("npm@bundled"))
+            .stdout(predicate::str::contains(
+                "Skipped, as node failed to install.",
+            ))
+            .stderr(predicate::str::contains("Failed to install 2 of 2 tools"));
+    }
+}
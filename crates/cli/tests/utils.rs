@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use proto_core::{Manifest, ManifestVersion, ShimEntry, ShimRegistry, SHIM_VERSION};
 use starbase_sandbox::{assert_cmd, create_command_with_name};
 pub use starbase_sandbox::{create_empty_sandbox, output_to_string, Sandbox};
 use std::path::Path;
@@ -30,3 +31,77 @@ pub fn create_proto_command<T: AsRef<Path>>(path: T) -> assert_cmd::Command {
     cmd.env("PROTO_TEST", "true");
     cmd
 }
+
+/// Create a manifest for the tool with the provided versions installed, each with
+/// the provided metadata and an empty binary. The saved manifest is returned, so
+/// that it can be customized further.
+pub fn create_manifest(
+    temp: &Sandbox,
+    tool: &str,
+    versions: &[&str],
+    metadata: ManifestVersion,
+) -> Manifest {
+    let mut manifest = Manifest::load_from(temp.path().join("tools").join(tool)).unwrap();
+    manifest.shim_version = SHIM_VERSION;
+
+    for version in versions {
+        manifest
+            .insert_version_with(version, None, metadata.clone())
+            .unwrap();
+
+        temp.create_file(format!("tools/{tool}/{version}/bin/{tool}"), "");
+        temp.create_file(format!("tools/{tool}/{version}/{tool}.exe"), "");
+    }
+
+    manifest.save().unwrap();
+    manifest
+}
+
+/// Create an installed Node.js for each version, whose binary prints its version.
+pub fn create_fake_node(temp: &Sandbox, versions: &[&str]) -> Manifest {
+    let manifest = create_manifest(temp, "node", versions, ManifestVersion::default());
+
+    for version in versions {
+        temp.create_file(
+            format!("tools/node/{version}/bin/node"),
+            format!("#!/bin/sh\necho \"node {version}\"\n"),
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::set_permissions(
+                temp.path().join(format!("tools/node/{version}/bin/node")),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+        }
+    }
+
+    manifest
+}
+
+/// Create an installed npm, whose binary is a script that's ran through node,
+/// along with its global shim.
+pub fn create_fake_npm(temp: &Sandbox) {
+    create_manifest(temp, "npm", &["9.0.0"], ManifestVersion::default());
+
+    temp.create_file(
+        "tools/npm/9.0.0/package.json",
+        r#"{"bin":{"npm":"bin/npm-cli.js"}}"#,
+    );
+    temp.create_file("tools/npm/9.0.0/bin/npm-cli.js", "");
+
+    ShimRegistry::load_from(temp.path())
+        .unwrap()
+        .register(
+            "npm",
+            ShimEntry {
+                tool: "npm".into(),
+                parent_bin: Some("node".into()),
+                ..ShimEntry::default()
+            },
+        )
+        .unwrap();
+}
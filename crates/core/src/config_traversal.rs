@@ -0,0 +1,173 @@
+use crate::errors::ProtoError;
+use crate::tools_config::{ToolsConfig, TOOLS_CONFIG_NAME};
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tracing::trace;
+
+pub const CONFIG_ROOT_ENV_VAR: &str = "PROTO_CONFIG_ROOT";
+pub const CONFIG_STOP_AT_GIT_ENV_VAR: &str = "PROTO_CONFIG_STOP_AT_GIT";
+
+static CONFIG_CACHE: Lazy<RwLock<FxHashMap<PathBuf, Arc<ToolsConfig>>>> =
+    Lazy::new(|| RwLock::new(FxHashMap::default()));
+
+/// Load the `.prototools` file within the provided directory, caching the
+/// parsed result for the lifetime of the process.
+pub fn load_cached_config(dir: &Path) -> Result<Arc<ToolsConfig>, ProtoError> {
+    if let Some(config) = CONFIG_CACHE.read().unwrap().get(dir) {
+        return Ok(Arc::clone(config));
+    }
+
+    let config = Arc::new(ToolsConfig::load_from(dir)?);
+
+    CONFIG_CACHE
+        .write()
+        .unwrap()
+        .insert(dir.to_path_buf(), Arc::clone(&config));
+
+    Ok(config)
+}
+
+/// Remove a directory's `.prototools` from the cache, so that it's re-parsed on next load.
+pub fn invalidate_cached_config(dir: &Path) {
+    CONFIG_CACHE.write().unwrap().remove(dir);
+}
+
+/// A directory visited while traversing upwards, and its `.prototools` file.
+#[derive(Debug)]
+pub struct TraversedDir {
+    pub dir: PathBuf,
+    pub config: Arc<ToolsConfig>,
+}
+
+/// Traverses upwards from a starting directory, yielding each directory and its
+/// `.prototools` file, until a boundary is reached. A boundary is a directory that:
+///
+/// - Contains a `.prototools` file with `root = true`.
+/// - Matches the `PROTO_CONFIG_ROOT` environment variable.
+/// - Contains a `.git` directory or file (the repository root). Can be disabled
+///   with `PROTO_CONFIG_STOP_AT_GIT=false`.
+///
+/// The boundary directory itself is always included.
+#[derive(Clone, Debug)]
+pub struct ConfigTraversal {
+    pub root_dir: Option<PathBuf>,
+    pub stop_at_git: bool,
+}
+
+impl Default for ConfigTraversal {
+    fn default() -> Self {
+        ConfigTraversal {
+            root_dir: get_config_root(),
+            stop_at_git: is_stop_at_git_enabled(),
+        }
+    }
+}
+
+/// Return the directory defined by `PROTO_CONFIG_ROOT`, if set.
+pub fn get_config_root() -> Option<PathBuf> {
+    env::var_os(CONFIG_ROOT_ENV_VAR)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Return true if traversal should stop at the repository root. Enabled by default,
+/// and can be disabled with `PROTO_CONFIG_STOP_AT_GIT`.
+pub fn is_stop_at_git_enabled() -> bool {
+    env::var(CONFIG_STOP_AT_GIT_ENV_VAR).map_or(true, |value| {
+        value != "0" && value != "false" && value != "no" && value != "off"
+    })
+}
+
+fn is_same_dir(a: &Path, b: &Path) -> bool {
+    if a == b {
+        return true;
+    }
+
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+impl ConfigTraversal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn traverse(&self) -> ConfigTraversalIter {
+        let working_dir = env::current_dir().expect("Unknown current working directory!");
+
+        self.traverse_from(working_dir)
+    }
+
+    pub fn traverse_from<P: AsRef<Path>>(&self, starting_dir: P) -> ConfigTraversalIter {
+        trace!(
+            dir = ?starting_dir.as_ref(),
+            "Traversing upwards for {} files",
+            TOOLS_CONFIG_NAME,
+        );
+
+        ConfigTraversalIter {
+            current_dir: Some(starting_dir.as_ref().to_path_buf()),
+            traversal: self,
+        }
+    }
+
+    pub fn is_boundary(&self, dir: &Path, config: &ToolsConfig) -> bool {
+        if config.root {
+            trace!(file = ?config.path, "Found root config, stopping traversal");
+
+            return true;
+        }
+
+        if self
+            .root_dir
+            .as_ref()
+            .is_some_and(|root| is_same_dir(root, dir))
+        {
+            trace!(
+                dir = ?dir,
+                "Reached {} directory, stopping traversal",
+                CONFIG_ROOT_ENV_VAR,
+            );
+
+            return true;
+        }
+
+        if self.stop_at_git && dir.join(".git").exists() {
+            trace!(dir = ?dir, "Reached repository root, stopping traversal");
+
+            return true;
+        }
+
+        false
+    }
+}
+
+pub struct ConfigTraversalIter<'t> {
+    current_dir: Option<PathBuf>,
+    traversal: &'t ConfigTraversal,
+}
+
+impl Iterator for ConfigTraversalIter<'_> {
+    type Item = Result<TraversedDir, ProtoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let dir = self.current_dir.take()?;
+
+        let config = match load_cached_config(&dir) {
+            Ok(config) => config,
+            Err(error) => return Some(Err(error)),
+        };
+
+        if !self.traversal.is_boundary(&dir, &config) {
+            self.current_dir = dir.parent().map(|parent| parent.to_path_buf());
+        }
+
+        Some(Ok(TraversedDir { dir, config }))
+    }
+}
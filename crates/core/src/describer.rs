@@ -1,3 +1,15 @@
+use std::path::PathBuf;
+use warpgate::PluginLocator;
+
+/// Where a plugin-provided tool was loaded from.
+#[derive(Clone, Debug)]
+pub struct PluginSource {
+    /// Locator the plugin was resolved from.
+    pub locator: PluginLocator,
+    /// Absolute path to the downloaded or local plugin file.
+    pub path: PathBuf,
+}
+
 #[async_trait::async_trait]
 pub trait Describable<'tool>: Send + Sync {
     /// Return an identifier for the tool. Will also be used in variables,
@@ -6,4 +18,10 @@ pub trait Describable<'tool>: Send + Sync {
 
     /// Return a human readable name of the tool.
     fn get_name(&self) -> String;
+
+    /// Return the source of the plugin that provides the tool,
+    /// or `None` if the tool is built-in.
+    fn get_plugin_source(&self) -> Option<&PluginSource> {
+        None
+    }
 }
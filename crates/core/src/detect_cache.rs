@@ -0,0 +1,285 @@
+#![allow(clippy::borrowed_box)]
+
+use crate::config_traversal::{
+    get_config_root, is_stop_at_git_enabled, ConfigTraversal, TraversedDir, CONFIG_ROOT_ENV_VAR,
+    CONFIG_STOP_AT_GIT_ENV_VAR,
+};
+use crate::detector::{DetectStep, DetectTrace};
+use crate::errors::ProtoError;
+use crate::helpers::{get_modified_time, get_temp_dir, is_cache_enabled, now};
+use crate::manifest::Manifest;
+use crate::tool::Tool;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use starbase_utils::{fs, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+pub const DETECT_CACHE_DIR: &str = "detect";
+
+// Versions may resolve differently once new versions are released,
+// so align with the lifetime of the remote versions manifest
+const MAX_AGE: Duration = Duration::from_secs(60 * 60 * 24);
+
+const MAX_ENTRIES: usize = 250;
+
+/// A file that was consulted while detecting a version. Files that did not exist
+/// are also watched, so that creating them will invalidate the entry.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WatchedFile {
+    pub path: PathBuf,
+    pub modified: Option<u128>,
+}
+
+impl WatchedFile {
+    pub fn new(path: PathBuf) -> Self {
+        WatchedFile {
+            modified: get_modified_time(&path).map(|time| time.as_nanos()),
+            path,
+        }
+    }
+
+    pub fn is_stale(&self) -> bool {
+        get_modified_time(&self.path).map(|time| time.as_nanos()) != self.modified
+    }
+}
+
+/// Parts of the tool's manifest that affect detection and resolution.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ManifestState {
+    pub aliases: BTreeMap<String, String>,
+    pub default_version: Option<String>,
+    pub installed_versions: BTreeSet<String>,
+}
+
+impl From<&Manifest> for ManifestState {
+    fn from(manifest: &Manifest) -> Self {
+        ManifestState {
+            aliases: BTreeMap::from_iter(manifest.aliases.clone()),
+            default_version: manifest.default_version.clone(),
+            installed_versions: BTreeSet::from_iter(manifest.installed_versions.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DetectCacheEntry {
+    /// The fully resolved version.
+    pub version: String,
+    pub cached_at: u128,
+    pub config_root: Option<PathBuf>,
+    #[serde(default = "default_stop_at_git")]
+    pub stop_at_git: bool,
+    /// Environment variables from `.prototools` to pass to the tool.
+    pub env: BTreeMap<String, String>,
+    pub files: Vec<WatchedFile>,
+    pub manifest: ManifestState,
+}
+
+impl DetectCacheEntry {
+    /// Create an entry from the steps taken to detect a version. Returns `None`
+    /// if the version was explicitly provided, if the files that a tool detects
+    /// from are unknown, if a config interpolates environment variables, or if
+    /// the tool's default version was detected (npm's `bundled` resolves from
+    /// the detected Node.js version), as the entry could not be reliably invalidated.
+    pub fn from_trace<'l, T: Tool<'l> + ?Sized>(
+        tool: &Box<T>,
+        trace: &DetectTrace,
+        working_dir: &Path,
+        resolved_version: &str,
+        tool_env: &FxHashMap<String, String>,
+    ) -> Result<Option<Self>, ProtoError> {
+        let Some(version_files) = tool.get_version_files() else {
+            return Ok(None);
+        };
+
+        if trace.version.is_some() && trace.version.as_deref() == tool.get_default_version() {
+            return Ok(None);
+        }
+
+        let mut files = vec![];
+
+        // Every config is loaded when running a tool, not just those used for detection
+        for entry in ConfigTraversal::new().traverse_from(working_dir) {
+            let TraversedDir { config, .. } = entry?;
+
+            if config.path.exists() && fs::read_file(&config.path)?.contains("${") {
+                return Ok(None);
+            }
+
+            files.push(WatchedFile::new(config.path.clone()));
+        }
+
+        for step in &trace.steps {
+            let watch_paths = match step {
+                DetectStep::CliArgument { version: Some(_) }
+                | DetectStep::EnvVar {
+                    version: Some(_), ..
+                } => {
+                    return Ok(None);
+                }
+                DetectStep::Directory { dir } => version_files
+                    .iter()
+                    .map(|file| dir.join(file))
+                    .collect::<Vec<_>>(),
+                DetectStep::EcosystemFile { file, .. } => {
+                    // The tool did not report which file it detected from
+                    if file.is_dir() {
+                        return Ok(None);
+                    }
+
+                    vec![file.to_owned()]
+                }
+                _ => vec![],
+            };
+
+            for path in watch_paths {
+                if !files.iter().any(|file| file.path == path) {
+                    files.push(WatchedFile::new(path));
+                }
+            }
+        }
+
+        Ok(Some(DetectCacheEntry {
+            version: resolved_version.to_owned(),
+            cached_at: now(),
+            config_root: get_config_root(),
+            stop_at_git: is_stop_at_git_enabled(),
+            env: BTreeMap::from_iter(tool_env.clone()),
+            files,
+            manifest: ManifestState::from(tool.get_manifest()?),
+        }))
+    }
+
+    pub fn is_valid(&self, manifest: &Manifest) -> bool {
+        if now().saturating_sub(self.cached_at) > MAX_AGE.as_millis() {
+            debug!("Cache entry has expired");
+
+            return false;
+        }
+
+        if self.config_root != get_config_root() {
+            debug!("{} has changed", CONFIG_ROOT_ENV_VAR);
+
+            return false;
+        }
+
+        if self.stop_at_git != is_stop_at_git_enabled() {
+            debug!("{} has changed", CONFIG_STOP_AT_GIT_ENV_VAR);
+
+            return false;
+        }
+
+        if self.manifest != ManifestState::from(manifest) {
+            debug!(file = ?manifest.path, "Manifest has changed");
+
+            return false;
+        }
+
+        if let Some(file) = self.files.iter().find(|file| file.is_stale()) {
+            debug!(file = ?file.path, "Watched file has changed");
+
+            return false;
+        }
+
+        true
+    }
+}
+
+fn default_stop_at_git() -> bool {
+    true
+}
+
+/// A persistent cache of versions detected for a tool, keyed by working directory.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DetectCache {
+    pub entries: BTreeMap<PathBuf, DetectCacheEntry>,
+
+    #[serde(skip)]
+    pub id: String,
+
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl DetectCache {
+    pub fn load_for(id: &str) -> Result<Self, ProtoError> {
+        Self::load_from(get_temp_dir()?.join(DETECT_CACHE_DIR), id)
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub fn load_from<P: AsRef<Path>>(dir: P, id: &str) -> Result<Self, ProtoError> {
+        let path = dir.as_ref().join(format!("{id}.json"));
+
+        debug!(file = ?path, "Loading detection cache");
+
+        // The cache is not critical, so ignore files that fail to parse
+        let mut cache: DetectCache = if path.exists() && is_cache_enabled() {
+            json::read_file(&path).unwrap_or_default()
+        } else {
+            DetectCache::default()
+        };
+
+        cache.id = id.to_owned();
+        cache.path = path;
+
+        Ok(cache)
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub fn save(&self) -> Result<(), ProtoError> {
+        debug!(file = ?self.path, "Saving detection cache");
+
+        json::write_file(&self.path, self, false)?;
+
+        Ok(())
+    }
+
+    /// Return the cached version for a working directory, if it's still valid.
+    pub fn get(&self, working_dir: &Path, manifest: &Manifest) -> Option<&DetectCacheEntry> {
+        if !is_cache_enabled()
+            || env::var_os(format!("PROTO_{}_VERSION", self.id.to_uppercase())).is_some()
+        {
+            return None;
+        }
+
+        let entry = self.entries.get(working_dir)?;
+
+        if !entry.is_valid(manifest) {
+            return None;
+        }
+
+        debug!(
+            tool = &self.id,
+            dir = ?working_dir,
+            version = &entry.version,
+            "Using cached version for working directory"
+        );
+
+        Some(entry)
+    }
+
+    /// Insert an entry for a working directory, evicting the oldest entries
+    /// when the cache is full.
+    pub fn insert(&mut self, working_dir: &Path, entry: DetectCacheEntry) {
+        self.entries.insert(working_dir.to_path_buf(), entry);
+
+        while self.entries.len() > MAX_ENTRIES {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.cached_at)
+                .map(|(dir, _)| dir.to_owned())
+            else {
+                break;
+            };
+
+            self.entries.remove(&oldest);
+        }
+    }
+}
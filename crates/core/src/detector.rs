@@ -1,14 +1,16 @@
 #![allow(clippy::borrowed_box)]
 
+use crate::config_traversal::{ConfigTraversal, TraversedDir};
 use crate::errors::ProtoError;
 use crate::helpers::{is_alias_name, remove_v_prefix};
 use crate::manifest::Manifest;
+use crate::resolver::parse_version;
 use crate::tool::Tool;
-use crate::tools_config::ToolsConfig;
 use human_sort::compare;
-use lenient_semver::Version;
+use serde::Serialize;
 use starbase_utils::fs;
-use std::{env, path::Path};
+use std::env;
+use std::path::{Path, PathBuf};
 use tracing::{debug, trace};
 
 #[async_trait::async_trait]
@@ -17,6 +19,72 @@ pub trait Detector<'tool>: Send + Sync {
     async fn detect_version_from(&self, _working_dir: &Path) -> Result<Option<String>, ProtoError> {
         Ok(None)
     }
+
+    /// Attempt to detect an applicable version from the provided working directory,
+    /// and return the file it was detected from. If the file is not known,
+    /// the working directory will be returned instead.
+    async fn detect_version_with_source(
+        &self,
+        working_dir: &Path,
+    ) -> Result<Option<(String, PathBuf)>, ProtoError> {
+        Ok(self
+            .detect_version_from(working_dir)
+            .await?
+            .map(|version| (version, working_dir.to_path_buf())))
+    }
+
+    /// Return the names of files that a version may be detected from, if known.
+    /// Native shims defer to proto when one of these files is encountered.
+    fn get_version_files(&self) -> Option<Vec<String>> {
+        None
+    }
+}
+
+/// A single step taken while detecting a version, in the order it was taken.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "step", rename_all = "kebab-case")]
+pub enum DetectStep {
+    /// Version explicitly passed on the command line.
+    CliArgument { version: Option<String> },
+
+    /// Version provided by a `PROTO_<TOOL>_VERSION` environment variable.
+    EnvVar {
+        name: String,
+        version: Option<String>,
+    },
+
+    /// Directory that was checked while traversing upwards.
+    Directory { dir: PathBuf },
+
+    /// A `.prototools` file that was found in a directory.
+    ConfigFile {
+        file: PathBuf,
+        version: Option<String>,
+    },
+
+    /// A version file from the tool's ecosystem (`.nvmrc`, etc).
+    EcosystemFile {
+        file: PathBuf,
+        version: String,
+        expanded: Option<String>,
+    },
+
+    /// The global default version from the tool's manifest.
+    GlobalDefault {
+        file: PathBuf,
+        version: Option<String>,
+    },
+}
+
+/// The result of detecting a version, with every step taken to get there.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct DetectTrace {
+    pub tool: String,
+    pub version: Option<String>,
+    pub steps: Vec<DetectStep>,
+    /// Why detection could not run, for example, when a plugin failed to load.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 pub fn load_version_file(path: &Path) -> Result<String, ProtoError> {
@@ -28,6 +96,23 @@ pub async fn detect_version<'l, T: Tool<'l> + ?Sized>(
     tool: &Box<T>,
     forced_version: Option<String>,
 ) -> Result<String, ProtoError> {
+    match detect_version_with_trace(tool, forced_version)
+        .await?
+        .version
+    {
+        Some(ver) => Ok(ver),
+        None => Err(ProtoError::VersionDetectFailed(tool.get_id().to_owned())),
+    }
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn detect_version_with_trace<'l, T: Tool<'l> + ?Sized>(
+    tool: &Box<T>,
+    forced_version: Option<String>,
+) -> Result<DetectTrace, ProtoError> {
+    let mut steps = vec![DetectStep::CliArgument {
+        version: forced_version.clone(),
+    }];
     let mut version = forced_version;
     let env_var = format!("PROTO_{}_VERSION", tool.get_id().to_uppercase());
 
@@ -48,6 +133,11 @@ pub async fn detect_version<'l, T: Tool<'l> + ?Sized>(
                 "Attempting to find local version from config files"
             );
         }
+
+        steps.push(DetectStep::EnvVar {
+            name: env_var,
+            version: version.clone(),
+        });
     } else {
         debug!(
             tool = tool.get_id(),
@@ -57,14 +147,9 @@ pub async fn detect_version<'l, T: Tool<'l> + ?Sized>(
     }
 
     // Traverse upwards and attempt to detect a local version
-    if let Ok(working_dir) = env::current_dir() {
-        let mut current_dir: Option<&Path> = Some(&working_dir);
-
-        while let Some(dir) = &current_dir {
-            // We already found a version, so exit
-            if version.is_some() {
-                break;
-            }
+    if let (None, Ok(working_dir)) = (&version, env::current_dir()) {
+        for entry in ConfigTraversal::new().traverse_from(working_dir) {
+            let TraversedDir { dir, config } = entry?;
 
             trace!(
                 tool = tool.get_id(),
@@ -72,10 +157,19 @@ pub async fn detect_version<'l, T: Tool<'l> + ?Sized>(
                 "Checking directory",
             );
 
+            steps.push(DetectStep::Directory { dir: dir.clone() });
+
             // Detect from our config file
-            let config = ToolsConfig::load_from(dir)?;
+            let local_version = config.tools.get(tool.get_id());
+
+            if config.path.exists() {
+                steps.push(DetectStep::ConfigFile {
+                    file: config.path.clone(),
+                    version: local_version.cloned(),
+                });
+            }
 
-            if let Some(local_version) = config.tools.get(tool.get_id()) {
+            if let Some(local_version) = local_version {
                 debug!(
                     tool = tool.get_id(),
                     version = local_version,
@@ -88,10 +182,16 @@ pub async fn detect_version<'l, T: Tool<'l> + ?Sized>(
             }
 
             // Detect using the tool
-            if let Some(eco_version) = tool.detect_version_from(dir).await? {
-                if let Some(eco_version) =
-                    expand_detected_version(&eco_version, tool.get_manifest()?)?
-                {
+            if let Some((eco_version, eco_file)) = tool.detect_version_with_source(&dir).await? {
+                let expanded_version = expand_detected_version(&eco_version, tool.get_manifest()?)?;
+
+                steps.push(DetectStep::EcosystemFile {
+                    file: eco_file,
+                    version: eco_version,
+                    expanded: expanded_version.clone(),
+                });
+
+                if let Some(eco_version) = expanded_version {
                     debug!(
                         tool = tool.get_id(),
                         version = eco_version,
@@ -102,8 +202,6 @@ pub async fn detect_version<'l, T: Tool<'l> + ?Sized>(
                     break;
                 }
             }
-
-            current_dir = dir.parent();
         }
     }
 
@@ -126,13 +224,19 @@ pub async fn detect_version<'l, T: Tool<'l> + ?Sized>(
 
             version = Some(global_version.to_owned());
         }
-    }
 
-    // We didn't find anything!
-    match version {
-        Some(ver) => Ok(ver),
-        None => Err(ProtoError::VersionDetectFailed(tool.get_id().to_owned())),
+        steps.push(DetectStep::GlobalDefault {
+            file: manifest.path.clone(),
+            version: version.clone(),
+        });
     }
+
+    Ok(DetectTrace {
+        tool: tool.get_id().to_owned(),
+        version,
+        steps,
+        error: None,
+    })
 }
 
 #[tracing::instrument(skip_all)]
@@ -230,7 +334,7 @@ pub fn expand_detected_version(
         return Ok(None);
     }
 
-    let semver = Version::parse(&maybe_version).map_err(|e| ProtoError::Message(e.to_string()))?;
+    let semver = parse_version(&maybe_version)?;
 
     let version_parts = version.split('.').collect::<Vec<_>>();
     let mut matched_version = semver.major.to_string();
@@ -5,6 +5,13 @@ use thiserror::Error;
 
 #[derive(Error, Debug, Diagnostic)]
 pub enum ProtoError {
+    #[diagnostic(
+        code(proto::doctor::issues),
+        help = "Run proto doctor --fix to automatically fix the issues that can be."
+    )]
+    #[error("Found {0} issue(s) with your proto installation.")]
+    DoctorIssuesFound(usize),
+
     #[diagnostic(
         code(proto::download::missing),
         help = "Please refer to the tool's official documentation."
@@ -16,11 +23,22 @@ pub enum ProtoError {
     #[error("Failed to download tool from {}: {1}", .0.style(Style::Url))]
     DownloadFailed(String, String),
 
+    #[diagnostic(code(proto::execute::failed))]
+    #[error("Failed to execute {}.", .bin.style(Style::Shell))]
+    ExecuteFailed {
+        bin: String,
+        #[source]
+        error: std::io::Error,
+    },
+
     #[diagnostic(code(proto::execute::missing_bin))]
     #[error("Unable to find an executable binary for {0}, expected file {} does not exist.", .1.style(Style::Path))]
     ExecuteMissingBin(String, PathBuf),
 
-    #[diagnostic(code(proto::http))]
+    #[diagnostic(
+        code(proto::http),
+        help = "Check your internet connection, or try again later."
+    )]
     #[error("Failure for {}", .url.style(Style::Url))]
     Http {
         url: String,
@@ -28,6 +46,17 @@ pub enum ProtoError {
         error: reqwest::Error,
     },
 
+    #[diagnostic(code(proto::install::dependency_failed))]
+    #[error("Skipped, as {} failed to install.", .0.style(Style::Id))]
+    InstallDependencyFailed(String),
+
+    #[diagnostic(
+        code(proto::install::failed),
+        help = "Review the summary above for the cause of each failure."
+    )]
+    #[error("Failed to install {0} of {1} tools.")]
+    InstallFailed(usize, usize),
+
     #[diagnostic(code(proto::download::file_missing))]
     #[error("Unable to install {0}, download file is missing.")]
     InstallMissingDownload(String),
@@ -36,26 +65,53 @@ pub enum ProtoError {
     #[error("Internet connection required, unable to download and install tools.")]
     InternetConnectionRequired,
 
-    #[diagnostic(code(proto::config::invalid))]
+    #[diagnostic(
+        code(proto::config::invalid),
+        help = "Please refer to the documentation for supported settings."
+    )]
     #[error("Invalid configuration for {}: {1}", .0.style(Style::Path))]
     InvalidConfig(PathBuf, String),
 
+    #[diagnostic(
+        code(proto::config::invalid_plugin_id),
+        help = "Use a different name for the plugin."
+    )]
+    #[error("{} is a built-in tool and cannot be configured as a plugin.", .0.style(Style::Id))]
+    InvalidPluginId(String),
+
     #[diagnostic(code(proto::plugin::invalid_protocol))]
     #[error("Invalid plugin protocol {}", .0.style(Style::Label))]
     InvalidPluginProtocol(String),
 
-    #[diagnostic(code(proto::plugin::invalid_locator))]
-    #[error("Invalid plugin locator, must be a relative file path or an HTTPS URL.")]
-    InvalidPluginLocator,
+    #[diagnostic(
+        code(proto::config::invalid_plugin),
+        help = "Plugin locators must be in the format of source:<file|url>, github:<owner>/<repo>, or wapm:<package>."
+    )]
+    #[error("Invalid plugin {} in {}.", .plugin.style(Style::Id), .path.style(Style::Path))]
+    InvalidPluginLocator {
+        path: PathBuf,
+        plugin: String,
+        #[source]
+        #[diagnostic_source]
+        error: warpgate::WarpgateError,
+    },
 
     #[diagnostic(code(proto::plugin::invalid_ext))]
     #[error("Invalid plugin locator, must have a {0} extension.")]
     InvalidPluginLocatorExt(String),
 
+    #[diagnostic(code(proto::tool::invalid_spec))]
+    #[error("Missing version for {}, expected the format <tool>@<version>.", .0.style(Style::Id))]
+    InvalidToolSpec(String),
+
     #[diagnostic(code(proto::misc))]
     #[error("{0}")]
     Message(String),
 
+    #[diagnostic(code(proto::execute::missing_alt_bin))]
+    #[error("Alternate binary {} does not exist.", .0.style(Style::File))]
+    MissingAltBin(String),
+
     #[diagnostic(code(proto::config::missing))]
     #[error("Could not locate a {} configuration file.", .0.style(Style::File))]
     MissingConfig(String),
@@ -84,18 +140,61 @@ pub enum ProtoError {
     )]
     MissingToolForRun(String, String, String),
 
-    #[diagnostic(code(proto::plugin::load_wasm_failed))]
-    #[error("Failed to load WASM plugin. {0}")]
-    PluginWasmCreateFailed(String),
+    #[diagnostic(
+        code(proto::plugin::load_failed),
+        help = "Check the plugin's locator in .prototools or ~/.proto/config.toml."
+    )]
+    #[error("Failed to load plugin {}.", .id.style(Style::Id))]
+    PluginLoadFailed {
+        id: String,
+        #[source]
+        #[diagnostic_source]
+        error: warpgate::WarpgateError,
+    },
 
-    #[diagnostic(code(proto::plugin::call_wasm_failed))]
-    #[error("Failed to call WASM plugin function. {0}")]
-    PluginWasmCallFailed(String),
+    #[diagnostic(
+        code(proto::plugin::load_wasm_failed),
+        help = "Is the plugin a valid WASM module? It may have been built for an incompatible version of proto."
+    )]
+    #[error("Failed to load WASM plugin {}.", .id.style(Style::Id))]
+    PluginWasmCreateFailed {
+        id: String,
+        #[source]
+        #[diagnostic_source]
+        error: warpgate::WarpgateError,
+    },
+
+    #[diagnostic(
+        code(proto::plugin::call_wasm_failed),
+        help = "The plugin may be outdated or incompatible, try updating it with proto plugins refresh."
+    )]
+    #[error("Failed to call function {} of WASM plugin {}.", .func.style(Style::Id), .id.style(Style::Id))]
+    PluginWasmCallFailed {
+        id: String,
+        func: String,
+        #[source]
+        #[diagnostic_source]
+        error: warpgate::WarpgateError,
+    },
+
+    #[diagnostic(
+        code(proto::plugin::invalid_schema),
+        help = "Schema either requires a `git_url` or `manifest_url`."
+    )]
+    #[error("Unable to resolve versions for {0}, the schema is invalid.")]
+    PluginInvalidSchema(String),
 
     #[diagnostic(code(proto::plugin::missing_file))]
     #[error("Plugin file {} does not exist.", .0.style(Style::Path))]
     PluginFileMissing(PathBuf),
 
+    #[diagnostic(
+        code(proto::plugin::not_configured),
+        help = "Run proto plugins to see which plugins are configured."
+    )]
+    #[error("Plugin {} does not exist in config {}.", .0.style(Style::Id), .1.style(Style::Path))]
+    PluginNotConfigured(String, PathBuf),
+
     #[diagnostic(code(proto::version::invalid))]
     #[error("Invalid version {version}")]
     Semver {
@@ -116,6 +215,13 @@ pub enum ProtoError {
     #[error("Failed to create shim")]
     Shim(#[source] tinytemplate::error::Error),
 
+    #[diagnostic(
+        code(proto::unsupported::activate),
+        help = "Use proto env --shell json to print the environment as JSON instead."
+    )]
+    #[error("Unable to activate {0}, as it is not a shell.")]
+    UnsupportedActivateShell(String),
+
     #[diagnostic(code(proto::unsupported::archive))]
     #[error("Unable to unpack {}, unsupported archive format {1}.", .0.style(Style::Path))]
     UnsupportedArchiveFormat(PathBuf, String),
@@ -140,6 +246,25 @@ pub enum ProtoError {
     #[error("Tool {0} is unknown or unsupported.")]
     UnsupportedTool(String),
 
+    #[diagnostic(code(proto::upgrade::missing_binary))]
+    #[error("Failed to upgrade proto, {} could not be located after download!", .0.style(Style::Shell))]
+    UpgradeMissingBinary(String),
+
+    #[diagnostic(code(proto::upgrade::missing_rollback))]
+    #[error("No previous version of proto to rollback to, {} does not exist.", .0.style(Style::Path))]
+    UpgradeMissingRollback(PathBuf),
+
+    #[diagnostic(code(proto::upgrade::smoke_test))]
+    #[error("Smoke test failed for {}, aborting! {}", .0.style(Style::Path), .1.style(Style::MutedLight))]
+    UpgradeSmokeTestFailed(PathBuf, String),
+
+    #[diagnostic(
+        code(proto::upgrade::unknown_latest),
+        help = "Try again later, or pass a specific version to upgrade to."
+    )]
+    #[error("Unable to determine the latest version of proto.")]
+    UpgradeUnknownLatestVersion,
+
     #[diagnostic(code(proto::verify::invalid_checksum))]
     #[error(
         "Checksum has failed for {}, which was verified using {}.", .0.style(Style::Path), .1.style(Style::Path)
@@ -150,7 +275,10 @@ pub enum ProtoError {
     #[error("Version alias {} could not be found in the manifest.", .0.style(Style::Id))]
     VersionUnknownAlias(String),
 
-    #[diagnostic(code(proto::version::unresolved))]
+    #[diagnostic(
+        code(proto::version::unresolved),
+        help = "Does this version exist? Try running proto list-remote to see available versions."
+    )]
     #[error("Failed to resolve a semantic version for {0}.")]
     VersionResolveFailed(String),
 
@@ -181,3 +309,81 @@ pub enum ProtoError {
     #[error(transparent)]
     Toml(#[from] starbase_utils::toml::TomlError),
 }
+
+impl ProtoError {
+    /// Return a stable exit code for the error, grouped by category, so that
+    /// wrapping tools can handle failures without parsing the output.
+    ///
+    /// - 1 - Unknown or uncategorized failure.
+    /// - 2 - Invalid command line arguments.
+    /// - 3 - Invalid or missing configuration.
+    /// - 4 - Plugin failed to load or execute.
+    /// - 5 - Network or download failure.
+    /// - 6 - Version could not be detected or resolved.
+    /// - 7 - Tool is missing or failed to execute.
+    /// - 8 - Unsupported platform, architecture, or feature.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::InvalidToolSpec(..) => 2,
+
+            Self::DoctorIssuesFound(..)
+            | Self::InvalidConfig(..)
+            | Self::InvalidPluginId(..)
+            | Self::InvalidPluginLocator { .. }
+            | Self::InvalidPluginLocatorExt(..)
+            | Self::InvalidPluginProtocol(..)
+            | Self::MissingConfig(..) => 3,
+
+            Self::MissingPlugin(..)
+            | Self::PluginFileMissing(..)
+            | Self::PluginInvalidSchema(..)
+            | Self::PluginLoadFailed { .. }
+            | Self::PluginNotConfigured(..)
+            | Self::PluginWasmCallFailed { .. }
+            | Self::PluginWasmCreateFailed { .. } => 4,
+
+            Self::DownloadFailed(..)
+            | Self::DownloadNotFound(..)
+            | Self::Http { .. }
+            | Self::InstallMissingDownload(..)
+            | Self::InternetConnectionRequired
+            | Self::UpgradeMissingBinary(..)
+            | Self::UpgradeUnknownLatestVersion
+            | Self::VerifyInvalidChecksum(..) => 5,
+
+            Self::Semver { .. }
+            | Self::SemverLenient { .. }
+            | Self::VersionDetectFailed(..)
+            | Self::VersionResolveFailed(..)
+            | Self::VersionUnknownAlias(..) => 6,
+
+            Self::ExecuteFailed { .. }
+            | Self::ExecuteMissingBin(..)
+            | Self::InstallDependencyFailed(..)
+            | Self::InstallFailed(..)
+            | Self::MissingAltBin(..)
+            | Self::MissingTool(..)
+            | Self::MissingToolForRun(..)
+            | Self::UnsupportedTool(..)
+            | Self::UpgradeMissingRollback(..)
+            | Self::UpgradeSmokeTestFailed(..) => 7,
+
+            Self::UnsupportedActivateShell(..)
+            | Self::UnsupportedArchiveFormat(..)
+            | Self::UnsupportedArchitecture(..)
+            | Self::UnsupportedGlobals(..)
+            | Self::UnsupportedPlatform(..)
+            | Self::UnsupportedShell => 8,
+
+            Self::Message(..)
+            | Self::MissingHomeDir
+            | Self::MissingPathEnv
+            | Self::Shim(..)
+            | Self::WritePathFailed
+            | Self::Zip(..)
+            | Self::Fs(..)
+            | Self::Json(..)
+            | Self::Toml(..) => 1,
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use crate::errors::ProtoError;
 use cached::proc_macro::cached;
 use dirs::home_dir;
+use std::env;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::{env, path::PathBuf};
+use std::time::{Duration, SystemTime};
 
 pub fn get_root() -> Result<PathBuf, ProtoError> {
     if let Ok(root) = env::var("PROTO_ROOT") {
@@ -119,8 +121,45 @@ pub fn is_musl() -> bool {
     String::from_utf8_lossy(&output.stdout).contains("musl")
 }
 
+/// Return the current time in milliseconds since the UNIX epoch.
+pub fn now() -> u128 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// Return when a file was last modified, as a duration since the UNIX epoch,
+/// or `None` if it does not exist.
+pub fn get_modified_time<P: AsRef<Path>>(path: P) -> Option<Duration> {
+    path.as_ref()
+        .metadata()
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+}
+
 pub fn is_cache_enabled() -> bool {
     env::var("PROTO_CACHE").map_or(true, |value| {
         value != "0" && value != "false" && value != "no" && value != "off"
     })
 }
+
+/// Calculate the total size of all files within a directory, in bytes.
+/// Symlinks are not followed.
+pub fn get_dir_size<P: AsRef<Path>>(dir: P) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir.as_ref()) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => get_dir_size(entry.path()),
+            Ok(file_type) if file_type.is_file() => {
+                entry.metadata().map(|meta| meta.len()).unwrap_or(0)
+            }
+            _ => 0,
+        })
+        .sum()
+}
@@ -1,4 +1,6 @@
+mod config_traversal;
 mod describer;
+mod detect_cache;
 mod detector;
 mod downloader;
 mod errors;
@@ -8,6 +10,7 @@ mod installer;
 mod manifest;
 mod plugin;
 mod resolver;
+mod shim_registry;
 mod shimmer;
 mod tool;
 mod tools_config;
@@ -15,7 +18,9 @@ mod user_config;
 mod verifier;
 
 pub use async_trait::async_trait;
+pub use config_traversal::*;
 pub use describer::*;
+pub use detect_cache::*;
 pub use detector::*;
 pub use downloader::*;
 pub use errors::*;
@@ -26,6 +31,7 @@ pub use lenient_semver::Version;
 pub use manifest::*;
 pub use plugin::*;
 pub use resolver::*;
+pub use shim_registry::*;
 pub use shimmer::*;
 pub use starbase_styles::color;
 pub use tool::*;
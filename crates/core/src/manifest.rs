@@ -1,4 +1,5 @@
 use crate::errors::ProtoError;
+use crate::helpers::now;
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use starbase_utils::{
@@ -8,25 +9,36 @@ use starbase_utils::{
 use std::{
     env,
     path::{Path, PathBuf},
-    time::SystemTime,
 };
 use tracing::{debug, info};
-
-fn now() -> u128 {
-    SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .map(|d| d.as_millis())
-        .unwrap_or(0)
-}
+use warpgate::PluginLocator;
 
 pub const MANIFEST_NAME: &str = "manifest.json";
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct ManifestVersion {
     pub no_clean: bool,
     pub installed_at: u128,
     pub last_used_at: Option<u128>,
+
+    // Provenance
+    /// URL the archive was downloaded from.
+    pub download_url: Option<String>,
+    /// Checksum of the downloaded archive.
+    pub checksum: Option<String>,
+    /// Algorithm used for the checksum.
+    pub checksum_algorithm: Option<String>,
+    /// Size of the downloaded archive in bytes.
+    pub archive_size: Option<u64>,
+    /// Size of the installation directory in bytes.
+    pub installed_size: Option<u64>,
+    /// Locator of the plugin that installed the tool.
+    pub plugin_locator: Option<PluginLocator>,
+    /// SHA256 hash of the plugin file that installed the tool.
+    pub plugin_hash: Option<String>,
+    /// Version of proto that installed the tool.
+    pub proto_version: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -130,6 +142,15 @@ impl Manifest {
         &mut self,
         version: &str,
         default_version: Option<String>,
+    ) -> Result<(), ProtoError> {
+        self.insert_version_with(version, default_version, ManifestVersion::default())
+    }
+
+    pub fn insert_version_with(
+        &mut self,
+        version: &str,
+        default_version: Option<String>,
+        metadata: ManifestVersion,
     ) -> Result<(), ProtoError> {
         if self.default_version.is_none() {
             self.default_version = Some(default_version.unwrap_or(version.to_owned()));
@@ -142,7 +163,7 @@ impl Manifest {
             ManifestVersion {
                 installed_at: now(),
                 no_clean: env::var("PROTO_NO_CLEAN").is_ok(),
-                ..ManifestVersion::default()
+                ..metadata
             },
         );
 
@@ -0,0 +1,373 @@
+use crate::errors::ProtoError;
+use crate::helpers::get_root;
+use crate::shimmer::{get_native_shim_file_name, get_shim_file_name};
+use serde::{Deserialize, Serialize};
+use starbase_utils::{
+    fs::{self, FsError},
+    json::{self, JsonError},
+};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+pub const SHIM_REGISTRY_NAME: &str = "shims.json";
+
+/// A global shim within `~/.proto/bin`, and how to execute it.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ShimEntry {
+    /// ID of the tool that owns the shim, and is executed by it.
+    pub tool: String,
+    /// Relative path to an alternative binary within the tool's install directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alt_bin: Option<String>,
+    /// Name of a parent binary required to execute the tool's binary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_bin: Option<String>,
+    /// Args to prepend to user-provided args.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub before_args: Vec<String>,
+    /// Args to append to user-provided args.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub after_args: Vec<String>,
+}
+
+/// A shim within a tool's install directory.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct LocalShimEntry {
+    /// ID of the tool that owns the shim.
+    pub tool: String,
+    /// Version of the tool the shim was created for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Information about a tool that is required by native shims.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ShimTool {
+    /// Path to the primary binary, relative to a version's install directory.
+    pub bin_path: Option<String>,
+    /// Files that a version may be detected from. When unknown, native shims
+    /// will defer to proto for any directory that isn't configured.
+    pub version_files: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ShimRegistry {
+    /// Global shims, keyed by file name (without extension).
+    pub shims: BTreeMap<String, ShimEntry>,
+    /// Local shims, keyed by absolute file path.
+    pub local_shims: BTreeMap<String, LocalShimEntry>,
+    pub tools: BTreeMap<String, ShimTool>,
+
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl ShimRegistry {
+    pub fn load_default() -> Result<Self, ProtoError> {
+        Self::load_from(get_root()?)
+    }
+
+    pub fn load_from<P: AsRef<Path>>(dir: P) -> Result<Self, ProtoError> {
+        Self::load(dir.as_ref().join(SHIM_REGISTRY_NAME))
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ProtoError> {
+        use fs4::FileExt;
+
+        let path = path.as_ref();
+
+        debug!(file = ?path, "Loading shim registry");
+
+        let mut registry = if path.exists() {
+            let handle_error = |error: std::io::Error| FsError::Read {
+                path: path.to_path_buf(),
+                error,
+            };
+
+            let mut file = fs::open_file(path)?;
+
+            file.lock_shared().map_err(handle_error)?;
+
+            let registry = Self::read_from(&mut file, path)?;
+
+            file.unlock().map_err(handle_error)?;
+
+            registry
+        } else {
+            ShimRegistry::default()
+        };
+
+        registry.path = path.to_owned();
+
+        Ok(registry)
+    }
+
+    fn read_from(file: &mut File, path: &Path) -> Result<Self, ProtoError> {
+        use std::io::prelude::*;
+
+        let mut buffer = String::new();
+
+        file.read_to_string(&mut buffer)
+            .map_err(|error| FsError::Read {
+                path: path.to_path_buf(),
+                error,
+            })?;
+
+        if buffer.trim().is_empty() {
+            return Ok(ShimRegistry::default());
+        }
+
+        Ok(
+            json::from_str(&buffer).map_err(|error| JsonError::ReadFile {
+                path: path.to_path_buf(),
+                error,
+            })?,
+        )
+    }
+
+    fn write_to(&self, file: &mut File) -> Result<(), ProtoError> {
+        use std::io::{prelude::*, SeekFrom};
+
+        let handle_error = |error: std::io::Error| FsError::Write {
+            path: self.path.to_path_buf(),
+            error,
+        };
+
+        let data = json::to_string_pretty(self).map_err(|error| JsonError::StringifyFile {
+            path: self.path.to_path_buf(),
+            error,
+        })?;
+
+        // Truncate then write file
+        file.set_len(0).map_err(handle_error)?;
+        file.seek(SeekFrom::Start(0)).map_err(handle_error)?;
+
+        write!(file, "{}", data).map_err(handle_error)?;
+
+        Ok(())
+    }
+
+    fn open_locked(&self) -> Result<File, ProtoError> {
+        use fs4::FileExt;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let handle_error = |error: std::io::Error| FsError::Write {
+            path: self.path.to_path_buf(),
+            error,
+        };
+
+        // Don't use fs::create_file() as it truncates!
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&self.path)
+            .map_err(handle_error)?;
+
+        file.lock_exclusive().map_err(handle_error)?;
+
+        Ok(file)
+    }
+
+    /// Write the registry as-is, replacing any changes made by other processes.
+    /// Prefer [`ShimRegistry::update`] when modifying entries.
+    #[tracing::instrument(skip_all)]
+    pub fn save(&self) -> Result<(), ProtoError> {
+        use fs4::FileExt;
+
+        debug!(file = ?self.path, "Saving shim registry");
+
+        let mut file = self.open_locked()?;
+
+        self.write_to(&mut file)?;
+
+        file.unlock().map_err(|error| FsError::Write {
+            path: self.path.to_path_buf(),
+            error,
+        })?;
+
+        Ok(())
+    }
+
+    /// Modify the registry while holding an exclusive lock on its file. Shims are
+    /// created concurrently, by multiple installs and processes, so the registry is
+    /// read again under the lock, and only written if the callback returns true.
+    #[tracing::instrument(skip_all)]
+    pub fn update<F>(&mut self, op: F) -> Result<(), ProtoError>
+    where
+        F: FnOnce(&mut Self) -> Result<bool, ProtoError>,
+    {
+        use fs4::FileExt;
+
+        let mut file = self.open_locked()?;
+        let latest = Self::read_from(&mut file, &self.path)?;
+
+        self.shims = latest.shims;
+        self.local_shims = latest.local_shims;
+        self.tools = latest.tools;
+
+        if op(self)? {
+            debug!(file = ?self.path, "Saving shim registry");
+
+            self.write_to(&mut file)?;
+        }
+
+        file.unlock().map_err(|error| FsError::Write {
+            path: self.path.to_path_buf(),
+            error,
+        })?;
+
+        Ok(())
+    }
+
+    /// Register a shim, and save the registry if it has changed.
+    pub fn register(&mut self, name: &str, entry: ShimEntry) -> Result<(), ProtoError> {
+        if self.shims.get(name) == Some(&entry) {
+            return Ok(());
+        }
+
+        self.update(|registry| {
+            let previous = registry.shims.insert(name.to_owned(), entry.clone());
+
+            Ok(previous.as_ref() != Some(&entry))
+        })
+    }
+
+    /// Register a local shim, and save the registry if it has changed.
+    pub fn register_local(&mut self, path: &Path, entry: LocalShimEntry) -> Result<(), ProtoError> {
+        let key = path.to_string_lossy().to_string();
+
+        if self.local_shims.get(&key) == Some(&entry) {
+            return Ok(());
+        }
+
+        self.update(|registry| {
+            let previous = registry.local_shims.insert(key, entry.clone());
+
+            Ok(previous.as_ref() != Some(&entry))
+        })
+    }
+
+    /// Register information about a tool, and save the registry if it has changed.
+    pub fn register_tool(&mut self, id: &str, tool: ShimTool) -> Result<(), ProtoError> {
+        if self.tools.get(id) == Some(&tool) {
+            return Ok(());
+        }
+
+        self.update(|registry| {
+            let previous = registry.tools.insert(id.to_owned(), tool.clone());
+
+            Ok(previous.as_ref() != Some(&tool))
+        })
+    }
+
+    /// Return the directory that global shims are created in.
+    pub fn get_bin_dir(&self) -> PathBuf {
+        self.path
+            .parent()
+            .map(|root| root.join("bin"))
+            .unwrap_or_default()
+    }
+
+    /// Return possible file paths for a global shim, as it may either be
+    /// a native executable or a script.
+    pub fn get_shim_paths(&self, name: &str) -> Vec<PathBuf> {
+        let bin_dir = self.get_bin_dir();
+        let mut paths = vec![bin_dir.join(get_native_shim_file_name(name))];
+        let script_path = bin_dir.join(get_shim_file_name(name, true));
+
+        if !paths.contains(&script_path) {
+            paths.push(script_path);
+        }
+
+        paths
+    }
+
+    /// Return names of global shims owned by the provided tool.
+    pub fn get_tool_shims(&self, id: &str) -> Vec<String> {
+        self.shims
+            .iter()
+            .filter(|(_, entry)| entry.tool == id)
+            .map(|(name, _)| name.to_owned())
+            .collect()
+    }
+
+    /// Remove global shims from the file system and the registry,
+    /// and return the paths of the files that were removed.
+    pub fn remove_shims(&mut self, names: &[String]) -> Result<Vec<PathBuf>, ProtoError> {
+        let mut removed = vec![];
+
+        for name in names {
+            for path in self.get_shim_paths(name) {
+                if path.exists() {
+                    debug!(shim = ?path, "Removing global shim");
+
+                    fs::remove_file(&path)?;
+                    removed.push(path);
+                }
+            }
+        }
+
+        if !names.is_empty() {
+            self.update(|registry| {
+                let count = registry.shims.len();
+
+                for name in names {
+                    registry.shims.remove(name);
+                }
+
+                Ok(registry.shims.len() != count)
+            })?;
+        }
+
+        Ok(removed)
+    }
+
+    /// Unregister local shims within the provided directory,
+    /// typically when a version has been uninstalled.
+    pub fn remove_local_shims(&mut self, dir: &Path) -> Result<(), ProtoError> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        self.update(|registry| {
+            let count = registry.local_shims.len();
+
+            registry
+                .local_shims
+                .retain(|path, _| !Path::new(path).starts_with(dir));
+
+            Ok(registry.local_shims.len() != count)
+        })
+    }
+
+    /// Remove all shims owned by the provided tool, typically when the
+    /// last version has been uninstalled.
+    pub fn remove_tool(&mut self, id: &str) -> Result<Vec<PathBuf>, ProtoError> {
+        let removed = self.remove_shims(&self.get_tool_shims(id))?;
+
+        if !self.path.exists() {
+            return Ok(removed);
+        }
+
+        self.update(|registry| {
+            let count = registry.local_shims.len();
+
+            registry.local_shims.retain(|_, entry| entry.tool != id);
+
+            Ok(registry.tools.remove(id).is_some() || registry.local_shims.len() != count)
+        })?;
+
+        Ok(removed)
+    }
+}
@@ -1,15 +1,17 @@
 use crate::errors::ProtoError;
 use crate::helpers::get_bin_dir;
+use crate::shim_registry::{LocalShimEntry, ShimEntry, ShimRegistry};
 use serde::Serialize;
 use serde_json::Value;
 use starbase_utils::fs;
+use std::env;
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 use tinytemplate::error::Error as TemplateError;
 use tinytemplate::TinyTemplate;
 use tracing::debug;
 
-pub const SHIM_VERSION: u8 = 6;
+pub const SHIM_VERSION: u8 = 7;
 
 #[derive(Default, Serialize)]
 pub struct ShimContext<'tool> {
@@ -61,6 +63,40 @@ impl<'tool> ShimContext<'tool> {
     }
 }
 
+impl<'tool> ShimContext<'tool> {
+    /// Native shims pass args through as-is, so args that rely on shell
+    /// quoting, escaping, or expansion can only be handled by script shims.
+    pub fn supports_native_shim(&self) -> bool {
+        [self.before_args, self.after_args]
+            .into_iter()
+            .flatten()
+            .all(|args| !args.contains(|c| matches!(c, '"' | '\'' | '\\' | '$' | '`' | '%')))
+    }
+
+    /// Convert to an entry for the shim registry, which is used by native shims.
+    pub fn to_registry_entry(&self) -> ShimEntry {
+        let split_args = |args: Option<&str>| {
+            args.map(|args| args.split_whitespace().map(|arg| arg.to_owned()).collect())
+                .unwrap_or_default()
+        };
+
+        // Global shims with an alternate binary execute the parent tool,
+        // otherwise the parent binary is required to execute the tool
+        let (tool, parent_bin) = match (self.alt_bin, self.parent_bin) {
+            (Some(_), Some(parent_bin)) => (parent_bin, None),
+            (_, parent_bin) => (self.bin, parent_bin),
+        };
+
+        ShimEntry {
+            tool: tool.to_owned(),
+            alt_bin: self.alt_bin.map(|bin| bin.to_owned()),
+            parent_bin: parent_bin.map(|bin| bin.to_owned()),
+            before_args: split_args(self.before_args),
+            after_args: split_args(self.after_args),
+        }
+    }
+}
+
 impl<'tool> AsRef<ShimContext<'tool>> for ShimContext<'tool> {
     fn as_ref(&self) -> &ShimContext<'tool> {
         self
@@ -141,6 +177,59 @@ pub fn get_shim_file_name(name: &str, _global: bool) -> String {
     name.to_owned()
 }
 
+pub fn get_native_shim_file_name(name: &str) -> String {
+    if cfg!(windows) {
+        format!("{name}.exe")
+    } else {
+        name.to_owned()
+    }
+}
+
+/// Locate the native `proto-shim` executable, either with the `PROTO_SHIM_PATH`
+/// environment variable, or alongside the current `proto` executable.
+pub fn find_native_shim() -> Option<PathBuf> {
+    if let Ok(path) = env::var("PROTO_SHIM_PATH") {
+        let path = PathBuf::from(path);
+
+        return path.is_file().then_some(path);
+    }
+
+    let path = env::current_exe()
+        .ok()?
+        .parent()?
+        .join(get_native_shim_file_name("proto-shim"));
+
+    path.is_file().then_some(path)
+}
+
+fn create_native_shim(
+    native_shim: &Path,
+    shim_path: PathBuf,
+    find_only: bool,
+) -> Result<PathBuf, ProtoError> {
+    if find_only && shim_path.exists() {
+        return Ok(shim_path);
+    }
+
+    if shim_path.exists() {
+        fs::remove_file(&shim_path)?;
+    }
+
+    // Hard link to avoid duplicating the binary, but fallback to
+    // copying when linking across devices or file systems
+    if let Some(parent) = shim_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if std::fs::hard_link(native_shim, &shim_path).is_err() {
+        fs::copy_file(native_shim, &shim_path)?;
+    }
+
+    fs::update_perms(&shim_path, None)?;
+
+    Ok(shim_path)
+}
+
 fn create_shim(
     context: &ShimContext,
     shim_path: PathBuf,
@@ -172,16 +261,46 @@ pub fn create_global_shim_with_name<'tool, C: AsRef<ShimContext<'tool>>>(
 ) -> Result<PathBuf, ProtoError> {
     let context = context.as_ref();
     let globals_dir = get_bin_dir()?;
-    let shim_path = context
-        .globals_bin_dir
-        .unwrap_or(globals_dir.as_path())
-        .join(get_shim_file_name(name, true));
+    let globals_dir = context.globals_bin_dir.unwrap_or(globals_dir.as_path());
+    let script_path = globals_dir.join(get_shim_file_name(name, true));
+
+    let native_shim = find_native_shim().filter(|_| context.supports_native_shim());
+
+    let shim_path = if let Some(native_shim) = native_shim {
+        let shim_path = globals_dir.join(get_native_shim_file_name(name));
+
+        if !find_only {
+            debug!(tool = context.bin, file = ?shim_path, "Creating native global shim");
+
+            // Scripts may take precedence over executables on Windows
+            if script_path != shim_path && script_path.exists() {
+                fs::remove_file(&script_path)?;
+            }
+        }
+
+        create_native_shim(&native_shim, shim_path, find_only)?
+    } else {
+        if !find_only {
+            debug!(tool = context.bin, file = ?script_path, "Creating global shim");
+
+            // Never write through a hard linked native shim
+            let shim_path = globals_dir.join(get_native_shim_file_name(name));
+
+            if shim_path.exists() {
+                fs::remove_file(&shim_path)?;
+            }
+        }
+
+        create_shim(context, script_path, true, find_only)?
+    };
 
     if !find_only {
-        debug!(tool = context.bin, file = ?shim_path, "Creating global shim");
+        if let Some(root) = globals_dir.parent() {
+            ShimRegistry::load_from(root)?.register(name, context.to_registry_entry())?;
+        }
     }
 
-    create_shim(context, shim_path, true, find_only)
+    Ok(shim_path)
 }
 
 #[tracing::instrument(skip_all)]
@@ -190,10 +309,8 @@ pub fn create_local_shim<'tool, C: AsRef<ShimContext<'tool>>>(
     find_only: bool,
 ) -> Result<PathBuf, ProtoError> {
     let context = context.as_ref();
-    let shim_path = context
-        .tool_dir
-        .as_ref()
-        .expect("Missing tool dir for shims.")
+    let tool_dir = context.tool_dir.expect("Missing tool dir for shims.");
+    let shim_path = tool_dir
         .join("shims")
         .join(get_shim_file_name(context.bin, false));
 
@@ -201,5 +318,30 @@ pub fn create_local_shim<'tool, C: AsRef<ShimContext<'tool>>>(
         debug!(tool = context.bin, file = ?shim_path, "Creating local shim");
     }
 
-    create_shim(context, shim_path, false, find_only)
+    let shim_path = create_shim(context, shim_path, false, find_only)?;
+
+    // Local shims are created within ~/.proto/tools/<tool>/<version>
+    if !find_only {
+        if let (Some(tool), Some(root)) = (
+            tool_dir.parent().and_then(|dir| dir.file_name()),
+            tool_dir.ancestors().nth(3),
+        ) {
+            ShimRegistry::load_from(root)?.register_local(
+                &shim_path,
+                LocalShimEntry {
+                    tool: tool.to_string_lossy().to_string(),
+                    version: context
+                        .tool_version
+                        .map(|version| version.to_owned())
+                        .or_else(|| {
+                            tool_dir
+                                .file_name()
+                                .map(|name| name.to_string_lossy().to_string())
+                        }),
+                },
+            )?;
+        }
+    }
+
+    Ok(shim_path)
 }
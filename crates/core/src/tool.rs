@@ -3,9 +3,11 @@ use crate::detector::*;
 use crate::downloader::*;
 use crate::errors::*;
 use crate::executor::*;
+use crate::helpers::get_dir_size;
 use crate::installer::*;
 use crate::manifest::*;
 use crate::resolver::*;
+use crate::shim_registry::*;
 use crate::shimmer::*;
 use crate::verifier::*;
 use std::any::Any;
@@ -60,6 +62,10 @@ pub trait Tool<'tool>:
         self.download_checksum(&checksum_path, None).await?;
         self.verify_checksum(&checksum_path, &download_path).await?;
 
+        // Capture provenance before installing, as single binaries
+        // are moved out of the download path when installed
+        let mut metadata = self.get_download_provenance(&download_path)?;
+
         // Install the tool
         let install_dir = self.get_install_dir()?;
 
@@ -73,8 +79,13 @@ pub trait Tool<'tool>:
             {
                 let default_version = self.get_default_version().map(|v| v.to_owned());
 
-                self.get_manifest_mut()?
-                    .insert_version(&version, default_version)?;
+                metadata.installed_size = install_dir.exists().then(|| get_dir_size(&install_dir));
+
+                self.get_manifest_mut()?.insert_version_with(
+                    &version,
+                    default_version,
+                    metadata,
+                )?;
             }
 
             self.after_setup().await?;
@@ -85,6 +96,30 @@ pub trait Tool<'tool>:
         Ok(false)
     }
 
+    /// Gather details about where the current version was downloaded from,
+    /// and the plugin that provided it.
+    fn get_download_provenance(&self, download_path: &Path) -> Result<ManifestVersion, ProtoError> {
+        let mut metadata = ManifestVersion::default();
+
+        // Some tools are installed without a download (e.g. rustup)
+        if download_path.is_file() {
+            metadata.download_url = self.get_download_url().ok();
+            metadata.checksum = Some(get_sha256_hash_of_file(download_path)?);
+            metadata.checksum_algorithm = Some("sha256".into());
+            metadata.archive_size = download_path.metadata().map(|meta| meta.len()).ok();
+        }
+
+        if let Some(source) = self.get_plugin_source() {
+            metadata.plugin_locator = Some(source.locator.clone());
+
+            if source.path.is_file() {
+                metadata.plugin_hash = Some(get_sha256_hash_of_file(&source.path)?);
+            }
+        }
+
+        Ok(metadata)
+    }
+
     async fn setup_shims(&mut self, force: bool) -> Result<(), ProtoError> {
         let is_outdated = { self.get_manifest_mut()?.shim_version != SHIM_VERSION };
         let do_create = force || is_outdated || env::var("CI").is_ok();
@@ -102,9 +137,30 @@ pub trait Tool<'tool>:
 
         self.create_shims(!do_create).await?;
 
+        // Native shims execute the primary binary directly, so record where it's located
+        if do_create {
+            self.register_shim_tool()?;
+        }
+
         Ok(())
     }
 
+    fn register_shim_tool(&self) -> Result<(), ProtoError> {
+        let (Ok(bin_path), Ok(install_dir)) = (self.get_bin_path(), self.get_install_dir()) else {
+            return Ok(());
+        };
+
+        let shim_tool = ShimTool {
+            bin_path: bin_path
+                .strip_prefix(&install_dir)
+                .ok()
+                .map(|path| path.to_string_lossy().to_string()),
+            version_files: self.get_version_files(),
+        };
+
+        ShimRegistry::load_default()?.register_tool(self.get_id(), shim_tool)
+    }
+
     async fn is_setup(&mut self, initial_version: &str) -> Result<bool, ProtoError> {
         self.resolve_version(initial_version).await?;
 
@@ -177,6 +233,18 @@ pub trait Tool<'tool>:
 
             self.get_manifest_mut()?.remove_version(&version)?;
 
+            let mut registry = ShimRegistry::load_default()?;
+
+            registry.remove_local_shims(&install_dir)?;
+
+            // Global shims are shared between versions, so only remove them
+            // once the last version has been uninstalled
+            if self.get_manifest()?.installed_versions.is_empty() {
+                debug!(tool = self.get_id(), "Removing global shims");
+
+                registry.remove_tool(self.get_id())?;
+            }
+
             self.after_teardown().await?;
 
             return Ok(true);
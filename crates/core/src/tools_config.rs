@@ -1,21 +1,132 @@
+use crate::config_traversal::{invalidate_cached_config, ConfigTraversal};
 use crate::errors::ProtoError;
 use convert_case::{Case, Casing};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, ObjectValidation, Schema, SchemaObject, SubschemaValidation};
+use schemars::JsonSchema;
+use starbase_utils::fs;
 use starbase_utils::toml::{self, TomlTable, TomlValue};
 use std::env;
 use std::path::{Path, PathBuf};
+use toml_edit::{value, Array, Document, InlineTable, Item, Table, TableLike, Value};
 use tracing::trace;
 use warpgate::PluginLocator;
 
 pub const TOOLS_CONFIG_NAME: &str = ".prototools";
 
-#[derive(Debug, Default)]
+/// Settings for a tool, configured within a `[tools.<name>]` table.
+#[derive(Clone, Debug, Default, JsonSchema, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ToolConfig {
+    /// Automatically install the tool when running and it's missing.
+    pub auto_install: Option<bool>,
+    /// Environment variables to export when running the tool.
+    #[serde(default)]
+    pub env: FxHashMap<String, String>,
+    /// Arguments to pass through when installing the tool.
+    #[serde(default)]
+    pub install_args: Vec<String>,
+}
+
+impl ToolConfig {
+    pub fn is_empty(&self) -> bool {
+        self.auto_install.is_none() && self.env.is_empty() && self.install_args.is_empty()
+    }
+
+    /// Merge settings from a child config, with the child taking precedence.
+    pub fn merge(&mut self, other: ToolConfig) {
+        if other.auto_install.is_some() {
+            self.auto_install = other.auto_install;
+        }
+
+        self.env.extend(other.env);
+
+        if !other.install_args.is_empty() {
+            self.install_args = other.install_args;
+        }
+    }
+}
+
+/// Configuration for a `.prototools` file. Tools mapped to a version at the
+/// root of the file cannot be represented by a field, and are added to the
+/// schema by the generator.
+#[derive(Clone, Debug, Default, JsonSchema)]
+#[schemars(title = "ToolsConfig")]
+#[serde(rename_all = "kebab-case")]
 pub struct ToolsConfig {
+    /// Stop traversing upwards for `.prototools` files once this file is reached.
+    #[serde(default)]
+    pub root: bool,
+    /// Mapping of tool names to versions or settings.
+    #[serde(default)]
+    #[schemars(schema_with = "tools_schema")]
     pub tools: FxHashMap<String, String>,
+    #[serde(skip)]
+    pub tool_configs: FxHashMap<String, ToolConfig>,
+    /// Mapping of plugin names to locators.
+    #[serde(default)]
     pub plugins: FxHashMap<String, PluginLocator>,
+    /// Environment variables to export when running any tool.
+    #[serde(default)]
+    pub env: FxHashMap<String, String>,
+    #[serde(skip)]
     pub path: PathBuf,
 }
 
+// The `[tools]` table maps to either a version, or a table of settings. Versions and
+// plugins within a table are hoisted into `tools` and `plugins` when loading.
+fn tools_schema(gen: &mut SchemaGenerator) -> Schema {
+    let mut settings = ToolConfig::json_schema(gen).into_object();
+    let properties = &mut settings.object().properties;
+
+    properties.insert(
+        "version".into(),
+        describe_schema(
+            gen.subschema_for::<String>(),
+            "A version, alias, or requirement.",
+        ),
+    );
+    properties.insert(
+        "plugin".into(),
+        describe_schema(
+            gen.subschema_for::<PluginLocator>(),
+            "Locator of the plugin to use for this tool.",
+        ),
+    );
+
+    SchemaObject {
+        instance_type: Some(InstanceType::Object.into()),
+        object: Some(Box::new(ObjectValidation {
+            additional_properties: Some(Box::new(
+                SchemaObject {
+                    subschemas: Some(Box::new(SubschemaValidation {
+                        any_of: Some(vec![
+                            describe_schema(
+                                gen.subschema_for::<String>(),
+                                "A version, alias, or requirement.",
+                            ),
+                            settings.into(),
+                        ]),
+                        ..SubschemaValidation::default()
+                    })),
+                    ..SchemaObject::default()
+                }
+                .into(),
+            )),
+            ..ObjectValidation::default()
+        })),
+        ..SchemaObject::default()
+    }
+    .into()
+}
+
+fn describe_schema(schema: Schema, description: &str) -> Schema {
+    let mut schema = schema.into_object();
+    schema.metadata().description = Some(description.into());
+    schema.into()
+}
+
 impl ToolsConfig {
     pub fn builtin_plugins() -> FxHashMap<String, PluginLocator> {
         let mut config = ToolsConfig::default();
@@ -35,27 +146,26 @@ impl ToolsConfig {
     {
         trace!("Traversing upwards and loading all .prototools files");
 
-        let mut current_dir = Some(starting_dir.as_ref());
-        let mut config = ToolsConfig::default();
-
-        while let Some(dir) = current_dir {
-            let path = dir.join(TOOLS_CONFIG_NAME);
+        let mut configs = vec![];
 
-            if path.exists() {
-                let mut parent_config = Self::load(&path)?;
-                parent_config.merge(config);
+        for entry in ConfigTraversal::new().traverse_from(starting_dir) {
+            let entry = entry?;
 
-                config = parent_config;
+            if entry.config.path.exists() {
+                configs.push(entry.config);
             }
+        }
 
-            match dir.parent() {
-                Some(parent) => {
-                    current_dir = Some(parent);
-                }
-                None => {
-                    break;
-                }
-            };
+        // Merge from the top-most file downwards, so that
+        // closer files take precedence over parent files
+        let mut configs = configs.into_iter().rev();
+        let mut config = match configs.next() {
+            Some(parent_config) => (*parent_config).clone(),
+            None => return Ok(ToolsConfig::default()),
+        };
+
+        for child_config in configs {
+            config.merge((*child_config).clone());
         }
 
         Ok(config)
@@ -77,68 +187,141 @@ impl ToolsConfig {
         }
 
         let config: TomlValue = toml::read_file(path)?;
-        let mut tools = FxHashMap::default();
-        let mut plugins = FxHashMap::default();
-
-        if let TomlValue::Table(table) = config {
-            for (key, value) in table {
-                match value {
-                    TomlValue::String(version) => {
-                        tools.insert(key, version);
-                    }
-                    TomlValue::Table(plugins_table) => {
-                        if key != "plugins" {
-                            return Err(ProtoError::InvalidConfig(
-                                path.to_path_buf(),
-                                "Expected a [plugins] map.".into(),
-                            ));
-                        }
+        let mut tools_config = ToolsConfig {
+            path: path.to_owned(),
+            ..ToolsConfig::default()
+        };
 
-                        for (plugin, location) in plugins_table {
-                            if let TomlValue::String(location) = location {
-                                let mut locator = PluginLocator::try_from(location).map_err(|e| ProtoError::Message(e.to_string()))?;
-
-                                // Update file paths to be absolute
-                                if let PluginLocator::SourceFile { path: ref mut source_path, .. } = locator {
-                                    *source_path = path.parent().unwrap().join(&source_path);
-                                }
-
-                                plugins.insert(
-                                    plugin.to_case(Case::Kebab),
-                                    locator,
-                                );
-                            } else {
-                                return Err(ProtoError::InvalidConfig(
-                                    path.to_path_buf(),
-                                    format!(
-                                        "Invalid plugin \"{plugin}\", expected a locator string."
-                                    ),
-                                ));
-                            }
+        let TomlValue::Table(table) = config else {
+            return Err(ProtoError::InvalidConfig(
+                path.to_path_buf(),
+                "Expected a mapping of tools or plugins.".into(),
+            ));
+        };
+
+        for (key, value) in table {
+            match value {
+                TomlValue::String(version) => {
+                    let version = interpolate_env_vars(path, &key, &version)?;
+
+                    tools_config.tools.insert(key, version);
+                }
+                TomlValue::Boolean(root) if key == "root" => {
+                    tools_config.root = root;
+                }
+                TomlValue::Table(inner_table) => match key.as_str() {
+                    "plugins" => {
+                        for (plugin, location) in inner_table {
+                            let locator = parse_plugin_locator(path, &plugin, location)?;
+
+                            tools_config
+                                .plugins
+                                .insert(plugin.to_case(Case::Kebab), locator);
+                        }
+                    }
+                    "tools" => {
+                        for (tool, tool_value) in inner_table {
+                            tools_config.load_tool(path, tool, tool_value)?;
                         }
                     }
+                    "env" => {
+                        tools_config.env = parse_env_vars(path, "env", inner_table)?;
+                    }
                     _ => {
                         return Err(ProtoError::InvalidConfig(
                             path.to_path_buf(),
                             format!(
-                                "Invalid field \"{key}\", expected a mapped tool version, or a [plugins] map."
+                                "Invalid table \"{key}\", expected a [plugins], [tools], or [env] map."
                             ),
-                        ))
+                        ));
                     }
+                },
+                _ => {
+                    return Err(ProtoError::InvalidConfig(
+                        path.to_path_buf(),
+                        format!(
+                            "Invalid field \"{key}\", expected a mapped tool version, or a [plugins] map."
+                        ),
+                    ))
                 }
             }
-        } else {
-            return Err(ProtoError::InvalidConfig(
-                path.to_path_buf(),
-                "Expected a mapping of tools or plugins.".into(),
-            ));
         }
 
-        Ok(ToolsConfig {
-            tools,
-            plugins,
-            path: path.to_owned(),
-        })
+        Ok(tools_config)
+    }
+
+    fn load_tool(&mut self, path: &Path, tool: String, value: TomlValue) -> Result<(), ProtoError> {
+        let table = match value {
+            TomlValue::String(version) => {
+                let version = interpolate_env_vars(path, &format!("tools.{tool}"), &version)?;
+
+                self.tools.insert(tool, version);
+
+                return Ok(());
+            }
+            TomlValue::Table(table) => table,
+            _ => {
+                return Err(ProtoError::InvalidConfig(
+                    path.to_path_buf(),
+                    format!("Invalid tool \"{tool}\", expected a version string or a table."),
+                ));
+            }
+        };
+
+        let mut tool_config = ToolConfig::default();
+
+        for (field, value) in table {
+            match (field.as_str(), value) {
+                ("version", TomlValue::String(version)) => {
+                    let version =
+                        interpolate_env_vars(path, &format!("tools.{tool}.version"), &version)?;
+
+                    self.tools.insert(tool.clone(), version);
+                }
+                ("plugin", location) => {
+                    let locator = parse_plugin_locator(path, &tool, location)?;
+
+                    self.plugins.insert(tool.to_case(Case::Kebab), locator);
+                }
+                ("auto-install", TomlValue::Boolean(auto_install)) => {
+                    tool_config.auto_install = Some(auto_install);
+                }
+                ("env", TomlValue::Table(env_table)) => {
+                    tool_config.env =
+                        parse_env_vars(path, &format!("tools.{tool}.env"), env_table)?;
+                }
+                ("install-args", TomlValue::Array(args)) => {
+                    for arg in args {
+                        let TomlValue::String(arg) = arg else {
+                            return Err(ProtoError::InvalidConfig(
+                                path.to_path_buf(),
+                                format!("Invalid tools.{tool}.install-args, expected a list of strings."),
+                            ));
+                        };
+
+                        tool_config.install_args.push(interpolate_env_vars(
+                            path,
+                            &format!("tools.{tool}.install-args"),
+                            &arg,
+                        )?);
+                    }
+                }
+                (field, _) => {
+                    return Err(ProtoError::InvalidConfig(
+                        path.to_path_buf(),
+                        format!(
+                            "Invalid field \"tools.{tool}.{field}\", expected version, plugin, env, auto-install, or install-args."
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if !tool_config.is_empty() {
+            self.tool_configs.insert(tool, tool_config);
+        }
+
+        Ok(())
     }
 
     pub fn inherit_builtin_plugins(&mut self) {
@@ -164,30 +347,442 @@ impl ToolsConfig {
         }
     }
 
+    pub fn get_tool_config(&self, tool: &str) -> Option<&ToolConfig> {
+        self.tool_configs.get(tool)
+    }
+
+    /// Return environment variables to export when running the provided tool,
+    /// with tool specific variables taking precedence over the global `[env]`.
+    pub fn get_tool_env(&self, tool: &str) -> FxHashMap<String, String> {
+        let mut env = self.env.clone();
+
+        if let Some(tool_config) = self.get_tool_config(tool) {
+            env.extend(tool_config.env.clone());
+        }
+
+        env
+    }
+
     pub fn merge(&mut self, other: ToolsConfig) {
         self.tools.extend(other.tools);
+
+        for (id, tool_config) in other.tool_configs {
+            self.tool_configs.entry(id).or_default().merge(tool_config);
+        }
+
         self.plugins.extend(other.plugins);
+        self.env.extend(other.env);
     }
 
+    /// Write the configuration to its file. If the file already exists, the
+    /// document will be edited in place, preserving comments, ordering, and
+    /// formatting of all unrelated lines.
+    #[tracing::instrument(skip_all)]
     pub fn save(&self) -> Result<(), ProtoError> {
-        let mut map = TomlTable::with_capacity(self.tools.len());
+        let mut doc = if self.path.exists() {
+            fs::read_file(&self.path)?
+                .parse::<Document>()
+                .map_err(|error| ProtoError::InvalidConfig(self.path.clone(), error.to_string()))?
+        } else {
+            Document::new()
+        };
 
-        for (tool, version) in &self.tools {
-            map.insert(tool.to_owned(), TomlValue::String(version.to_owned()));
+        trace!(file = ?self.path, "Updating .prototools document");
+
+        self.update_document(&mut doc);
+
+        fs::write_file(&self.path, doc.to_string())?;
+
+        if let Some(dir) = self.path.parent() {
+            invalidate_cached_config(dir);
         }
 
-        if !self.plugins.is_empty() {
-            let mut plugins = TomlTable::with_capacity(self.plugins.len());
+        Ok(())
+    }
+
+    fn update_document(&self, doc: &mut Document) {
+        let mut handled_tools = FxHashSet::default();
+        let mut handled_plugins = FxHashSet::default();
 
-            for (plugin, locator) in &self.plugins {
-                plugins.insert(plugin.to_owned(), TomlValue::String(locator.to_string()));
+        if self.root {
+            if !doc
+                .get("root")
+                .is_some_and(|root| root.as_bool() == Some(true))
+            {
+                doc.insert("root", value(true));
             }
+        } else if doc.get("root").is_some_and(|root| root.is_bool()) {
+            doc.remove("root");
+        }
 
-            map.insert("plugins".to_owned(), TomlValue::Table(plugins));
+        // Update or remove tools mapped at the root
+        let root_tools = doc
+            .iter()
+            .filter(|(_, item)| item.is_str())
+            .map(|(key, _)| key.to_owned())
+            .collect::<Vec<_>>();
+
+        for tool in root_tools {
+            match self.tools.get(&tool) {
+                Some(version) if !self.tool_configs.contains_key(&tool) => {
+                    set_value(doc.as_table_mut(), &tool, Value::from(version));
+                    handled_tools.insert(tool);
+                }
+                _ => {
+                    doc.remove(&tool);
+                }
+            };
         }
 
-        toml::write_file(&self.path, &TomlValue::Table(map), true)?;
+        // Update or remove tools within the [tools] table
+        if let Some(tools_table) = doc.get_mut("tools").and_then(|t| t.as_table_like_mut()) {
+            let nested_tools = tools_table
+                .iter()
+                .map(|(key, _)| key.to_owned())
+                .collect::<Vec<_>>();
+
+            for tool in nested_tools {
+                if handled_tools.contains(&tool)
+                    || (!self.tools.contains_key(&tool) && !self.tool_configs.contains_key(&tool))
+                {
+                    tools_table.remove(&tool);
+                    continue;
+                }
 
-        Ok(())
+                let item = tools_table.get_mut(&tool).unwrap();
+
+                if item.is_str() && !self.tool_configs.contains_key(&tool) {
+                    set_value(tools_table, &tool, Value::from(&self.tools[&tool]));
+                } else if let Some(tool_table) = item.as_table_like_mut() {
+                    let plugin_id = tool.to_case(Case::Kebab);
+
+                    if tool_table.contains_key("plugin") {
+                        if let Some(locator) = self.plugins.get(&plugin_id) {
+                            set_value(tool_table, "plugin", Value::from(locator.to_string()));
+                            handled_plugins.insert(plugin_id);
+                        } else {
+                            tool_table.remove("plugin");
+                        }
+                    }
+
+                    self.update_tool_table(&tool, tool_table);
+                } else {
+                    let mut tool_table = Table::new();
+
+                    self.update_tool_table(&tool, &mut tool_table);
+
+                    *item = Item::Table(tool_table);
+                }
+
+                handled_tools.insert(tool);
+            }
+        }
+
+        // Add new tools
+        let mut new_tools = self
+            .tools
+            .keys()
+            .chain(self.tool_configs.keys())
+            .filter(|tool| !handled_tools.contains(*tool))
+            .collect::<Vec<_>>();
+
+        new_tools.sort();
+        new_tools.dedup();
+
+        for tool in new_tools {
+            if self.tool_configs.contains_key(tool) {
+                let mut tool_table = Table::new();
+
+                self.update_tool_table(tool, &mut tool_table);
+
+                match get_or_create_table(doc, "tools", true) {
+                    Item::Table(tools_table) => {
+                        tools_table.insert(tool, Item::Table(tool_table));
+                    }
+                    Item::Value(Value::InlineTable(tools_table)) => {
+                        tools_table
+                            .insert(tool, Value::InlineTable(tool_table.into_inline_table()));
+                    }
+                    _ => {}
+                };
+            } else {
+                doc.insert(tool, value(&self.tools[tool]));
+            }
+        }
+
+        if doc
+            .get("tools")
+            .and_then(|t| t.as_table_like())
+            .is_some_and(|t| t.is_empty())
+        {
+            doc.remove("tools");
+        }
+
+        // Update the [env] table
+        if self.env.is_empty() {
+            doc.remove("env");
+        } else if let Some(env_table) = get_or_create_table(doc, "env", false).as_table_like_mut() {
+            update_string_map(env_table, &self.env);
+        }
+
+        // Update the [plugins] table
+        let plugins = self
+            .plugins
+            .iter()
+            .filter(|(id, _)| !handled_plugins.contains(*id))
+            .map(|(id, locator)| (id.to_owned(), locator.to_string()))
+            .collect::<FxHashMap<_, _>>();
+
+        if plugins.is_empty() {
+            doc.remove("plugins");
+        } else if let Some(plugins_table) =
+            get_or_create_table(doc, "plugins", false).as_table_like_mut()
+        {
+            update_string_map(plugins_table, &plugins);
+        }
     }
+
+    fn update_tool_table(&self, tool: &str, table: &mut dyn TableLike) {
+        match self.tools.get(tool) {
+            Some(version) => set_value(table, "version", Value::from(version)),
+            None => {
+                table.remove("version");
+            }
+        };
+
+        let tool_config = self.tool_configs.get(tool).cloned().unwrap_or_default();
+
+        match tool_config.auto_install {
+            Some(auto_install) => set_value(table, "auto-install", Value::from(auto_install)),
+            None => {
+                table.remove("auto-install");
+            }
+        };
+
+        if tool_config.install_args.is_empty() {
+            table.remove("install-args");
+        } else {
+            set_value(
+                table,
+                "install-args",
+                Value::Array(Array::from_iter(&tool_config.install_args)),
+            );
+        }
+
+        if tool_config.env.is_empty() {
+            table.remove("env");
+        } else {
+            if !table.contains_key("env") {
+                table.insert("env", Item::Value(Value::InlineTable(InlineTable::new())));
+            }
+
+            if let Some(env_table) = table.get_mut("env").and_then(|t| t.as_table_like_mut()) {
+                update_string_map(env_table, &tool_config.env);
+            }
+        }
+    }
+}
+
+fn is_same_value(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        // Retain interpolated values like `${VAR}` when their expanded value hasn't changed
+        (Value::String(a), Value::String(b)) => {
+            a.value() == b.value() || expand_env_vars(a.value()).is_ok_and(|a| &a == b.value())
+        }
+        (Value::Boolean(a), Value::Boolean(b)) => a.value() == b.value(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| is_same_value(a, b))
+        }
+        _ => false,
+    }
+}
+
+// Only replace the value when it has changed, and retain the
+// surrounding whitespace and comments when it has.
+fn set_value(table: &mut dyn TableLike, key: &str, new_value: Value) {
+    if let Some(Item::Value(existing)) = table.get_mut(key) {
+        if !is_same_value(existing, &new_value) {
+            let decor = existing.decor().clone();
+
+            *existing = new_value;
+            *existing.decor_mut() = decor;
+        }
+
+        return;
+    }
+
+    table.insert(key, Item::Value(new_value));
+}
+
+fn update_string_map(table: &mut dyn TableLike, map: &FxHashMap<String, String>) {
+    let keys = table
+        .iter()
+        .map(|(key, _)| key.to_owned())
+        .collect::<Vec<_>>();
+
+    // Keys in the document may not be normalized (camelCase plugins),
+    // so we need to compare against the kebab-cased variant as well
+    for key in &keys {
+        let map_value = map.get(key).or_else(|| map.get(&key.to_case(Case::Kebab)));
+
+        match map_value {
+            Some(map_value) => set_value(table, key, Value::from(map_value)),
+            None => {
+                table.remove(key);
+            }
+        };
+    }
+
+    let mut new_keys = map
+        .keys()
+        .filter(|key| {
+            !keys
+                .iter()
+                .any(|k| k == *key || &k.to_case(Case::Kebab) == *key)
+        })
+        .collect::<Vec<_>>();
+
+    new_keys.sort();
+
+    for key in new_keys {
+        table.insert(key, value(&map[key]));
+    }
+}
+
+fn get_or_create_table<'doc>(doc: &'doc mut Document, key: &str, implicit: bool) -> &'doc mut Item {
+    if !doc.contains_key(key) {
+        let mut table = Table::new();
+        table.set_implicit(implicit);
+
+        // Separate from the previous table with an empty line
+        if !doc.is_empty() {
+            table.decor_mut().set_prefix("\n");
+        }
+
+        doc.insert(key, Item::Table(table));
+    }
+
+    doc.get_mut(key).unwrap()
+}
+
+fn parse_plugin_locator(
+    path: &Path,
+    plugin: &str,
+    location: TomlValue,
+) -> Result<PluginLocator, ProtoError> {
+    let TomlValue::String(location) = location else {
+        return Err(ProtoError::InvalidConfig(
+            path.to_path_buf(),
+            format!("Invalid plugin \"{plugin}\", expected a locator string."),
+        ));
+    };
+
+    // Expand variables before parsing, so they can be used within file paths and URLs
+    let location = interpolate_env_vars(path, &format!("plugins.{plugin}"), &location)?;
+
+    let mut locator =
+        PluginLocator::try_from(location).map_err(|error| ProtoError::InvalidPluginLocator {
+            path: path.to_path_buf(),
+            plugin: plugin.to_owned(),
+            error,
+        })?;
+
+    // Update file paths to be absolute
+    if let PluginLocator::SourceFile {
+        path: ref mut source_path,
+        ..
+    } = locator
+    {
+        *source_path = path.parent().unwrap().join(&source_path);
+    }
+
+    Ok(locator)
+}
+
+fn parse_env_vars(
+    path: &Path,
+    label: &str,
+    table: TomlTable,
+) -> Result<FxHashMap<String, String>, ProtoError> {
+    let mut vars = FxHashMap::default();
+
+    for (key, value) in table {
+        let TomlValue::String(value) = value else {
+            return Err(ProtoError::InvalidConfig(
+                path.to_path_buf(),
+                format!("Invalid environment variable \"{label}.{key}\", expected a string value."),
+            ));
+        };
+
+        let value = interpolate_env_vars(path, &format!("{label}.{key}"), &value)?;
+
+        vars.insert(key, value);
+    }
+
+    Ok(vars)
+}
+
+enum ExpandError {
+    InvalidName(String),
+    Unresolved(String),
+    Unterminated(String),
+}
+
+/// Expand `${VAR}` and `${VAR:-default}` environment variable references within
+/// the provided value. The default is used when the variable is unset or empty.
+fn expand_env_vars(value: &str) -> Result<String, ExpandError> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+
+        let Some(end) = rest[start..].find('}') else {
+            return Err(ExpandError::Unterminated(rest[start..].to_owned()));
+        };
+
+        let reference = &rest[start + 2..start + end];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+
+        if name.is_empty()
+            || name.starts_with(|c: char| c.is_ascii_digit())
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(ExpandError::InvalidName(name.to_owned()));
+        }
+
+        match (env::var(name).ok().filter(|var| !var.is_empty()), default) {
+            (Some(var), _) => result.push_str(&var),
+            (None, Some(default)) => result.push_str(default),
+            (None, None) => return Err(ExpandError::Unresolved(name.to_owned())),
+        };
+
+        rest = &rest[start + end + 1..];
+    }
+
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+fn interpolate_env_vars(path: &Path, label: &str, value: &str) -> Result<String, ProtoError> {
+    expand_env_vars(value).map_err(|error| {
+        ProtoError::InvalidConfig(
+            path.to_path_buf(),
+            match error {
+                ExpandError::InvalidName(name) => {
+                    format!("Invalid environment variable name \"{name}\" in {label}.")
+                }
+                ExpandError::Unresolved(name) => format!(
+                    "Unable to resolve environment variable \"{name}\" in {label}. Set the variable, or provide a fallback with ${{{name}:-default}}."
+                ),
+                ExpandError::Unterminated(reference) => {
+                    format!("Missing closing brace for \"{reference}\" in {label}.")
+                }
+            },
+        )
+    })
 }
@@ -1,18 +1,26 @@
 use crate::{errors::ProtoError, helpers::get_root};
 use rustc_hash::FxHashMap;
+use schemars::JsonSchema;
 use serde::Deserialize;
-use starbase_utils::toml;
+use starbase_utils::{fs, toml};
 use std::env;
+use toml_edit::{value, Document, Item, Table};
+use tracing::trace;
 use warpgate::PluginLocator;
 
 pub const USER_CONFIG_NAME: &str = "config.toml";
 
-#[derive(Debug, Deserialize)]
+/// User configuration, located at `~/.proto/config.toml`.
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct UserConfig {
+    /// Automatically clean stale tools and plugins after installing.
     pub auto_clean: bool,
+    /// Automatically install a tool when running and it's missing.
     pub auto_install: bool,
+    /// Intercept and disallow global package installs for Node.js.
     pub node_intercept_globals: bool,
+    /// Mapping of plugin names to locators.
     pub plugins: FxHashMap<String, PluginLocator>,
 }
 
@@ -41,6 +49,64 @@ impl UserConfig {
 
         Ok(config)
     }
+
+    /// Add a plugin to the `[plugins]` table of `~/.proto/config.toml`,
+    /// or remove it when no locator is provided. The document is edited in
+    /// place, preserving comments and formatting. Returns true if changed.
+    #[tracing::instrument(skip_all)]
+    pub fn update_plugin(id: &str, locator: Option<&PluginLocator>) -> Result<bool, ProtoError> {
+        let path = get_root()?.join(USER_CONFIG_NAME);
+
+        let mut doc = if path.exists() {
+            fs::read_file(&path)?
+                .parse::<Document>()
+                .map_err(|error| ProtoError::InvalidConfig(path.clone(), error.to_string()))?
+        } else {
+            Document::new()
+        };
+
+        trace!(file = ?path, plugin = id, "Updating user config document");
+
+        if !doc.contains_key("plugins") {
+            if locator.is_none() {
+                return Ok(false);
+            }
+
+            let mut table = Table::new();
+
+            if !doc.is_empty() {
+                table.decor_mut().set_prefix("\n");
+            }
+
+            doc.insert("plugins", Item::Table(table));
+        }
+
+        let Some(plugins) = doc["plugins"].as_table_like_mut() else {
+            return Err(ProtoError::InvalidConfig(
+                path,
+                "Invalid field \"plugins\", expected a map.".into(),
+            ));
+        };
+
+        match locator {
+            Some(locator) => {
+                plugins.insert(id, value(locator.to_string()));
+            }
+            None => {
+                if plugins.remove(id).is_none() {
+                    return Ok(false);
+                }
+
+                if plugins.is_empty() {
+                    doc.remove("plugins");
+                }
+            }
+        };
+
+        fs::write_file(&path, doc.to_string())?;
+
+        Ok(true)
+    }
 }
 
 fn from_var(name: &str, fallback: bool) -> bool {
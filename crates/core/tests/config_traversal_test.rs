@@ -0,0 +1,150 @@
+use proto_core::{ConfigTraversal, ToolsConfig};
+use rustc_hash::FxHashMap;
+use starbase_sandbox::create_empty_sandbox;
+use std::path::PathBuf;
+
+fn collect_dirs(traversal: &ConfigTraversal, dir: PathBuf) -> Vec<PathBuf> {
+    traversal
+        .traverse_from(dir)
+        .map(|entry| entry.unwrap().dir)
+        .collect()
+}
+
+#[test]
+fn stops_at_root_config() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(".prototools", "node = \"1.0.0\"");
+    fixture.create_file("one/.prototools", "root = true\nbun = \"1.0.0\"");
+    fixture.create_file("one/two/.prototools", "deno = \"1.0.0\"");
+
+    let traversal = ConfigTraversal {
+        root_dir: None,
+        stop_at_git: false,
+    };
+
+    assert_eq!(
+        collect_dirs(&traversal, fixture.path().join("one/two")),
+        vec![fixture.path().join("one/two"), fixture.path().join("one")]
+    );
+}
+
+#[test]
+fn stops_at_git_boundary() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file("repo/.git/HEAD", "");
+
+    let traversal = ConfigTraversal {
+        root_dir: None,
+        stop_at_git: true,
+    };
+
+    assert_eq!(
+        collect_dirs(&traversal, fixture.path().join("repo/nested")),
+        vec![
+            fixture.path().join("repo/nested"),
+            fixture.path().join("repo")
+        ]
+    );
+}
+
+#[test]
+fn stops_at_root_dir() {
+    let fixture = create_empty_sandbox();
+
+    let traversal = ConfigTraversal {
+        root_dir: Some(fixture.path().join("one")),
+        stop_at_git: false,
+    };
+
+    assert_eq!(
+        collect_dirs(&traversal, fixture.path().join("one/two")),
+        vec![fixture.path().join("one/two"), fixture.path().join("one")]
+    );
+}
+
+#[test]
+fn stops_at_root_dir_when_not_canonical() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file("one/two/.gitkeep", "");
+
+    let traversal = ConfigTraversal {
+        root_dir: Some(fixture.path().join("one/two/..")),
+        stop_at_git: false,
+    };
+
+    assert_eq!(
+        collect_dirs(&traversal, fixture.path().join("one/two")),
+        vec![fixture.path().join("one/two"), fixture.path().join("one")]
+    );
+}
+
+#[test]
+fn continues_past_git_when_disabled() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file("repo/.git/HEAD", "");
+    fixture.create_file(".prototools", "root = true");
+
+    let traversal = ConfigTraversal {
+        root_dir: None,
+        stop_at_git: false,
+    };
+
+    assert_eq!(
+        collect_dirs(&traversal, fixture.path().join("repo")),
+        vec![fixture.path().join("repo"), fixture.path().to_path_buf()]
+    );
+}
+
+#[test]
+fn includes_configs_for_each_dir() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(".git", "");
+    fixture.create_file(".prototools", "node = \"1.0.0\"");
+
+    let traversal = ConfigTraversal::new();
+    let entries = traversal
+        .traverse_from(fixture.path().join("one"))
+        .map(|entry| entry.unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(entries.len(), 2);
+    assert!(!entries[0].config.path.exists());
+    assert_eq!(
+        entries[1].config.tools,
+        FxHashMap::from_iter([("node".into(), "1.0.0".into())])
+    );
+}
+
+#[test]
+fn load_upwards_doesnt_leak_past_boundary() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(".prototools", "node = \"1.0.0\"\ndeno = \"1.0.0\"");
+    fixture.create_file("project/.prototools", "root = true\nnode = \"2.0.0\"");
+
+    let config = ToolsConfig::load_upwards_from(fixture.path().join("project/src")).unwrap();
+
+    assert!(config.root);
+    assert_eq!(
+        config.tools,
+        FxHashMap::from_iter([("node".into(), "2.0.0".into())])
+    );
+}
+
+#[test]
+fn invalidates_cache_when_saving() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(".git", "");
+    fixture.create_file(".prototools", "node = \"1.0.0\"");
+
+    let mut config = ToolsConfig::load_upwards_from(fixture.path()).unwrap();
+
+    config.tools.insert("node".into(), "2.0.0".into());
+    config.save().unwrap();
+
+    assert_eq!(
+        ToolsConfig::load_upwards_from(fixture.path())
+            .unwrap()
+            .tools,
+        FxHashMap::from_iter([("node".into(), "2.0.0".into())])
+    );
+}
@@ -0,0 +1,65 @@
+use proto_core::{ShimEntry, ShimRegistry};
+use starbase_sandbox::create_empty_sandbox;
+use std::thread;
+
+fn create_entry(tool: &str) -> ShimEntry {
+    ShimEntry {
+        tool: tool.into(),
+        ..ShimEntry::default()
+    }
+}
+
+#[test]
+fn keeps_entries_registered_by_other_instances() {
+    let fixture = create_empty_sandbox();
+    let mut first = ShimRegistry::load_from(fixture.path()).unwrap();
+    let mut second = ShimRegistry::load_from(fixture.path()).unwrap();
+
+    first.register("node", create_entry("node")).unwrap();
+    second.register("npm", create_entry("npm")).unwrap();
+
+    let registry = ShimRegistry::load_from(fixture.path()).unwrap();
+
+    assert_eq!(registry.shims.get("node"), Some(&create_entry("node")));
+    assert_eq!(registry.shims.get("npm"), Some(&create_entry("npm")));
+}
+
+#[test]
+fn keeps_entries_registered_concurrently() {
+    let fixture = create_empty_sandbox();
+    let root = fixture.path().to_path_buf();
+
+    let handles = (0..10)
+        .map(|index| {
+            let root = root.clone();
+
+            thread::spawn(move || {
+                let name = format!("tool-{index}");
+
+                ShimRegistry::load_from(&root)
+                    .unwrap()
+                    .register(&name, create_entry(&name))
+                    .unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let registry = ShimRegistry::load_from(&root).unwrap();
+
+    assert_eq!(registry.shims.len(), 10);
+}
+
+#[test]
+fn doesnt_create_file_when_removing_from_missing_registry() {
+    let fixture = create_empty_sandbox();
+    let mut registry = ShimRegistry::load_from(fixture.path()).unwrap();
+
+    registry.remove_local_shims(fixture.path()).unwrap();
+    registry.remove_tool("node").unwrap();
+
+    assert!(!fixture.path().join("shims.json").exists());
+}
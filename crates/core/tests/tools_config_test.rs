@@ -1,4 +1,4 @@
-use proto_core::{PluginLocator, ToolsConfig};
+use proto_core::{PluginLocator, ToolConfig, ToolsConfig};
 use rustc_hash::FxHashMap;
 use starbase_sandbox::create_empty_sandbox;
 
@@ -62,6 +62,99 @@ camelCase = "source:./camel.toml"
     );
 }
 
+#[test]
+#[should_panic(expected = "InvalidConfig")]
+fn errors_for_unknown_tool_field() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(".prototools", "[tools.node]\nunknown = true");
+
+    ToolsConfig::load_from(fixture.path()).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "InvalidConfig")]
+fn errors_for_non_string_env_var() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(".prototools", "[env]\nKEY = 123");
+
+    ToolsConfig::load_from(fixture.path()).unwrap();
+}
+
+#[test]
+fn parses_tools_table() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(
+        ".prototools",
+        r#"
+npm = "9.0.0"
+
+[tools]
+pnpm = "8.0.0"
+
+[tools.node]
+version = "18.0.0"
+auto-install = true
+install-args = ["--no-bundled-npm"]
+env = { NODE_ENV = "production" }
+
+[tools.foo]
+version = "1.0.0"
+plugin = "source:./foo.toml"
+
+[env]
+GLOBAL = "true"
+"#,
+    );
+
+    let config = ToolsConfig::load_from(fixture.path()).unwrap();
+
+    assert_eq!(
+        config.tools,
+        FxHashMap::from_iter([
+            ("npm".into(), "9.0.0".into()),
+            ("pnpm".into(), "8.0.0".into()),
+            ("node".into(), "18.0.0".into()),
+            ("foo".into(), "1.0.0".into()),
+        ])
+    );
+
+    assert_eq!(
+        config.tool_configs,
+        FxHashMap::from_iter([(
+            "node".into(),
+            ToolConfig {
+                auto_install: Some(true),
+                env: FxHashMap::from_iter([("NODE_ENV".into(), "production".into())]),
+                install_args: vec!["--no-bundled-npm".into()],
+            }
+        )])
+    );
+
+    assert_eq!(
+        config.plugins,
+        FxHashMap::from_iter([(
+            "foo".into(),
+            PluginLocator::SourceFile {
+                file: "./foo.toml".into(),
+                path: fixture.path().join("./foo.toml")
+            }
+        )])
+    );
+
+    assert_eq!(
+        config.env,
+        FxHashMap::from_iter([("GLOBAL".into(), "true".into())])
+    );
+
+    assert_eq!(
+        config.get_tool_env("node"),
+        FxHashMap::from_iter([
+            ("GLOBAL".into(), "true".into()),
+            ("NODE_ENV".into(), "production".into())
+        ])
+    );
+}
+
 #[test]
 fn formats_plugins_table() {
     let fixture = create_empty_sandbox();
@@ -87,6 +180,107 @@ foo = "source:./test.toml"
     );
 }
 
+#[test]
+fn formats_tools_table() {
+    let fixture = create_empty_sandbox();
+
+    let mut config = ToolsConfig::load_from(fixture.path()).unwrap();
+    config.tools.insert("node".into(), "18.0.0".into());
+    config.tool_configs.insert(
+        "node".into(),
+        ToolConfig {
+            auto_install: Some(false),
+            ..ToolConfig::default()
+        },
+    );
+    config.env.insert("KEY".into(), "value".into());
+    config.save().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(config.path).unwrap(),
+        r#"[tools.node]
+version = "18.0.0"
+auto-install = false
+
+[env]
+KEY = "value"
+"#,
+    );
+}
+
+#[test]
+fn preserves_formatting_when_saving() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(
+        ".prototools",
+        r#"# Runtime
+node    = "16.0.0" # LTS
+yarn = "3.0.0"
+
+[tools.deno]
+# Pinned for CI
+version = "1.30.0"
+auto-install = true
+
+[plugins]
+# Custom
+foo = "source:./foo.toml"
+"#,
+    );
+
+    let mut config = ToolsConfig::load_from(fixture.path()).unwrap();
+    config.tools.insert("node".into(), "18.0.0".into());
+    config.tools.insert("bun".into(), "1.0.0".into());
+    config.tools.remove("yarn");
+    config.tools.insert("deno".into(), "1.35.0".into());
+    config.save().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(config.path).unwrap(),
+        r#"# Runtime
+node    = "18.0.0" # LTS
+bun = "1.0.0"
+
+[tools.deno]
+# Pinned for CI
+version = "1.35.0"
+auto-install = true
+
+[plugins]
+# Custom
+foo = "source:./foo.toml"
+"#,
+    );
+}
+
+#[test]
+fn removes_tools_when_saving() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(
+        ".prototools",
+        r#"node = "16.0.0"
+
+[tools.deno]
+version = "1.30.0"
+env = { DENO_DIR = "/tmp" }
+
+[tools]
+npm = "9.0.0"
+"#,
+    );
+
+    let mut config = ToolsConfig::load_from(fixture.path()).unwrap();
+    config.tools.remove("deno");
+    config.tool_configs.remove("deno");
+    config.tools.remove("npm");
+    config.save().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(config.path).unwrap(),
+        "node = \"16.0.0\"\n",
+    );
+}
+
 #[test]
 fn merges_traversing_upwards() {
     let fixture = create_empty_sandbox();
@@ -158,3 +352,172 @@ deno = "7.8.9"
         ])
     );
 }
+
+#[test]
+fn merges_tool_tables_traversing_upwards() {
+    let fixture = create_empty_sandbox();
+
+    fixture.create_file(
+        "child/.prototools",
+        r#"
+[tools.node]
+version = "20.0.0"
+env = { NODE_ENV = "development", CHILD = "true" }
+"#,
+    );
+
+    fixture.create_file(
+        ".prototools",
+        r#"
+[tools.node]
+version = "18.0.0"
+auto-install = true
+install-args = ["--no-bundled-npm"]
+env = { NODE_ENV = "production", PARENT = "true" }
+"#,
+    );
+
+    let config = ToolsConfig::load_upwards_from(fixture.path().join("child")).unwrap();
+
+    assert_eq!(config.tools.get("node").unwrap(), "20.0.0");
+    assert_eq!(
+        config.tool_configs.get("node").unwrap(),
+        &ToolConfig {
+            auto_install: Some(true),
+            env: FxHashMap::from_iter([
+                ("NODE_ENV".into(), "development".into()),
+                ("CHILD".into(), "true".into()),
+                ("PARENT".into(), "true".into()),
+            ]),
+            install_args: vec!["--no-bundled-npm".into()],
+        }
+    );
+}
+
+#[test]
+fn preserves_root_when_saving() {
+    let fixture = create_empty_sandbox();
+    fixture.create_file(".prototools", "root = true\nnode = \"1.0.0\"\n");
+
+    let mut config = ToolsConfig::load_from(fixture.path()).unwrap();
+
+    assert!(config.root);
+
+    config.tools.insert("bun".into(), "1.0.0".into());
+    config.save().unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(fixture.path().join(".prototools")).unwrap(),
+        "root = true\nnode = \"1.0.0\"\nbun = \"1.0.0\"\n"
+    );
+}
+
+mod interpolation {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn expands_env_vars_in_versions() {
+        let fixture = create_empty_sandbox();
+        fixture.create_file(
+            ".prototools",
+            r#"
+node = "${PROTO_TEST_NODE_VERSION}"
+bun = "${PROTO_TEST_BUN_VERSION:-1.0}"
+deno = "${PROTO_TEST_DENO_VERSION:-1.0}"
+
+[tools.go]
+version = "v${PROTO_TEST_GO_VERSION}.0"
+install-args = ["--${PROTO_TEST_GO_ARG:-quiet}"]
+"#,
+        );
+
+        env::set_var("PROTO_TEST_NODE_VERSION", "20.0.0");
+        env::set_var("PROTO_TEST_DENO_VERSION", "2.0");
+        env::set_var("PROTO_TEST_GO_VERSION", "1.20");
+
+        let config = ToolsConfig::load_from(fixture.path()).unwrap();
+
+        env::remove_var("PROTO_TEST_NODE_VERSION");
+        env::remove_var("PROTO_TEST_DENO_VERSION");
+        env::remove_var("PROTO_TEST_GO_VERSION");
+
+        assert_eq!(
+            config.tools,
+            FxHashMap::from_iter([
+                ("node".into(), "20.0.0".into()),
+                ("bun".into(), "1.0".into()),
+                ("deno".into(), "2.0".into()),
+                ("go".into(), "v1.20.0".into()),
+            ])
+        );
+
+        assert_eq!(
+            config.get_tool_config("go").unwrap().install_args,
+            vec!["--quiet".to_owned()]
+        );
+    }
+
+    #[test]
+    fn expands_env_vars_in_plugins() {
+        let fixture = create_empty_sandbox();
+        fixture.create_file(
+            ".prototools",
+            r#"
+[plugins]
+foo = "source:${PROTO_TEST_PLUGINS_DIR}/foo.toml"
+"#,
+        );
+
+        env::set_var("PROTO_TEST_PLUGINS_DIR", "./plugins");
+
+        let config = ToolsConfig::load_from(fixture.path()).unwrap();
+
+        env::remove_var("PROTO_TEST_PLUGINS_DIR");
+
+        assert_eq!(
+            config.plugins,
+            FxHashMap::from_iter([(
+                "foo".into(),
+                PluginLocator::SourceFile {
+                    file: "./plugins/foo.toml".into(),
+                    path: fixture.path().join("./plugins/foo.toml")
+                }
+            )])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unable to resolve environment variable")]
+    fn errors_for_unresolved_var() {
+        let fixture = create_empty_sandbox();
+        fixture.create_file(".prototools", "node = \"${PROTO_TEST_MISSING}\"");
+
+        ToolsConfig::load_from(fixture.path()).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Missing closing brace")]
+    fn errors_for_unterminated_var() {
+        let fixture = create_empty_sandbox();
+        fixture.create_file(".prototools", "node = \"${PROTO_TEST_NODE\"");
+
+        ToolsConfig::load_from(fixture.path()).unwrap();
+    }
+
+    #[test]
+    fn preserves_references_when_saving() {
+        let fixture = create_empty_sandbox();
+        fixture.create_file(".prototools", "node = \"${PROTO_TEST_UNSET_NODE:-20}\"\n");
+
+        let mut config = ToolsConfig::load_from(fixture.path()).unwrap();
+
+        config.tools.insert("bun".into(), "1.0.0".into());
+        config.save().unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(fixture.path().join(".prototools")).unwrap(),
+            "node = \"${PROTO_TEST_UNSET_NODE:-20}\"\nbun = \"1.0.0\"\n"
+        );
+    }
+}
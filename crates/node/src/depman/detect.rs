@@ -1,29 +1,59 @@
 use crate::depman::NodeDependencyManager;
 use crate::platform::PackageJson;
 use proto_core::{async_trait, Detector, ProtoError};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // https://nodejs.org/api/packages.html#packagemanager
+// Precedence: `packageManager` > `volta.<name>` (following `volta.extends`)
+// > `devEngines.packageManager` > `engines.<name>`
 #[async_trait]
 impl Detector<'_> for NodeDependencyManager {
     async fn detect_version_from(&self, working_dir: &Path) -> Result<Option<String>, ProtoError> {
+        Ok(self
+            .detect_version_with_source(working_dir)
+            .await?
+            .map(|(version, _)| version))
+    }
+
+    fn get_version_files(&self) -> Option<Vec<String>> {
+        Some(vec!["package.json".into()])
+    }
+
+    async fn detect_version_with_source(
+        &self,
+        working_dir: &Path,
+    ) -> Result<Option<(String, PathBuf)>, ProtoError> {
         let package_path = working_dir.join("package.json");
 
         if package_path.exists() {
             let package_json = PackageJson::load(&package_path)?;
 
-            if let Some(manager) = package_json.package_manager {
+            if let Some(manager) = &package_json.package_manager {
                 let mut parts = manager.split('@');
                 let name = parts.next().unwrap_or_default();
 
                 if name == self.package_name {
-                    return Ok(Some(parts.next().unwrap_or("latest").to_owned()));
+                    return Ok(Some((
+                        parts.next().unwrap_or("latest").to_owned(),
+                        package_path,
+                    )));
                 }
             }
 
+            if let Some(pin) = package_json.get_volta_version(&package_path, &self.package_name)? {
+                return Ok(Some(pin));
+            }
+
+            if let Some(constraint) = package_json.get_dev_engine_version(&self.package_name) {
+                return Ok(Some((constraint, package_path)));
+            }
+
             if let Some(engines) = package_json.engines {
-                if let Some(constraint) = engines.get(&self.package_name) {
-                    return Ok(Some(constraint.to_owned()));
+                if let Some(constraint) = engines
+                    .get(&self.package_name)
+                    .and_then(|value| value.as_str())
+                {
+                    return Ok(Some((constraint.to_owned(), package_path)));
                 }
             }
         }
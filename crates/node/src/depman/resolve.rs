@@ -1,5 +1,4 @@
 use crate::depman::{NodeDependencyManager, NodeDependencyManagerType};
-use crate::platform::PackageJson;
 use crate::NodeLanguage;
 use proto_core::{
     async_trait, detect_version, is_offline, is_semantic_version, load_versions_manifest,
@@ -101,20 +100,9 @@ impl Resolvable<'_> for NodeDependencyManager {
                     let node_tool = Box::new(NodeLanguage::new(Proto::new()?));
 
                     if let Ok(node_version) = detect_version(&node_tool, None).await {
-                        let npm_package_path = node_tool
-                            .base_dir
-                            .join(node_version)
-                            .join(if cfg!(windows) {
-                                "node_modules"
-                            } else {
-                                "lib/node_modules"
-                            })
-                            .join("npm/package.json");
-
-                        if let Ok(npm_package) = PackageJson::load(&npm_package_path) {
-                            if let Some(npm_version) = npm_package.version {
-                                initial_version = npm_version;
-                            }
+                        if let Some(npm_version) = node_tool.get_bundled_npm_version(&node_version)
+                        {
+                            initial_version = npm_version;
                         }
                     }
                 }
@@ -1,21 +1,41 @@
 use crate::platform::PackageJson;
 use crate::NodeLanguage;
 use proto_core::{async_trait, load_version_file, Detector, ProtoError};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+// Precedence: `.nvmrc` > `.node-version` > `package.json` `volta.node`
+// (following `volta.extends`) > `devEngines.runtime` > `engines.node`
 #[async_trait]
 impl Detector<'_> for NodeLanguage {
     async fn detect_version_from(&self, working_dir: &Path) -> Result<Option<String>, ProtoError> {
+        Ok(self
+            .detect_version_with_source(working_dir)
+            .await?
+            .map(|(version, _)| version))
+    }
+
+    fn get_version_files(&self) -> Option<Vec<String>> {
+        Some(vec![
+            ".nvmrc".into(),
+            ".node-version".into(),
+            "package.json".into(),
+        ])
+    }
+
+    async fn detect_version_with_source(
+        &self,
+        working_dir: &Path,
+    ) -> Result<Option<(String, PathBuf)>, ProtoError> {
         let nvmrc = working_dir.join(".nvmrc");
 
         if nvmrc.exists() {
-            return Ok(Some(load_version_file(&nvmrc)?));
+            return Ok(Some((load_version_file(&nvmrc)?, nvmrc)));
         }
 
         let nodenv = working_dir.join(".node-version");
 
         if nodenv.exists() {
-            return Ok(Some(load_version_file(&nodenv)?));
+            return Ok(Some((load_version_file(&nodenv)?, nodenv)));
         }
 
         let package_path = working_dir.join("package.json");
@@ -23,9 +43,17 @@ impl Detector<'_> for NodeLanguage {
         if package_path.exists() {
             let package_json = PackageJson::load(&package_path)?;
 
+            if let Some(pin) = package_json.get_volta_version(&package_path, "node")? {
+                return Ok(Some(pin));
+            }
+
+            if let Some(constraint) = package_json.get_dev_engine_version("node") {
+                return Ok(Some((constraint, package_path)));
+            }
+
             if let Some(engines) = package_json.engines {
-                if let Some(constraint) = engines.get("node") {
-                    return Ok(Some(constraint.to_owned()));
+                if let Some(constraint) = engines.get("node").and_then(|value| value.as_str()) {
+                    return Ok(Some((constraint.to_owned(), package_path)));
                 }
             }
         }
@@ -11,6 +11,7 @@ mod verify;
 pub use depman::*;
 
 use once_cell::sync::OnceCell;
+use platform::PackageJson;
 use proto_core::{impl_tool, Describable, Manifest, Proto, ProtoError, Tool};
 use std::{
     any::Any,
@@ -41,6 +42,22 @@ impl NodeLanguage {
             version: None,
         }
     }
+
+    /// Return the version of npm that comes bundled with the provided
+    /// Node.js version, if that version has been installed.
+    pub fn get_bundled_npm_version(&self, version: &str) -> Option<String> {
+        let package_path = self
+            .base_dir
+            .join(version)
+            .join(if cfg!(windows) {
+                "node_modules"
+            } else {
+                "lib/node_modules"
+            })
+            .join("npm/package.json");
+
+        PackageJson::load(&package_path).ok()?.version
+    }
 }
 
 impl Describable<'_> for NodeLanguage {
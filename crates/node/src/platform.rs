@@ -1,10 +1,12 @@
 use proto_core::ProtoError;
-use rustc_hash::FxHashMap;
-use serde::Deserialize;
-use starbase_utils::json;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use starbase_utils::json::{self, JsonValue};
 use std::env::consts;
 use std::fmt;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 // Not everything is supported at the moment...
 // https://nodejs.org/api/process.html#processarch
@@ -60,18 +62,139 @@ impl fmt::Display for NodeArch {
     }
 }
 
+// Third-party tools define these fields in many shapes, so an unknown shape
+// is treated as missing, instead of failing to parse the entire file.
+fn deserialize_lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    Ok(json::from_value(JsonValue::deserialize(deserializer)?).ok())
+}
+
+// https://github.com/openjs-foundation/package-metadata-interoperability-collab-space/issues/15
+#[derive(Deserialize)]
+pub struct DevEngineDependency {
+    pub name: String,
+    pub version: Option<String>,
+}
+
 #[derive(Deserialize)]
+#[serde(untagged)]
+pub enum DevEngineField {
+    One(DevEngineDependency),
+    Many(Vec<DevEngineDependency>),
+}
+
+impl DevEngineField {
+    pub fn find(&self, name: &str) -> Option<&DevEngineDependency> {
+        match self {
+            DevEngineField::One(dep) => (dep.name == name).then_some(dep),
+            DevEngineField::Many(deps) => deps.iter().find(|dep| dep.name == name),
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DevEngines {
+    #[serde(deserialize_with = "deserialize_lenient")]
+    pub package_manager: Option<DevEngineField>,
+
+    #[serde(deserialize_with = "deserialize_lenient")]
+    pub runtime: Option<DevEngineField>,
+}
+
+// https://docs.volta.sh/advanced/workspaces
+#[derive(Default, Deserialize)]
+pub struct VoltaField {
+    #[serde(default, deserialize_with = "deserialize_lenient")]
+    pub extends: Option<String>,
+
+    #[serde(flatten)]
+    pub tools: FxHashMap<String, JsonValue>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct PackageJson {
-    pub engines: Option<FxHashMap<String, String>>,
+    #[serde(deserialize_with = "deserialize_lenient")]
+    pub dev_engines: Option<DevEngines>,
+
+    #[serde(deserialize_with = "deserialize_lenient")]
+    pub engines: Option<FxHashMap<String, JsonValue>>,
 
-    #[serde(rename = "packageManager")]
+    #[serde(deserialize_with = "deserialize_lenient")]
     pub package_manager: Option<String>,
 
+    #[serde(deserialize_with = "deserialize_lenient")]
     pub version: Option<String>,
+
+    #[serde(deserialize_with = "deserialize_lenient")]
+    pub volta: Option<VoltaField>,
 }
 
 impl PackageJson {
     pub fn load(path: &Path) -> Result<Self, ProtoError> {
         Ok(json::read_file(path)?)
     }
+
+    /// Return the version constraint for the provided runtime or package manager
+    /// name from the `devEngines` field, if defined.
+    pub fn get_dev_engine_version(&self, name: &str) -> Option<String> {
+        let dev_engines = self.dev_engines.as_ref()?;
+
+        [&dev_engines.runtime, &dev_engines.package_manager]
+            .into_iter()
+            .flatten()
+            .find_map(|field| field.find(name))
+            .map(|dep| dep.version.clone().unwrap_or_else(|| "latest".into()))
+    }
+
+    /// Return the version pinned for the provided tool name in the `volta` field,
+    /// following the `volta.extends` chain until a pin is found. Returns the version
+    /// and the path of the `package.json` that defined it.
+    pub fn get_volta_version(
+        &self,
+        path: &Path,
+        name: &str,
+    ) -> Result<Option<(String, PathBuf)>, ProtoError> {
+        let mut visited = FxHashSet::default();
+        let mut current_path = path.to_path_buf();
+        let mut current_volta = self.volta.as_ref();
+        let mut extended;
+
+        while let Some(volta) = current_volta {
+            if let Some(version) = volta.tools.get(name).and_then(|value| value.as_str()) {
+                return Ok(Some((version.to_owned(), current_path)));
+            }
+
+            let Some(extends) = &volta.extends else {
+                break;
+            };
+
+            visited.insert(fs::canonicalize(&current_path).unwrap_or(current_path.clone()));
+
+            let next_path = current_path
+                .parent()
+                .unwrap_or(Path::new("."))
+                .join(extends);
+
+            if !next_path.exists() {
+                break;
+            }
+
+            // Avoid infinite loops from circular chains, regardless of how the
+            // same file is referenced (`../a/package.json` vs `../../x/a/package.json`)
+            if !visited.insert(fs::canonicalize(&next_path).unwrap_or(next_path.clone())) {
+                break;
+            }
+
+            extended = PackageJson::load(&next_path)?;
+            current_volta = extended.volta.as_ref();
+            current_path = next_path;
+        }
+
+        Ok(None)
+    }
 }
@@ -1,5 +1,6 @@
 use proto_core::{
-    Detector, Downloadable, Executable, Installable, Proto, Resolvable, Shimable, Tool,
+    DetectCacheEntry, DetectTrace, Detector, Downloadable, Executable, Installable, Proto,
+    Resolvable, Shimable, Tool,
 };
 use proto_node::{NodeDependencyManager, NodeDependencyManagerType};
 use starbase_sandbox::create_empty_sandbox;
@@ -190,6 +191,74 @@ mod node_depman {
             );
         }
 
+        #[tokio::test]
+        async fn detects_volta() {
+            let fixture = create_empty_sandbox();
+
+            fixture.create_file(
+                "package.json",
+                r#"{"volta":{"node":"18.0.0","npm":"9.1.0"}}"#,
+            );
+
+            let tool = create_depman(fixture.path());
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("9.1.0".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn detects_dev_engines() {
+            let fixture = create_empty_sandbox();
+
+            fixture.create_file(
+                "package.json",
+                r#"{"devEngines":{"packageManager":{"name":"npm","version":"^9"}}}"#,
+            );
+
+            let tool = create_depman(fixture.path());
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("^9".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn dev_engines_without_version_defaults_to_latest() {
+            let fixture = create_empty_sandbox();
+
+            fixture.create_file(
+                "package.json",
+                r#"{"devEngines":{"packageManager":[{"name":"npm"}]}}"#,
+            );
+
+            let tool = create_depman(fixture.path());
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("latest".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn prefers_package_manager_over_volta() {
+            let fixture = create_empty_sandbox();
+
+            fixture.create_file(
+                "package.json",
+                r#"{"packageManager":"npm@8.0.0","volta":{"npm":"9.1.0"}}"#,
+            );
+
+            let tool = create_depman(fixture.path());
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("8.0.0".into())
+            );
+        }
+
         #[tokio::test]
         async fn detects_npm() {
             let fixture = create_empty_sandbox();
@@ -277,6 +346,32 @@ mod node_depman {
                 Some("7.8.9".into())
             );
         }
+
+        #[test]
+        fn doesnt_cache_bundled_version() {
+            let fixture = create_empty_sandbox();
+            let tool = Box::new(NodeDependencyManager::new(
+                Proto::from(fixture.path()),
+                NodeDependencyManagerType::Npm,
+            ));
+
+            let trace = DetectTrace {
+                version: Some("bundled".into()),
+                ..DetectTrace::default()
+            };
+
+            assert_eq!(
+                DetectCacheEntry::from_trace(
+                    &tool,
+                    &trace,
+                    fixture.path(),
+                    "9.0.0",
+                    &Default::default()
+                )
+                .unwrap(),
+                None
+            );
+        }
     }
 
     mod downloader {
@@ -105,6 +105,127 @@ mod node {
                 Some("16.*".into())
             );
         }
+
+        #[tokio::test]
+        async fn detects_volta() {
+            let fixture = create_empty_sandbox();
+            let tool = create_node(fixture.path());
+
+            fixture.create_file("package.json", r#"{"volta":{"node":"18.12.0"}}"#);
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("18.12.0".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn follows_volta_extends() {
+            let fixture = create_empty_sandbox();
+            let tool = create_node(fixture.path());
+
+            fixture.create_file("package.json", r#"{"volta":{"node":"18.12.0"}}"#);
+            fixture.create_file(
+                "packages/app/package.json",
+                r#"{"volta":{"extends":"../../package.json","npm":"9.0.0"}}"#,
+            );
+
+            assert_eq!(
+                tool.detect_version_with_source(&fixture.path().join("packages/app"))
+                    .await
+                    .unwrap(),
+                Some((
+                    "18.12.0".into(),
+                    fixture.path().join("packages/app/../../package.json")
+                ))
+            );
+        }
+
+        #[tokio::test]
+        async fn handles_circular_volta_extends() {
+            let fixture = create_empty_sandbox();
+            let tool = create_node(fixture.path());
+
+            fixture.create_file(
+                "a/package.json",
+                r#"{"volta":{"extends":"../b/package.json"}}"#,
+            );
+            fixture.create_file(
+                "b/package.json",
+                r#"{"volta":{"extends":"../a/package.json"}}"#,
+            );
+
+            assert_eq!(
+                tool.detect_version_from(&fixture.path().join("a"))
+                    .await
+                    .unwrap(),
+                None
+            );
+        }
+
+        #[tokio::test]
+        async fn ignores_unknown_field_shapes() {
+            let fixture = create_empty_sandbox();
+            let tool = create_node(fixture.path());
+
+            fixture.create_file(
+                "package.json",
+                r#"{"version":1,"packageManager":{},"devEngines":{"runtime":"node"},"engines":{"node":"16.*","vscode":{}},"volta":{"extends":true,"node":"18.12.0","yarn":false}}"#,
+            );
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("18.12.0".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn detects_dev_engines() {
+            let fixture = create_empty_sandbox();
+            let tool = create_node(fixture.path());
+
+            fixture.create_file(
+                "package.json",
+                r#"{"devEngines":{"runtime":[{"name":"bun"},{"name":"node","version":">=20"}]}}"#,
+            );
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some(">=20".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn prefers_volta_over_dev_engines_and_engines() {
+            let fixture = create_empty_sandbox();
+            let tool = create_node(fixture.path());
+
+            fixture.create_file(
+                "package.json",
+                r#"{"engines":{"node":"16.*"},"devEngines":{"runtime":{"name":"node","version":"20.*"}},"volta":{"node":"18.12.0"}}"#,
+            );
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("18.12.0".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn prefers_dev_engines_over_engines() {
+            let fixture = create_empty_sandbox();
+            let tool = create_node(fixture.path());
+
+            fixture.create_file(
+                "package.json",
+                r#"{"engines":{"node":"16.*"},"devEngines":{"runtime":{"name":"node","version":"20.*"}}}"#,
+            );
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("20.*".into())
+            );
+        }
     }
 
     mod downloader {
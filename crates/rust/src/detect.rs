@@ -1,10 +1,25 @@
+use crate::toolchain::RustToolchain;
 use crate::RustLanguage;
 use proto_core::{async_trait, Detector, ProtoError};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[async_trait]
 impl Detector<'_> for RustLanguage {
-    async fn detect_version_from(&self, _working_dir: &Path) -> Result<Option<String>, ProtoError> {
-        Ok(None)
+    async fn detect_version_from(&self, working_dir: &Path) -> Result<Option<String>, ProtoError> {
+        Ok(self
+            .detect_version_with_source(working_dir)
+            .await?
+            .map(|(version, _)| version))
+    }
+
+    async fn detect_version_with_source(
+        &self,
+        working_dir: &Path,
+    ) -> Result<Option<(String, PathBuf)>, ProtoError> {
+        let Some((toolchain, path)) = RustToolchain::load_from(working_dir)? else {
+            return Ok(None);
+        };
+
+        Ok(toolchain.channel.map(|channel| (channel, path)))
     }
 }
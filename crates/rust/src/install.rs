@@ -1,15 +1,16 @@
+use crate::toolchain::RustToolchain;
 use crate::{get_triple_target, RustLanguage};
-use proto_core::{async_trait, color, Describable, Installable, ProtoError, Resolvable};
+use proto_core::{async_trait, Describable, Installable, ProtoError, Resolvable};
+use std::env;
 use std::path::{Path, PathBuf};
 use tokio::process::Command;
-use tracing::debug;
-
-fn handle_error(e: std::io::Error) -> ProtoError {
-    ProtoError::Message(format!(
-        "Failed to run {}: {}",
-        color::shell("rustup"),
-        color::muted_light(e.to_string())
-    ))
+use tracing::{debug, warn};
+
+fn handle_error(error: std::io::Error) -> ProtoError {
+    ProtoError::ExecuteFailed {
+        bin: "rustup".into(),
+        error,
+    }
 }
 
 async fn is_installed_in_rustup(install_dir: &Path) -> Result<bool, ProtoError> {
@@ -29,9 +30,14 @@ async fn is_installed_in_rustup(install_dir: &Path) -> Result<bool, ProtoError>
     Ok(installed_list.contains(&install_target))
 }
 
-async fn run_rustup_toolchain(command: &str, version: &str) -> Result<bool, ProtoError> {
+async fn run_rustup_toolchain(
+    command: &str,
+    version: &str,
+    args: &[String],
+) -> Result<bool, ProtoError> {
     let status = Command::new("rustup")
         .args(["toolchain", command, version])
+        .args(args)
         .spawn()
         .map_err(handle_error)?
         .wait()
@@ -66,7 +72,43 @@ impl Installable<'_> for RustLanguage {
             return Ok(false);
         }
 
-        let success = run_rustup_toolchain("install", self.get_resolved_version()).await?;
+        let mut args = vec![];
+
+        // Include components and targets from the project's toolchain file,
+        // but only when installing the channel that the file requires
+        if let Ok(working_dir) = env::current_dir() {
+            match RustToolchain::load_upwards_from(&working_dir) {
+                Ok(Some(toolchain)) if toolchain.matches_version(self.get_resolved_version()) => {
+                    for component in toolchain.components {
+                        args.push("--component".to_owned());
+                        args.push(component);
+                    }
+
+                    for target in toolchain.targets {
+                        args.push("--target".to_owned());
+                        args.push(target);
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    warn!(
+                        tool = self.get_id(),
+                        "Unable to load rust-toolchain file, ignoring its components and targets: {}",
+                        error
+                    );
+                }
+            };
+        }
+
+        if !args.is_empty() {
+            debug!(
+                tool = self.get_id(),
+                args = ?args,
+                "Including components and targets from rust-toolchain file"
+            );
+        }
+
+        let success = run_rustup_toolchain("install", self.get_resolved_version(), &args).await?;
 
         debug!(tool = self.get_id(), "Successfully installed tool");
 
@@ -83,7 +125,7 @@ impl Installable<'_> for RustLanguage {
             return Ok(false);
         }
 
-        let success = run_rustup_toolchain("uninstall", self.get_resolved_version()).await?;
+        let success = run_rustup_toolchain("uninstall", self.get_resolved_version(), &[]).await?;
 
         debug!(tool = self.get_id(), "Successfully uninstalled tool");
 
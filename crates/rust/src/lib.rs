@@ -4,8 +4,11 @@ mod execute;
 mod install;
 mod resolve;
 mod shim;
+mod toolchain;
 mod verify;
 
+pub use toolchain::*;
+
 use once_cell::sync::OnceCell;
 use proto_core::{impl_tool, is_musl, Describable, Manifest, Proto, ProtoError, Tool};
 use std::{
@@ -1,3 +1,4 @@
+use crate::toolchain::is_channel;
 use crate::RustLanguage;
 use proto_core::{
     async_trait, create_version_manifest_from_tags, is_offline, is_semantic_version, load_git_tags,
@@ -52,10 +53,7 @@ impl Resolvable<'_> for RustLanguage {
 
         let manifest = self.load_version_manifest().await?;
 
-        let candidate = if initial_version == "stable"
-            || initial_version == "beta"
-            || initial_version == "nightly"
-        {
+        let candidate = if is_channel(&initial_version) {
             debug!(
                 tool = self.get_id(),
                 channel = initial_version,
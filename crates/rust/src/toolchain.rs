@@ -0,0 +1,122 @@
+use proto_core::{ConfigTraversal, ProtoError};
+use serde::Deserialize;
+use starbase_utils::{fs, toml};
+use std::path::{Path, PathBuf};
+
+// https://rust-lang.github.io/rustup/overrides.html#the-toolchain-file
+pub const TOOLCHAIN_FILES: [&str; 2] = ["rust-toolchain", "rust-toolchain.toml"];
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RustToolchain {
+    pub channel: Option<String>,
+    pub components: Vec<String>,
+    pub targets: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RustToolchainFile {
+    toolchain: RustToolchain,
+}
+
+impl RustToolchain {
+    /// Load the toolchain file from the provided directory. When both files exist,
+    /// the legacy `rust-toolchain` takes precedence, as it does in rustup.
+    pub fn load_from(dir: &Path) -> Result<Option<(Self, PathBuf)>, ProtoError> {
+        for file in TOOLCHAIN_FILES {
+            let path = dir.join(file);
+
+            if path.exists() {
+                return Ok(Some((Self::load(&path)?, path)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Load the toolchain file from the provided directory, or the closest parent,
+    /// stopping at the same boundaries as `.prototools` traversal.
+    pub fn load_upwards_from(dir: &Path) -> Result<Option<Self>, ProtoError> {
+        for entry in ConfigTraversal::new().traverse_from(dir) {
+            if let Some((toolchain, _)) = Self::load_from(&entry?.dir)? {
+                return Ok(Some(toolchain));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ProtoError> {
+        let content = fs::read_file(path)?;
+
+        // The legacy file may only contain the channel name
+        if !path.extension().is_some_and(|ext| ext == "toml") && !content.contains('[') {
+            let channel = content.trim();
+
+            return Ok(RustToolchain {
+                channel: (!channel.is_empty()).then(|| remove_host_triple(channel)),
+                ..RustToolchain::default()
+            });
+        }
+
+        let mut file: RustToolchainFile = toml::from_str(&content)
+            .map_err(|error| ProtoError::InvalidConfig(path.to_path_buf(), error.to_string()))?;
+
+        file.toolchain.channel = file
+            .toolchain
+            .channel
+            .map(|channel| remove_host_triple(&channel));
+
+        Ok(file.toolchain)
+    }
+
+    /// Return true if the toolchain's channel is the provided version,
+    /// either exactly, or partially (`1.70` matches `1.70.0`).
+    pub fn matches_version(&self, version: &str) -> bool {
+        self.channel.as_deref().is_some_and(|channel| {
+            channel == version || version.starts_with(&format!("{channel}."))
+        })
+    }
+}
+
+/// Remove the host triple from a channel, while preserving the archive date.
+/// For example, `nightly-2023-07-01-x86_64-unknown-linux-gnu` becomes `nightly-2023-07-01`.
+pub fn remove_host_triple(value: &str) -> String {
+    let mut parts = value.split('-');
+    let mut channel = parts.next().unwrap_or_default().to_owned();
+    let rest = parts.collect::<Vec<_>>();
+
+    if rest.len() >= 3
+        && rest[0].len() == 4
+        && rest[..3]
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+    {
+        channel.push('-');
+        channel.push_str(&rest[..3].join("-"));
+    }
+
+    channel
+}
+
+/// Return true if the value is a rustup channel, optionally with
+/// an archive date. For example, `stable`, or `nightly-2023-07-01`.
+pub fn is_channel(value: &str) -> bool {
+    let (channel, date) = match value.split_once('-') {
+        Some((channel, date)) => (channel, Some(date)),
+        None => (value, None),
+    };
+
+    if channel != "stable" && channel != "beta" && channel != "nightly" {
+        return false;
+    }
+
+    match date {
+        Some(date) => {
+            let parts = date.split('-').collect::<Vec<_>>();
+
+            parts.len() == 3 && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()))
+        }
+        None => true,
+    }
+}
@@ -0,0 +1,186 @@
+use proto_core::{Detector, Proto};
+use proto_rust::{is_channel, remove_host_triple, RustLanguage, RustToolchain};
+use starbase_sandbox::create_empty_sandbox;
+
+mod rust {
+    use super::*;
+
+    mod detector {
+        use super::*;
+
+        #[tokio::test]
+        async fn doesnt_match_if_no_files() {
+            let fixture = create_empty_sandbox();
+            let tool = RustLanguage::new(Proto::from(fixture.path()));
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                None
+            );
+        }
+
+        #[tokio::test]
+        async fn detects_legacy_file() {
+            let fixture = create_empty_sandbox();
+            let tool = RustLanguage::new(Proto::from(fixture.path()));
+
+            fixture.create_file("rust-toolchain", "1.70\n");
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("1.70".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn detects_legacy_file_with_toml() {
+            let fixture = create_empty_sandbox();
+            let tool = RustLanguage::new(Proto::from(fixture.path()));
+
+            fixture.create_file("rust-toolchain", "[toolchain]\nchannel = \"beta\"");
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("beta".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn detects_toml_file() {
+            let fixture = create_empty_sandbox();
+            let tool = RustLanguage::new(Proto::from(fixture.path()));
+
+            fixture.create_file(
+                "rust-toolchain.toml",
+                "[toolchain]\nchannel = \"nightly-2023-07-01\"",
+            );
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("nightly-2023-07-01".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn prefers_legacy_file() {
+            let fixture = create_empty_sandbox();
+            let tool = RustLanguage::new(Proto::from(fixture.path()));
+
+            fixture.create_file("rust-toolchain", "stable");
+            fixture.create_file("rust-toolchain.toml", "[toolchain]\nchannel = \"beta\"");
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                Some("stable".into())
+            );
+        }
+
+        #[tokio::test]
+        async fn skips_toml_file_without_channel() {
+            let fixture = create_empty_sandbox();
+            let tool = RustLanguage::new(Proto::from(fixture.path()));
+
+            fixture.create_file("rust-toolchain.toml", "[toolchain]\npath = \"/custom\"");
+
+            assert_eq!(
+                tool.detect_version_from(fixture.path()).await.unwrap(),
+                None
+            );
+        }
+    }
+
+    mod toolchain {
+        use super::*;
+
+        #[test]
+        fn parses_components_and_targets() {
+            let fixture = create_empty_sandbox();
+
+            fixture.create_file(
+                "rust-toolchain.toml",
+                r#"
+[toolchain]
+channel = "1.70.0"
+components = ["clippy", "rustfmt"]
+targets = ["wasm32-wasi"]
+"#,
+            );
+
+            assert_eq!(
+                RustToolchain::load_upwards_from(&fixture.path().join("nested/dir")).unwrap(),
+                Some(RustToolchain {
+                    channel: Some("1.70.0".into()),
+                    components: vec!["clippy".into(), "rustfmt".into()],
+                    targets: vec!["wasm32-wasi".into()],
+                })
+            );
+        }
+
+        #[test]
+        fn stops_at_traversal_boundary() {
+            let fixture = create_empty_sandbox();
+
+            fixture.create_file("rust-toolchain", "1.70.0");
+            fixture.create_file("nested/.prototools", "root = true");
+
+            assert_eq!(
+                RustToolchain::load_upwards_from(&fixture.path().join("nested")).unwrap(),
+                None
+            );
+        }
+
+        #[test]
+        fn removes_host_triple_from_channel() {
+            let fixture = create_empty_sandbox();
+
+            fixture.create_file(
+                "rust-toolchain.toml",
+                "[toolchain]\nchannel = \"nightly-2023-07-01-x86_64-unknown-linux-gnu\"",
+            );
+
+            assert_eq!(
+                RustToolchain::load_from(fixture.path())
+                    .unwrap()
+                    .unwrap()
+                    .0
+                    .channel,
+                Some("nightly-2023-07-01".into())
+            );
+
+            assert_eq!(
+                remove_host_triple("stable-x86_64-pc-windows-msvc"),
+                "stable"
+            );
+            assert_eq!(remove_host_triple("1.70.0-aarch64-apple-darwin"), "1.70.0");
+            assert_eq!(
+                remove_host_triple("nightly-2023-07-01"),
+                "nightly-2023-07-01"
+            );
+        }
+
+        #[test]
+        fn matches_version_against_channel() {
+            let toolchain = RustToolchain {
+                channel: Some("1.70".into()),
+                ..RustToolchain::default()
+            };
+
+            assert!(toolchain.matches_version("1.70"));
+            assert!(toolchain.matches_version("1.70.0"));
+            assert!(!toolchain.matches_version("1.71.0"));
+            assert!(!toolchain.matches_version("1.700.0"));
+            assert!(!RustToolchain::default().matches_version("stable"));
+        }
+
+        #[test]
+        fn matches_channels() {
+            assert!(is_channel("stable"));
+            assert!(is_channel("beta"));
+            assert!(is_channel("nightly"));
+            assert!(is_channel("nightly-2023-07-01"));
+            assert!(!is_channel("nightly-2023"));
+            assert!(!is_channel("1.70"));
+            assert!(!is_channel("canary"));
+        }
+    }
+}
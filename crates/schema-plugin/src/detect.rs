@@ -1,16 +1,30 @@
 use crate::SchemaPlugin;
 use proto_core::{async_trait, load_version_file, Detector, ProtoError};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[async_trait]
 impl Detector<'_> for SchemaPlugin {
     async fn detect_version_from(&self, working_dir: &Path) -> Result<Option<String>, ProtoError> {
+        Ok(self
+            .detect_version_with_source(working_dir)
+            .await?
+            .map(|(version, _)| version))
+    }
+
+    fn get_version_files(&self) -> Option<Vec<String>> {
+        Some(self.schema.detect.version_files.clone().unwrap_or_default())
+    }
+
+    async fn detect_version_with_source(
+        &self,
+        working_dir: &Path,
+    ) -> Result<Option<(String, PathBuf)>, ProtoError> {
         if let Some(version_files) = &self.schema.detect.version_files {
             for file in version_files {
                 let file_path = working_dir.join(file);
 
                 if file_path.exists() {
-                    return Ok(Some(load_version_file(&file_path)?));
+                    return Ok(Some((load_version_file(&file_path)?, file_path)));
                 }
             }
         }
@@ -8,7 +8,9 @@ mod shim;
 mod verify;
 
 use once_cell::sync::OnceCell;
-use proto_core::{impl_tool, Describable, Manifest, Proto, ProtoError, Resolvable, Tool};
+use proto_core::{
+    impl_tool, Describable, Manifest, PluginSource, Proto, ProtoError, Resolvable, Tool,
+};
 pub use schema::*;
 use std::{
     any::Any,
@@ -23,6 +25,7 @@ pub struct SchemaPlugin {
     pub base_dir: PathBuf,
     pub bin_path: Option<PathBuf>,
     pub shim_path: Option<PathBuf>,
+    pub source: Option<PluginSource>,
     pub temp_dir: PathBuf,
     pub version: Option<String>,
 
@@ -39,6 +42,7 @@ impl SchemaPlugin {
             bin_path: None,
             manifest: OnceCell::new(),
             shim_path: None,
+            source: None,
             temp_dir: proto.temp_dir.join(&id),
             version: None,
             id,
@@ -93,6 +97,10 @@ impl Describable<'_> for SchemaPlugin {
     fn get_name(&self) -> String {
         self.schema.name.clone()
     }
+
+    fn get_plugin_source(&self) -> Option<&PluginSource> {
+        self.source.as_ref()
+    }
 }
 
 impl_tool!(SchemaPlugin);
@@ -76,7 +76,7 @@ impl Resolvable<'_> for SchemaPlugin {
 
             // Invalid schema
         } else {
-            return Err(ProtoError::Message(format!("Unable to resolve versions for {}. Schema either requires a `git_url` or `manifest_url`.", self.get_name())));
+            return Err(ProtoError::PluginInvalidSchema(self.get_name()));
         };
 
         manifest.inherit_aliases(&self.get_manifest()?.aliases);
@@ -1,31 +1,46 @@
 use rustc_hash::FxHashMap;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::env::consts;
 
-#[derive(Debug, Default, Deserialize)]
+/// Settings for a specific operating system.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct PlatformMapper {
+    /// Name of the folder within the archive to remove when unpacking.
     pub archive_prefix: Option<String>,
+    /// Path to the executable, relative from the installation directory.
     pub bin_path: Option<String>,
+    /// File name of the checksum file to verify downloads with.
     pub checksum_file: Option<String>,
+    /// File name of the archive or executable to download.
     pub download_file: String,
 }
 
-#[derive(Debug, Default, Deserialize)]
+/// Settings for detecting versions from the file system.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct DetectSchema {
+    /// List of file names to detect a version from.
     pub version_files: Option<Vec<String>>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Settings for downloading and installing the tool.
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct InstallSchema {
+    /// Mapping of Rust architectures to the tool's architecture names.
     pub arch: FxHashMap<String, String>,
+    /// URL to download the checksum file from.
     pub checksum_url: Option<String>,
+    /// URL to download the archive or executable from.
     pub download_url: String,
+    /// Whether the download is an archive that must be unpacked.
     pub unpack: bool,
     // Global bins
+    /// Arguments to pass when installing global packages.
     pub global_args: Option<Vec<String>>,
+    /// List of directories to locate globally installed binaries.
     pub globals_dir: Vec<String>,
 }
 
@@ -42,14 +57,19 @@ impl Default for InstallSchema {
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Settings for resolving available versions.
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct ResolveSchema {
     // Manifest
+    /// URL to a JSON manifest of available versions.
     pub manifest_url: Option<String>,
+    /// Property name of each version within the manifest.
     pub manifest_version_key: String,
     // Tags
+    /// URL to a Git repository to load version tags from.
     pub git_url: Option<String>,
+    /// Regex pattern to extract a version from each Git tag.
     pub git_tag_pattern: String,
 }
 
@@ -64,11 +84,15 @@ impl Default for ResolveSchema {
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Settings for creating shims.
+#[derive(Debug, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct ShimSchema {
+    /// Create a local shim within the tool's installation directory.
     pub local: bool,
+    /// Create a global shim within `~/.proto/bin`.
     pub global: bool,
+    /// Name of a parent executable to run the shim with.
     pub parent_bin: Option<String>,
 }
 
@@ -82,7 +106,8 @@ impl Default for ShimSchema {
     }
 }
 
-#[derive(Debug, Default, Deserialize)]
+/// The type of tool being managed.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum SchemaToolType {
     #[default]
@@ -91,12 +116,15 @@ pub enum SchemaToolType {
     Cli,
 }
 
-#[derive(Debug, Default, Deserialize)]
+/// A TOML schema plugin, for managing a tool without WASM.
+#[derive(Debug, Default, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case")]
 pub struct Schema {
+    /// Human readable name of the tool.
     pub name: String,
     #[serde(rename = "type")]
     pub type_of: SchemaToolType,
+    /// Platform specific settings, keyed by operating system.
     pub platform: FxHashMap<String, PlatformMapper>,
 
     pub detect: DetectSchema,
@@ -1,5 +1,6 @@
 use proto_core::{
-    Detector, Downloadable, Executable, Installable, Proto, Resolvable, Shimable, Verifiable,
+    get_sha256_hash_of_file, Detector, Downloadable, Executable, Installable, Manifest, Proto,
+    Resolvable, Shimable, Tool, Verifiable,
 };
 use proto_schema_plugin::{
     DetectSchema, InstallSchema, PlatformMapper, ResolveSchema, Schema, SchemaPlugin,
@@ -335,6 +336,56 @@ mod schema_plugin {
 
             assert_eq!(tool.get_archive_prefix().unwrap(), Some(prefix));
         }
+
+        #[tokio::test]
+        async fn records_provenance_for_single_binaries() {
+            let fixture = create_empty_sandbox();
+            let proto = Proto::from(fixture.path());
+            let mut tool = create_plugin(
+                fixture.path(),
+                Schema {
+                    platform: FxHashMap::from_iter([(
+                        consts::OS.into(),
+                        PlatformMapper {
+                            download_file: "moon-{arch}".into(),
+                            ..PlatformMapper::default()
+                        },
+                    )]),
+                    install: InstallSchema {
+                        download_url: "https://example.com/v{version}/{download_file}".into(),
+                        unpack: false,
+                        ..InstallSchema::default()
+                    },
+                    ..Schema::default()
+                },
+            );
+
+            // Already downloaded, so no request is made
+            let download_path = tool.get_download_path().unwrap();
+
+            fs::create_dir_all(download_path.parent().unwrap()).unwrap();
+            fs::write(&download_path, "binary").unwrap();
+
+            let checksum = get_sha256_hash_of_file(&download_path).unwrap();
+
+            tool.setup("1.0.0").await.unwrap();
+
+            assert!(!download_path.exists());
+
+            let manifest = Manifest::load_from(proto.tools_dir.join("moon-test")).unwrap();
+            let metadata = manifest.versions.get("1.0.0").unwrap();
+
+            assert_eq!(
+                metadata.download_url.as_deref(),
+                Some(
+                    format!("https://example.com/v1.0.0/moon-{}", tool.schema.get_arch()).as_str()
+                )
+            );
+            assert_eq!(metadata.checksum, Some(checksum));
+            assert_eq!(metadata.checksum_algorithm.as_deref(), Some("sha256"));
+            assert_eq!(metadata.archive_size, Some(6));
+            assert_eq!(metadata.installed_size, Some(6));
+        }
     }
 
     mod resolver {
@@ -0,0 +1,597 @@
+//! A native shim that is hard linked into `~/.proto/bin` for each tool. When a version
+//! can be resolved cheaply (environment variable, `.prototools`, or global default),
+//! the tool's binary is executed directly. Otherwise it defers to `proto run`.
+
+use serde_json::Value;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CONFIG_NAME: &str = ".prototools";
+const DETECT_CACHE_DIR: &str = "temp/detect";
+const MANIFEST_NAME: &str = "manifest.json";
+const REGISTRY_NAME: &str = "shims.json";
+
+// Avoid writing the manifest on every execution
+const LAST_USED_THRESHOLD: u128 = 60 * 60 * 1000;
+
+// Must match the detection cache in proto
+const DETECT_CACHE_MAX_AGE: u128 = 60 * 60 * 24 * 1000;
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if env::var_os("PROTO_DEBUG").is_some() {
+            eprintln!("[proto-shim] {}", format!($($arg)*));
+        }
+    };
+}
+
+struct Shim {
+    name: String,
+    tool: String,
+    alt_bin: Option<String>,
+    parent_bin: Option<String>,
+    before_args: Vec<String>,
+    after_args: Vec<String>,
+    bin_path: Option<String>,
+    version_files: Option<Vec<String>>,
+}
+
+fn get_string(value: &Value, key: &str) -> Option<String> {
+    value.get(key)?.as_str().map(|value| value.to_owned())
+}
+
+fn get_strings(value: &Value, key: &str) -> Option<Vec<String>> {
+    Some(
+        value
+            .get(key)?
+            .as_array()?
+            .iter()
+            .filter_map(|item| item.as_str().map(|item| item.to_owned()))
+            .collect(),
+    )
+}
+
+fn get_env_var_name(tool: &str, suffix: &str) -> String {
+    format!("PROTO_{}_{suffix}", tool.to_uppercase())
+}
+
+fn get_proto_root() -> Option<PathBuf> {
+    if let Some(root) = env::var_os("PROTO_ROOT") {
+        return Some(PathBuf::from(root));
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".proto"))
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn load_shim(root: &Path, name: &str) -> Option<Shim> {
+    let registry = read_json(&root.join(REGISTRY_NAME))?;
+    let entry = registry.get("shims")?.get(name)?;
+    let tool = get_string(entry, "tool")?;
+    let tool_entry = registry.get("tools").and_then(|tools| tools.get(&tool));
+
+    Some(Shim {
+        name: name.to_owned(),
+        alt_bin: get_string(entry, "alt_bin"),
+        parent_bin: get_string(entry, "parent_bin"),
+        before_args: get_strings(entry, "before_args").unwrap_or_default(),
+        after_args: get_strings(entry, "after_args").unwrap_or_default(),
+        bin_path: tool_entry.and_then(|entry| get_string(entry, "bin_path")),
+        version_files: tool_entry.and_then(|entry| get_strings(entry, "version_files")),
+        tool,
+    })
+}
+
+/// Parse a simple TOML string value, and error for anything more complex
+/// (inline tables, interpolated variables, etc).
+fn parse_string_value(value: &str) -> Result<String, ()> {
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'');
+    let quote = quote.ok_or(())?;
+    let end = value[1..].find(quote).ok_or(())? + 1;
+    let rest = value[end + 1..].trim();
+
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(());
+    }
+
+    let inner = &value[1..end];
+
+    if inner.contains("${") || inner.contains('\\') {
+        return Err(());
+    }
+
+    Ok(inner.to_owned())
+}
+
+/// Extract the tool's version, and whether this is a root config, from a `.prototools` file.
+/// Returns an error for anything that requires proto to handle, like environment variables,
+/// or for any key or section that is not understood.
+fn parse_config(contents: &str, tool: &str) -> Result<(Option<String>, bool), ()> {
+    let tool_table = format!("tools.{tool}");
+    let mut section = String::new();
+    let mut version = None;
+    let mut root = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            section = line
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .replace(['"', ' '], "");
+
+            // Other tools and plugins do not affect this tool
+            let is_other_tool = section
+                .strip_prefix("tools.")
+                .is_some_and(|id| id != tool && !id.starts_with(&format!("{tool}.")));
+
+            if section != "tools" && section != "plugins" && section != tool_table && !is_other_tool
+            {
+                return Err(());
+            }
+
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or(())?;
+        let key = key.trim().trim_matches('"');
+        let value = value.trim();
+
+        match section.as_str() {
+            "" if key == "root" => {
+                root = match value.split('#').next().unwrap_or_default().trim() {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(()),
+                };
+            }
+            // Root level keys are tool versions, anything else (like `env`) is deferred
+            "" | "tools" => {
+                if key == "env" || key == "plugins" || key == "tools" || key.contains('.') {
+                    return Err(());
+                }
+
+                let value = parse_string_value(value)?;
+
+                if key == tool {
+                    version = Some(value);
+                }
+            }
+            "plugins" => {}
+            _ if section == tool_table => match key {
+                "version" => {
+                    version = Some(parse_string_value(value)?);
+                }
+                "auto-install" | "install-args" | "plugin" => {}
+                _ => return Err(()),
+            },
+            _ => {}
+        };
+    }
+
+    Ok((version, root))
+}
+
+enum Detected {
+    Version(String),
+    Global,
+    Defer,
+}
+
+struct CachedVersion {
+    version: String,
+    env: Vec<(String, String)>,
+}
+
+fn detect_local_version(shim: &Shim) -> Detected {
+    if let Ok(version) = env::var(get_env_var_name(&shim.tool, "VERSION")) {
+        debug!("Detected version {version} from environment variable");
+
+        return Detected::Version(version);
+    }
+
+    let config_root = env::var_os("PROTO_CONFIG_ROOT")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+    let stop_at_git = is_stop_at_git_enabled();
+    let mut current_dir = env::current_dir().ok();
+    let mut detected = None;
+
+    // Keep traversing after a version is found, as environment variables
+    // from any config up to the boundary must be applied by proto itself
+    while let Some(dir) = current_dir {
+        let config_path = dir.join(CONFIG_NAME);
+        let mut is_root = false;
+
+        if config_path.exists() {
+            let Ok(contents) = fs::read_to_string(&config_path) else {
+                return Detected::Defer;
+            };
+
+            match parse_config(&contents, &shim.tool) {
+                Ok((version, root)) => {
+                    if let Some(version) = version.filter(|_| detected.is_none()) {
+                        debug!("Detected version {version} from {}", config_path.display());
+
+                        detected = Some(version);
+                    }
+
+                    is_root = root;
+                }
+                Err(_) => {
+                    debug!("Unable to parse {}, deferring", config_path.display());
+
+                    return Detected::Defer;
+                }
+            };
+        }
+
+        // Ecosystem files must be parsed by the tool itself
+        if detected.is_none() {
+            match &shim.version_files {
+                Some(files) => {
+                    if let Some(file) = files.iter().find(|file| dir.join(file).exists()) {
+                        debug!("Found version file {file} in {}, deferring", dir.display());
+
+                        return Detected::Defer;
+                    }
+                }
+                None => {
+                    debug!("Version files are unknown, deferring");
+
+                    return Detected::Defer;
+                }
+            };
+        }
+
+        if is_root
+            || config_root
+                .as_ref()
+                .is_some_and(|root| is_same_dir(root, &dir))
+            || (stop_at_git && dir.join(".git").exists())
+        {
+            break;
+        }
+
+        current_dir = dir.parent().map(|parent| parent.to_path_buf());
+    }
+
+    detected.map_or(Detected::Global, Detected::Version)
+}
+
+fn get_modified_time(path: &Path) -> Option<u128> {
+    path.metadata()
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+}
+
+fn is_cache_enabled() -> bool {
+    env::var("PROTO_CACHE").map_or(true, |value| {
+        value != "0" && value != "false" && value != "no" && value != "off"
+    })
+}
+
+fn is_stop_at_git_enabled() -> bool {
+    env::var("PROTO_CONFIG_STOP_AT_GIT").map_or(true, |value| {
+        value != "0" && value != "false" && value != "no" && value != "off"
+    })
+}
+
+fn is_same_dir(a: &Path, b: &Path) -> bool {
+    if a == b {
+        return true;
+    }
+
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn get_sorted_strings(value: Option<&Value>) -> Vec<String> {
+    let mut list = value
+        .and_then(|value| value.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(|item| item.to_owned()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    list.sort();
+    list
+}
+
+/// Load a version (and environment variables) for the current directory that was
+/// previously detected by `proto run`, if none of its inputs have changed.
+fn load_cached_version(shim: &Shim, root: &Path, manifest: &Value) -> Option<CachedVersion> {
+    if !is_cache_enabled() {
+        return None;
+    }
+
+    let cache = read_json(
+        &root
+            .join(DETECT_CACHE_DIR)
+            .join(format!("{}.json", shim.tool)),
+    )?;
+    let working_dir = env::current_dir().ok()?;
+    let entry = cache
+        .get("entries")?
+        .get(working_dir.to_string_lossy().as_ref())?;
+
+    let cached_at = entry.get("cached_at")?.as_u64()? as u128;
+
+    if now().saturating_sub(cached_at) > DETECT_CACHE_MAX_AGE {
+        debug!("Cached version has expired");
+
+        return None;
+    }
+
+    let config_root = env::var("PROTO_CONFIG_ROOT")
+        .ok()
+        .filter(|value| !value.is_empty());
+
+    if get_string(entry, "config_root") != config_root
+        || entry
+            .get("stop_at_git")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(true)
+            != is_stop_at_git_enabled()
+    {
+        return None;
+    }
+
+    // Manifest state that affects detection and resolution
+    let state = entry.get("manifest")?;
+
+    if get_string(state, "default_version") != get_string(manifest, "default_version")
+        || get_sorted_strings(state.get("installed_versions"))
+            != get_sorted_strings(manifest.get("installed_versions"))
+        || state.get("aliases") != manifest.get("aliases")
+    {
+        debug!("Manifest has changed since the version was cached");
+
+        return None;
+    }
+
+    for file in entry.get("files")?.as_array()? {
+        let path = PathBuf::from(file.get("path")?.as_str()?);
+        let modified = file
+            .get("modified")
+            .and_then(|value| value.as_u64())
+            .map(u128::from);
+
+        if get_modified_time(&path) != modified {
+            debug!(
+                "File {} has changed since the version was cached",
+                path.display()
+            );
+
+            return None;
+        }
+    }
+
+    let version = get_string(entry, "version")?;
+
+    debug!("Detected version {version} from cache");
+
+    Some(CachedVersion {
+        env: entry
+            .get("env")
+            .and_then(|env| env.as_object())
+            .map(|env| {
+                env.iter()
+                    .filter_map(|(key, value)| Some((key.to_owned(), value.as_str()?.to_owned())))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        version,
+    })
+}
+
+fn now() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0)
+}
+
+/// Update the last used timestamp, so that `proto clean` doesn't remove the version.
+fn track_used_at(manifest_path: &Path, mut manifest: Value, version: &str) {
+    let now = now();
+
+    let Some(versions) = manifest
+        .get_mut("versions")
+        .and_then(|versions| versions.as_object_mut())
+    else {
+        return;
+    };
+
+    let Some(metadata) = versions
+        .get_mut(version)
+        .and_then(|metadata| metadata.as_object_mut())
+    else {
+        return;
+    };
+
+    let last_used_at = metadata
+        .get("last_used_at")
+        .and_then(|value| value.as_u64())
+        .unwrap_or(0) as u128;
+
+    if now.saturating_sub(last_used_at) < LAST_USED_THRESHOLD {
+        return;
+    }
+
+    metadata.insert("last_used_at".into(), Value::from(now as u64));
+
+    // Ignore errors in case of race conditions,
+    // this timestamp isn't *super* important
+    if let Ok(data) = serde_json::to_string_pretty(&manifest) {
+        let _ = fs::write(manifest_path, data);
+    }
+}
+
+fn is_script(path: &Path) -> bool {
+    cfg!(windows)
+        && path
+            .extension()
+            .is_some_and(|ext| ext == "cmd" || ext == "bat" || ext == "ps1")
+}
+
+/// Attempt to create a command that executes the tool's binary directly.
+fn create_direct_command(shim: &Shim, root: &Path, args: &[OsString]) -> Option<Command> {
+    let tool_dir = root.join("tools").join(&shim.tool);
+    let manifest_path = tool_dir.join(MANIFEST_NAME);
+    let manifest = read_json(&manifest_path)?;
+    let mut env_vars = vec![];
+
+    let version = match detect_local_version(shim) {
+        Detected::Version(version) => version,
+        Detected::Global => get_string(&manifest, "default_version")?,
+        Detected::Defer => {
+            let cached = load_cached_version(shim, root, &manifest)?;
+            env_vars = cached.env;
+            cached.version
+        }
+    };
+
+    let version = manifest
+        .get("aliases")
+        .and_then(|aliases| get_string(aliases, &version))
+        .unwrap_or(version);
+
+    // Only fully-qualified installed versions can be handled without proto
+    let is_installed = manifest
+        .get("installed_versions")
+        .and_then(|versions| versions.as_array())
+        .is_some_and(|versions| versions.iter().any(|item| item.as_str() == Some(&version)));
+
+    if !is_installed {
+        debug!("Version {version} is not installed, deferring");
+
+        return None;
+    }
+
+    let install_dir = tool_dir.join(&version);
+    let bin_path = install_dir.join(shim.alt_bin.as_ref().or(shim.bin_path.as_ref())?);
+
+    if !bin_path.is_file() || is_script(&bin_path) {
+        debug!("Binary {} is not executable, deferring", bin_path.display());
+
+        return None;
+    }
+
+    let mut command = match (&shim.alt_bin, &shim.parent_bin) {
+        (None, Some(parent_bin)) => {
+            let mut command = Command::new(
+                env::var_os(get_env_var_name(parent_bin, "BIN"))
+                    .unwrap_or_else(|| OsString::from(parent_bin)),
+            );
+            command.arg(&bin_path);
+            command
+        }
+        _ => Command::new(&bin_path),
+    };
+
+    command
+        .args(&shim.before_args)
+        .args(args)
+        .args(&shim.after_args)
+        .envs(env_vars)
+        .env(get_env_var_name(&shim.tool, "VERSION"), &version)
+        .env(get_env_var_name(&shim.tool, "BIN"), &bin_path);
+
+    debug!("Executing {} directly", bin_path.display());
+
+    if env::var_os("PROTO_SKIP_USED_AT").is_none() {
+        track_used_at(&manifest_path, manifest, &version);
+    }
+
+    Some(command)
+}
+
+fn create_proto_command(shim: &Shim, args: &[OsString]) -> Command {
+    let mut command = Command::new(if cfg!(windows) { "proto.exe" } else { "proto" });
+    command.arg("run").arg(&shim.tool);
+
+    if let Some(alt_bin) = &shim.alt_bin {
+        command.arg("--bin").arg(alt_bin);
+    }
+
+    command
+        .arg("--")
+        .args(&shim.before_args)
+        .args(args)
+        .args(&shim.after_args);
+
+    debug!("Executing {} with proto run", shim.name);
+
+    command
+}
+
+#[cfg(unix)]
+fn exec_command(mut command: Command) -> ! {
+    use std::os::unix::process::CommandExt;
+
+    let error = command.exec();
+
+    eprintln!("proto-shim: failed to execute: {error}");
+    process::exit(1);
+}
+
+#[cfg(not(unix))]
+fn exec_command(mut command: Command) -> ! {
+    match command.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(error) => {
+            eprintln!("proto-shim: failed to execute: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let mut args = env::args_os();
+
+    let name = args
+        .next()
+        .as_deref()
+        .and_then(|arg| Path::new(arg).file_stem())
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let args = args.collect::<Vec<_>>();
+
+    let Some(root) = get_proto_root() else {
+        eprintln!("proto-shim: unable to determine the proto root directory");
+        process::exit(1);
+    };
+
+    let Some(shim) = load_shim(&root, &name) else {
+        eprintln!("proto-shim: no shim registered for \"{name}\", run `proto shims regen` to regenerate shims");
+        process::exit(1);
+    };
+
+    let command = create_direct_command(&shim, &root, &args)
+        .unwrap_or_else(|| create_proto_command(&shim, &args));
+
+    exec_command(command);
+}
@@ -0,0 +1,362 @@
+#![cfg(unix)]
+
+use starbase_sandbox::{create_empty_sandbox, Sandbox};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+fn create_executable(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn create_shim_sandbox() -> Sandbox {
+    let temp = create_empty_sandbox();
+    let root = temp.path().join(".proto");
+
+    // Stop upward traversal at the sandbox
+    temp.create_file(".git/HEAD", "");
+
+    temp.create_file(
+        ".proto/shims.json",
+        r#"{
+  "shims": {
+    "node": { "tool": "node" },
+    "npx": { "tool": "node", "alt_bin": "bin/npx" },
+    "pnpx": { "tool": "pnpm", "before_args": ["dlx"] }
+  },
+  "tools": {
+    "node": { "bin_path": "bin/node", "version_files": [".nvmrc"] }
+  }
+}"#,
+    );
+
+    temp.create_file(
+        ".proto/tools/node/manifest.json",
+        r#"{
+  "aliases": { "work": "20.0.0" },
+  "default_version": "18.0.0",
+  "installed_versions": ["18.0.0", "20.0.0"],
+  "versions": {
+    "18.0.0": { "installed_at": 1, "last_used_at": 1 },
+    "20.0.0": { "installed_at": 1 }
+  }
+}"#,
+    );
+
+    for version in ["18.0.0", "20.0.0"] {
+        create_executable(
+            &root.join(format!("tools/node/{version}/bin/node")),
+            &format!("#!/bin/sh\necho \"node {version} $@\""),
+        );
+    }
+
+    create_executable(
+        &root.join("tools/node/20.0.0/bin/npx"),
+        "#!/bin/sh\necho \"npx $@\"",
+    );
+
+    // Fallback for when the shim defers to proto
+    create_executable(&root.join("bin/proto"), "#!/bin/sh\necho \"proto $@\"");
+
+    for name in ["node", "npx", "pnpx"] {
+        fs::hard_link(
+            env!("CARGO_BIN_EXE_proto-shim"),
+            root.join("bin").join(name),
+        )
+        .unwrap();
+    }
+
+    temp
+}
+
+fn run_shim(temp: &Sandbox, name: &str, args: &[&str]) -> String {
+    run_shim_in(temp, temp.path(), name, args, &[])
+}
+
+fn run_shim_in(
+    temp: &Sandbox,
+    dir: &Path,
+    name: &str,
+    args: &[&str],
+    envs: &[(&str, &str)],
+) -> String {
+    let root = temp.path().join(".proto");
+
+    let output = Command::new(root.join("bin").join(name))
+        .args(args)
+        .current_dir(dir)
+        .env("PROTO_ROOT", &root)
+        .env(
+            "PATH",
+            format!("{}:/usr/bin:/bin", root.join("bin").display()),
+        )
+        .env_remove("PROTO_NODE_VERSION")
+        .env_remove("PROTO_PNPM_VERSION")
+        .env_remove("PROTO_CONFIG_ROOT")
+        .env_remove("PROTO_CONFIG_STOP_AT_GIT")
+        .envs(envs.iter().copied())
+        .output()
+        .unwrap();
+
+    String::from_utf8(output.stdout).unwrap().trim().to_owned()
+}
+
+#[test]
+fn executes_global_default_version() {
+    let temp = create_shim_sandbox();
+
+    assert_eq!(
+        run_shim(&temp, "node", &["--version"]),
+        "node 18.0.0 --version"
+    );
+}
+
+#[test]
+fn executes_version_from_prototools() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "node = \"20.0.0\"");
+
+    assert_eq!(run_shim(&temp, "node", &[]), "node 20.0.0");
+}
+
+#[test]
+fn stops_at_git_boundary() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "node = \"20.0.0\"");
+    temp.create_file("repo/.git/HEAD", "");
+
+    let dir = temp.path().join("repo");
+
+    assert_eq!(run_shim_in(&temp, &dir, "node", &[], &[]), "node 18.0.0");
+    assert_eq!(
+        run_shim_in(
+            &temp,
+            &dir,
+            "node",
+            &[],
+            &[("PROTO_CONFIG_STOP_AT_GIT", "false")]
+        ),
+        "node 20.0.0"
+    );
+}
+
+#[test]
+fn executes_version_from_tool_table() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "[tools.node]\nversion = \"20.0.0\"");
+
+    assert_eq!(run_shim(&temp, "node", &[]), "node 20.0.0");
+}
+
+#[test]
+fn executes_version_from_alias() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "node = \"work\"");
+
+    assert_eq!(run_shim(&temp, "node", &[]), "node 20.0.0");
+}
+
+#[test]
+fn executes_version_from_env_var() {
+    let temp = create_shim_sandbox();
+    let root = temp.path().join(".proto");
+
+    let output = Command::new(root.join("bin/node"))
+        .current_dir(temp.path())
+        .env("PROTO_ROOT", &root)
+        .env("PROTO_NODE_VERSION", "20.0.0")
+        .output()
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        "node 20.0.0"
+    );
+}
+
+#[test]
+fn executes_alt_bin() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "node = \"20.0.0\"");
+
+    assert_eq!(run_shim(&temp, "npx", &["foo"]), "npx foo");
+}
+
+#[test]
+fn defers_for_version_files() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".nvmrc", "20");
+
+    assert_eq!(run_shim(&temp, "node", &["a"]), "proto run node -- a");
+}
+
+#[test]
+fn defers_for_uninstalled_versions() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "node = \"19\"");
+
+    assert_eq!(run_shim(&temp, "node", &[]), "proto run node --");
+}
+
+#[test]
+fn defers_for_env_vars() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "node = \"20.0.0\"\n\n[env]\nKEY = \"value\"");
+
+    assert_eq!(run_shim(&temp, "node", &[]), "proto run node --");
+}
+
+#[test]
+fn defers_for_inline_env_vars() {
+    let temp = create_shim_sandbox();
+    temp.create_file(
+        ".prototools",
+        "node = \"20.0.0\"\nenv = { KEY = \"value\" }",
+    );
+
+    assert_eq!(run_shim(&temp, "node", &[]), "proto run node --");
+}
+
+#[test]
+fn defers_for_unknown_settings() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "node = \"20.0.0\"\n\n[settings]\nkey = true");
+
+    assert_eq!(run_shim(&temp, "node", &[]), "proto run node --");
+
+    temp.create_file(
+        ".prototools",
+        "[tools.node]\nversion = \"20.0.0\"\nunknown = true",
+    );
+
+    assert_eq!(run_shim(&temp, "node", &[]), "proto run node --");
+}
+
+#[test]
+fn ignores_settings_for_other_tools() {
+    let temp = create_shim_sandbox();
+    temp.create_file(
+        ".prototools",
+        "node = \"20.0.0\"\n\n[tools.bun]\nversion = \"1.0.0\"\n\n[tools.bun.env]\nKEY = \"value\"\n\n[plugins]\nbun = \"source:./bun.toml\"",
+    );
+
+    assert_eq!(run_shim(&temp, "node", &[]), "node 20.0.0");
+}
+
+#[test]
+fn defers_for_env_vars_in_parent_configs() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".prototools", "[tools.node.env]\nKEY = \"value\"");
+    temp.create_file("app/.prototools", "node = \"20.0.0\"");
+
+    let dir = temp.path().join("app");
+
+    assert_eq!(
+        run_shim_in(&temp, &dir, "node", &[], &[]),
+        "proto run node --"
+    );
+}
+
+#[test]
+fn defers_for_unknown_tools() {
+    let temp = create_shim_sandbox();
+
+    assert_eq!(
+        run_shim(&temp, "pnpx", &["create"]),
+        "proto run pnpm -- dlx create"
+    );
+}
+
+#[test]
+fn tracks_last_used_at() {
+    let temp = create_shim_sandbox();
+
+    run_shim(&temp, "node", &[]);
+
+    let manifest = fs::read_to_string(temp.path().join(".proto/tools/node/manifest.json")).unwrap();
+
+    assert!(!manifest.contains("\"last_used_at\": 1\n"));
+}
+
+fn create_detect_cache(temp: &Sandbox, file: &str, env: &str) {
+    let file = temp.path().join(file);
+    let modified = file.exists().then(|| get_modified_time(&file));
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap();
+
+    temp.create_file(
+        ".proto/temp/detect/node.json",
+        format!(
+            r#"{{
+  "entries": {{
+    "{}": {{
+      "version": "20.0.0",
+      "cached_at": {},
+      "config_root": null,
+      "env": {},
+      "files": [{{ "path": "{}", "modified": {} }}],
+      "manifest": {{
+        "aliases": {{ "work": "20.0.0" }},
+        "default_version": "18.0.0",
+        "installed_versions": ["20.0.0", "18.0.0"]
+      }}
+    }}
+  }}
+}}"#,
+            temp.path().display(),
+            now.as_millis(),
+            env,
+            file.display(),
+            modified.map_or("null".into(), |time| time.to_string()),
+        ),
+    );
+}
+
+fn get_modified_time(path: &Path) -> u128 {
+    fs::metadata(path)
+        .unwrap()
+        .modified()
+        .unwrap()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
+
+#[test]
+fn executes_cached_version() {
+    let temp = create_shim_sandbox();
+    temp.create_file(".nvmrc", "20");
+    create_detect_cache(&temp, ".nvmrc", "{}");
+
+    assert_eq!(run_shim(&temp, "node", &["a"]), "node 20.0.0 a");
+}
+
+#[test]
+fn executes_cached_version_with_inline_env_vars() {
+    let temp = create_shim_sandbox();
+    temp.create_file(
+        ".prototools",
+        "node = \"20.0.0\"\nenv = { KEY = \"value\" }",
+    );
+    create_detect_cache(&temp, ".prototools", r#"{ "KEY": "value" }"#);
+
+    create_executable(
+        &temp.path().join(".proto/tools/node/20.0.0/bin/node"),
+        "#!/bin/sh\necho \"node 20.0.0 $KEY\"",
+    );
+
+    assert_eq!(run_shim(&temp, "node", &[]), "node 20.0.0 value");
+}
+
+#[test]
+fn defers_for_stale_cached_version() {
+    let temp = create_shim_sandbox();
+    create_detect_cache(&temp, ".nvmrc", "{}");
+    temp.create_file(".nvmrc", "20");
+
+    assert_eq!(run_shim(&temp, "node", &["a"]), "proto run node -- a");
+}
@@ -29,7 +29,7 @@ impl<'plugin> PluginContainer<'plugin> {
         id: &str,
         manifest: Manifest,
         functions: impl IntoIterator<Item = Function>,
-    ) -> miette::Result<PluginContainer<'new>> {
+    ) -> Result<PluginContainer<'new>, WarpgateError> {
         let plugin = Plugin::create_with_manifest(&manifest, functions, true)
             .map_err(|error| WarpgateError::PluginCreateFailed { error })?;
 
@@ -45,13 +45,13 @@ impl<'plugin> PluginContainer<'plugin> {
     pub fn new_without_functions<'new>(
         id: &str,
         manifest: Manifest,
-    ) -> miette::Result<PluginContainer<'new>> {
+    ) -> Result<PluginContainer<'new>, WarpgateError> {
         Self::new(id, manifest, [])
     }
 
     /// Call a function on the plugin with no input and cache the output before returning it.
     /// Subsequent calls will read from the cache.
-    pub fn cache_func<O>(&self, func: &str) -> miette::Result<O>
+    pub fn cache_func<O>(&self, func: &str) -> Result<O, WarpgateError>
     where
         O: Debug + DeserializeOwned,
     {
@@ -60,7 +60,7 @@ impl<'plugin> PluginContainer<'plugin> {
 
     /// Call a function on the plugin with the given input and cache the output
     /// before returning it. Subsequent calls with the same input will read from the cache.
-    pub fn cache_func_with<I, O>(&self, func: &str, input: I) -> miette::Result<O>
+    pub fn cache_func_with<I, O>(&self, func: &str, input: I) -> Result<O, WarpgateError>
     where
         I: Debug + Serialize,
         O: Debug + DeserializeOwned,
@@ -85,7 +85,7 @@ impl<'plugin> PluginContainer<'plugin> {
     }
 
     /// Call a function on the plugin with no input and return the output.
-    pub fn call_func<O>(&self, func: &str) -> miette::Result<O>
+    pub fn call_func<O>(&self, func: &str) -> Result<O, WarpgateError>
     where
         O: Debug + DeserializeOwned,
     {
@@ -93,7 +93,7 @@ impl<'plugin> PluginContainer<'plugin> {
     }
 
     /// Call a function on the plugin with the given input and return the output.
-    pub fn call_func_with<I, O>(&self, func: &str, input: I) -> miette::Result<O>
+    pub fn call_func_with<I, O>(&self, func: &str, input: I) -> Result<O, WarpgateError>
     where
         I: Debug + Serialize,
         O: Debug + DeserializeOwned,
@@ -153,7 +153,7 @@ impl<'plugin> PluginContainer<'plugin> {
     }
 
     /// Call a function on the plugin with the given raw input and return the raw output.
-    pub fn call(&self, func: &str, input: impl AsRef<[u8]>) -> miette::Result<&[u8]> {
+    pub fn call(&self, func: &str, input: impl AsRef<[u8]>) -> Result<&[u8], WarpgateError> {
         let input = input.as_ref();
 
         let output = self
@@ -182,21 +182,21 @@ impl<'plugin> PluginContainer<'plugin> {
         Ok(output)
     }
 
-    fn format_input<I: Serialize>(&self, func: &str, input: I) -> miette::Result<String> {
-        Ok(
-            serde_json::to_string(&input).map_err(|error| WarpgateError::FormatInputFailed {
-                func: func.to_owned(),
-                error,
-            })?,
-        )
+    fn format_input<I: Serialize>(&self, func: &str, input: I) -> Result<String, WarpgateError> {
+        serde_json::to_string(&input).map_err(|error| WarpgateError::FormatInputFailed {
+            func: func.to_owned(),
+            error,
+        })
     }
 
-    fn parse_output<O: DeserializeOwned>(&self, func: &str, data: &[u8]) -> miette::Result<O> {
-        Ok(
-            serde_json::from_slice(data).map_err(|error| WarpgateError::ParseOutputFailed {
-                func: func.to_owned(),
-                error,
-            })?,
-        )
+    fn parse_output<O: DeserializeOwned>(
+        &self,
+        func: &str,
+        data: &[u8],
+    ) -> Result<O, WarpgateError> {
+        serde_json::from_slice(data).map_err(|error| WarpgateError::ParseOutputFailed {
+            func: func.to_owned(),
+            error,
+        })
     }
 }
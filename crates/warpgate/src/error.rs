@@ -8,6 +8,10 @@ pub enum WarpgateError {
     #[error("{0}")]
     Serde(String),
 
+    #[diagnostic(transparent)]
+    #[error(transparent)]
+    Fs(#[from] starbase_utils::fs::FsError),
+
     #[diagnostic(code(plugin::http))]
     #[error("Failed to make HTTP request.")]
     Http {
@@ -76,4 +80,20 @@ pub enum WarpgateError {
     #[diagnostic(code(plugin::download::failed))]
     #[error("Failed to download plugin from {} ({status}).", .url.style(Style::Url))]
     DownloadFailed { url: String, status: String },
+
+    #[diagnostic(code(plugin::download::invalid_url))]
+    #[error("Invalid plugin download URL {}.", .url.style(Style::Url))]
+    InvalidUrl {
+        url: String,
+        #[source]
+        error: url::ParseError,
+    },
+
+    #[diagnostic(code(plugin::download::unsupported_ext))]
+    #[error("Unsupported file extension {} for downloaded plugin.", .0.style(Style::File))]
+    UnsupportedDownloadExtension(String),
+
+    #[diagnostic(code(plugin::download::unknown_type))]
+    #[error("Unsure how to handle downloaded plugin {}, as no file extension could be derived.", .0.style(Style::Path))]
+    UnknownDownloadType(PathBuf),
 }
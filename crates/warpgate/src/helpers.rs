@@ -1,5 +1,4 @@
 use crate::error::WarpgateError;
-use miette::IntoDiagnostic;
 use reqwest::Url;
 use starbase_utils::fs::{self, FsError};
 use std::io;
@@ -35,8 +34,14 @@ pub fn create_wasm_file_stem(name: &str) -> String {
     name
 }
 
-pub async fn download_url_to_temp(raw_url: &str, temp_dir: &Path) -> miette::Result<PathBuf> {
-    let url = Url::parse(raw_url).into_diagnostic()?;
+pub async fn download_url_to_temp(
+    raw_url: &str,
+    temp_dir: &Path,
+) -> Result<PathBuf, WarpgateError> {
+    let url = Url::parse(raw_url).map_err(|error| WarpgateError::InvalidUrl {
+        url: raw_url.to_owned(),
+        error,
+    })?;
     let filename = url.path_segments().unwrap().last().unwrap().to_owned();
 
     // Fetch the file from the HTTP source
@@ -48,16 +53,14 @@ pub async fn download_url_to_temp(raw_url: &str, temp_dir: &Path) -> miette::Res
     if status.as_u16() == 404 {
         return Err(WarpgateError::DownloadNotFound {
             url: raw_url.to_owned(),
-        }
-        .into());
+        });
     }
 
     if !status.is_success() {
         return Err(WarpgateError::DownloadFailed {
             url: raw_url.to_owned(),
             status: status.to_string(),
-        }
-        .into());
+        });
     }
 
     // Write the bytes to our temporary file
@@ -79,7 +82,7 @@ pub async fn download_url_to_temp(raw_url: &str, temp_dir: &Path) -> miette::Res
     Ok(temp_path)
 }
 
-pub fn move_or_unpack_download(temp_path: &Path, dest_path: &Path) -> miette::Result<()> {
+pub fn move_or_unpack_download(temp_path: &Path, dest_path: &Path) -> Result<(), WarpgateError> {
     let ext = temp_path.extension().map(|e| e.to_str().unwrap());
 
     match ext {
@@ -93,17 +96,12 @@ pub fn move_or_unpack_download(temp_path: &Path, dest_path: &Path) -> miette::Re
             unimplemented!();
         }
 
-        Some(x) => {
-            return Err(miette::miette!(
-                "Unsupported file extension `{}` for downloaded plugin.",
-                x
-            ));
+        Some(ext) => {
+            return Err(WarpgateError::UnsupportedDownloadExtension(ext.to_owned()));
         }
 
         None => {
-            return Err(miette::miette!(
-                "Unsure how to handle downloaded plugin as no file extension/type could be derived."
-            ));
+            return Err(WarpgateError::UnknownDownloadType(temp_path.to_path_buf()));
         }
     };
 
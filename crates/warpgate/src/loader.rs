@@ -19,6 +19,9 @@ pub struct PluginLoader {
     /// Location where downloaded .wasm plugins are stored.
     plugins_dir: PathBuf,
 
+    /// Re-download plugins using a latest strategy, even if cached.
+    refresh: bool,
+
     /// Location where temporary files (like archives) are stored.
     temp_dir: PathBuf,
 }
@@ -32,17 +35,24 @@ impl PluginLoader {
 
         Self {
             plugins_dir: plugins_dir.to_owned(),
+            refresh: false,
             temp_dir: temp_dir.as_ref().to_owned(),
         }
     }
 
+    /// Force plugins that use a latest strategy to be re-downloaded,
+    /// instead of waiting for their cache to expire.
+    pub fn set_refresh(&mut self, refresh: bool) {
+        self.refresh = refresh;
+    }
+
     /// Load a plugin using the provided locator. File system plugins are loaded directly,
     /// while remote/URL plugins are downloaded and cached.
     pub async fn load_plugin<T: AsRef<str>, L: AsRef<PluginLocator>>(
         &self,
         id: T,
         locator: L,
-    ) -> miette::Result<PathBuf> {
+    ) -> Result<PathBuf, WarpgateError> {
         let id = id.as_ref();
         let locator = locator.as_ref();
 
@@ -68,7 +78,7 @@ impl PluginLoader {
 
                     Ok(path)
                 } else {
-                    Err(WarpgateError::SourceFileMissing(path).into())
+                    Err(WarpgateError::SourceFileMissing(path))
                 }
             }
             PluginLocator::SourceUrl { url } => {
@@ -101,7 +111,7 @@ impl PluginLoader {
     /// Check if the plugin has been downloaded and is cached.
     /// If using a latest strategy (no explicit version or tag), the cache
     /// is only valid for 7 days (to ensure not stale), otherwise forever.
-    pub fn is_cached(&self, id: &str, path: &Path) -> miette::Result<bool> {
+    pub fn is_cached(&self, id: &str, path: &Path) -> Result<bool, WarpgateError> {
         if !path.exists() {
             trace!(plugin = id, "Plugin not cached, downloading");
 
@@ -114,7 +124,9 @@ impl PluginLoader {
         if fs::file_name(path).contains("-latest-") {
             let metadata = fs::metadata(path)?;
 
-            cached = if let Ok(filetime) = metadata.created().or_else(|_| metadata.modified()) {
+            cached = if self.refresh {
+                false
+            } else if let Ok(filetime) = metadata.created().or_else(|_| metadata.modified()) {
                 filetime > SystemTime::now() - Duration::from_secs(86400 * 7)
             } else {
                 false
@@ -139,7 +151,7 @@ impl PluginLoader {
         id: &str,
         source_url: &str,
         dest_path: PathBuf,
-    ) -> miette::Result<PathBuf> {
+    ) -> Result<PathBuf, WarpgateError> {
         if self.is_cached(id, &dest_path)? {
             return Ok(dest_path);
         }
@@ -158,7 +170,7 @@ impl PluginLoader {
         &self,
         id: &str,
         github: &GitHubLocator,
-    ) -> miette::Result<PathBuf> {
+    ) -> Result<PathBuf, WarpgateError> {
         let (api_url, release_tag) = if let Some(tag) = &github.tag {
             (
                 format!(
@@ -251,15 +263,14 @@ impl PluginLoader {
         Err(WarpgateError::GitHubAssetMissing {
             repo_slug: github.repo_slug.to_owned(),
             tag: release_tag,
-        }
-        .into())
+        })
     }
 
     async fn download_plugin_from_wapm(
         &self,
         id: &str,
         wapm: &WapmLocator,
-    ) -> miette::Result<PathBuf> {
+    ) -> Result<PathBuf, WarpgateError> {
         let version = wapm.version.as_deref().unwrap_or("latest");
         let fake_api_url = format!(
             "https://registry.wapm.io/graphql/{}@{}",
@@ -353,7 +364,6 @@ impl PluginLoader {
         Err(WarpgateError::WapmModuleMissing {
             package: wapm.package_name.to_owned(),
             version: version.to_owned(),
-        }
-        .into())
+        })
     }
 }
@@ -1,5 +1,8 @@
 use crate::helpers::{create_wasm_file_stem, extract_suffix_from_slug};
 use crate::WarpgateError;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::path::PathBuf;
@@ -179,3 +182,28 @@ impl AsRef<PluginLocator> for PluginLocator {
         self
     }
 }
+
+// Locators are serialized as strings, so represent them as such.
+impl JsonSchema for PluginLocator {
+    fn schema_name() -> String {
+        "PluginLocator".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            metadata: Some(Box::new(Metadata {
+                description: Some(
+                    "Strategy for locating a plugin: source:path/to/file, source:https://url, github:owner/repo@tag, or wapm:package/name@version.".into(),
+                ),
+                ..Metadata::default()
+            })),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^(source|github|wapm):.+$".into()),
+                ..StringValidation::default()
+            })),
+            ..SchemaObject::default()
+        }
+        .into()
+    }
+}
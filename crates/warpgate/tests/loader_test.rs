@@ -1,6 +1,6 @@
 use starbase_sandbox::{create_empty_sandbox, locate_fixture, Sandbox};
 use std::path::PathBuf;
-use warpgate::{GitHubLocator, PluginLoader, PluginLocator};
+use warpgate::{GitHubLocator, PluginLoader, PluginLocator, WarpgateError};
 
 fn create_loader() -> (Sandbox, PluginLoader) {
     let sandbox = create_empty_sandbox();
@@ -16,11 +16,10 @@ mod loader {
         use super::*;
 
         #[tokio::test]
-        #[should_panic(expected = "Cannot load plugin, source file fake-file does not exist.")]
         async fn errors_missing_file() {
             let (_sandbox, loader) = create_loader();
 
-            loader
+            let error = loader
                 .load_plugin(
                     "test",
                     &PluginLocator::SourceFile {
@@ -29,7 +28,11 @@ mod loader {
                     },
                 )
                 .await
-                .unwrap();
+                .unwrap_err();
+
+            assert!(
+                matches!(error, WarpgateError::SourceFileMissing(path) if path == PathBuf::from("fake-file"))
+            );
         }
 
         #[tokio::test]
@@ -61,14 +64,15 @@ mod loader {
         use super::*;
 
         #[tokio::test]
-        #[should_panic(expected = "does not exist")]
         async fn errors_broken_url() {
             let (_sandbox, loader) = create_loader();
 
-            loader
+            let error = loader
                 .load_plugin("test", &PluginLocator::SourceUrl { url: "https://github.com/moonrepo/deno-plugin/releases/download/v0.0.2/deno_plugin_invalid_name.wasm".into() })
                 .await
-                .unwrap();
+                .unwrap_err();
+
+            assert!(matches!(error, WarpgateError::DownloadNotFound { .. }));
         }
 
         #[tokio::test]
@@ -100,11 +104,10 @@ mod loader {
         use super::*;
 
         #[tokio::test]
-        #[should_panic(expected = "Cannot download plugin from GitHub (moonrepo/invalid-repo)")]
         async fn errors_invalid_slug() {
             let (_sandbox, loader) = create_loader();
 
-            loader
+            let error = loader
                 .load_plugin(
                     "test",
                     &PluginLocator::GitHub(GitHubLocator {
@@ -114,7 +117,9 @@ mod loader {
                     }),
                 )
                 .await
-                .unwrap();
+                .unwrap_err();
+
+            assert!(matches!(error, WarpgateError::GitHubAssetMissing { .. }));
         }
 
         #[tokio::test]
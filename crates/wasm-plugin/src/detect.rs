@@ -2,20 +2,38 @@ use crate::WasmPlugin;
 use proto_core::{async_trait, load_version_file, Detector, ProtoError};
 use proto_pdk_api::{DetectVersionOutput, ParseVersionFileInput, ParseVersionFileOutput};
 use starbase_utils::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[async_trait]
 impl Detector<'_> for WasmPlugin {
     async fn detect_version_from(&self, working_dir: &Path) -> Result<Option<String>, ProtoError> {
+        Ok(self
+            .detect_version_with_source(working_dir)
+            .await?
+            .map(|(version, _)| version))
+    }
+
+    fn get_version_files(&self) -> Option<Vec<String>> {
+        if !self.container.has_func("detect_version_files") {
+            return Some(vec![]);
+        }
+
+        self.container
+            .cache_func::<DetectVersionOutput>("detect_version_files")
+            .ok()
+            .map(|result| result.files)
+    }
+
+    async fn detect_version_with_source(
+        &self,
+        working_dir: &Path,
+    ) -> Result<Option<(String, PathBuf)>, ProtoError> {
         if !self.container.has_func("detect_version_files") {
             return Ok(None);
         }
 
         let has_parser = self.container.has_func("parse_version_file");
-        let result: DetectVersionOutput = self
-            .container
-            .cache_func("detect_version_files")
-            .map_err(|e| ProtoError::Message(e.to_string()))?;
+        let result: DetectVersionOutput = self.cache_func("detect_version_files")?;
 
         for file in result.files {
             let file_path = working_dir.join(&file);
@@ -25,26 +43,23 @@ impl Detector<'_> for WasmPlugin {
             }
 
             if has_parser {
-                let result: ParseVersionFileOutput = self
-                    .container
-                    .call_func_with(
-                        "parse_version_file",
-                        ParseVersionFileInput {
-                            content: fs::read_file(&file_path)?,
-                            env: self.get_environment()?,
-                            file: file.clone(),
-                        },
-                    )
-                    .map_err(|e| ProtoError::Message(e.to_string()))?;
-
-                if result.version.is_none() {
+                let result: ParseVersionFileOutput = self.call_func_with(
+                    "parse_version_file",
+                    ParseVersionFileInput {
+                        content: fs::read_file(&file_path)?,
+                        env: self.get_environment()?,
+                        file: file.clone(),
+                    },
+                )?;
+
+                let Some(version) = result.version else {
                     continue;
-                }
+                };
 
-                return Ok(result.version);
+                return Ok(Some((version, file_path)));
             }
 
-            return Ok(Some(load_version_file(&file_path)?));
+            return Ok(Some((load_version_file(&file_path)?, file_path)));
         }
 
         Ok(None)
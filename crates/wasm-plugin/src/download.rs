@@ -1,6 +1,7 @@
 use crate::WasmPlugin;
 use proto_core::{async_trait, Downloadable, ProtoError, Resolvable};
 use std::path::PathBuf;
+use warpgate::WarpgateError;
 
 #[async_trait]
 impl Downloadable<'_> for WasmPlugin {
@@ -10,8 +11,14 @@ impl Downloadable<'_> for WasmPlugin {
         let name = match &params.download_name {
             Some(file) => file.to_owned(),
             None => {
-                let url = url::Url::parse(&params.download_url).map_err(|e| {
-                    ProtoError::Message(format!("Failed to parse download URL: {e}"))
+                let url = url::Url::parse(&params.download_url).map_err(|error| {
+                    self.create_call_error(
+                        "download_prebuilt",
+                        WarpgateError::InvalidUrl {
+                            url: params.download_url.clone(),
+                            error,
+                        },
+                    )
                 })?;
 
                 url.path_segments().unwrap().last().unwrap().to_owned()
@@ -11,16 +11,13 @@ impl Executable<'_> for WasmPlugin {
         let mut bin_path = None;
 
         if self.container.has_func("locate_bins") {
-            let execute_params: LocateBinsOutput = self
-                .container
-                .cache_func_with(
-                    "locate_bins",
-                    LocateBinsInput {
-                        env: self.get_environment()?,
-                        tool_dir: self.container.to_virtual_path(&install_dir),
-                    },
-                )
-                .map_err(|e| ProtoError::Message(e.to_string()))?;
+            let execute_params: LocateBinsOutput = self.cache_func_with(
+                "locate_bins",
+                LocateBinsInput {
+                    env: self.get_environment()?,
+                    tool_dir: self.container.to_virtual_path(&install_dir),
+                },
+            )?;
 
             if let Some(bin) = &execute_params.bin_path {
                 bin_path = Some(install_dir.join(bin));
@@ -66,16 +63,13 @@ impl Executable<'_> for WasmPlugin {
         let tool_dir = self.get_install_dir()?;
         let env_var_pattern = regex::Regex::new(r"\$([A-Z0-9_]+)").unwrap();
 
-        let params: LocateBinsOutput = self
-            .container
-            .cache_func_with(
-                "locate_bins",
-                LocateBinsInput {
-                    env: self.get_environment()?,
-                    tool_dir: self.container.to_virtual_path(&tool_dir),
-                },
-            )
-            .map_err(|e| ProtoError::Message(e.to_string()))?;
+        let params: LocateBinsOutput = self.cache_func_with(
+            "locate_bins",
+            LocateBinsInput {
+                env: self.get_environment()?,
+                tool_dir: self.container.to_virtual_path(&tool_dir),
+            },
+        )?;
 
         let lookup_count = params.globals_lookup_dirs.len() - 1;
 
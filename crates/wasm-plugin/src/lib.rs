@@ -10,25 +10,30 @@ mod verify;
 use extism::{manifest::Wasm, Manifest as PluginManifest};
 use host_funcs::HostData;
 use once_cell::sync::OnceCell;
-use proto_core::{impl_tool, Describable, Manifest, Proto, ProtoError, Resolvable, Tool};
+use proto_core::{
+    impl_tool, Describable, Manifest, PluginSource, Proto, ProtoError, Resolvable, Tool,
+};
 use proto_pdk_api::{
     DownloadPrebuiltInput, DownloadPrebuiltOutput, Environment, HostArch, HostOS,
     ToolMetadataInput, ToolMetadataOutput,
 };
 use rustc_hash::FxHashMap;
+use serde::{de::DeserializeOwned, Serialize};
 use std::{
     any::Any,
     env::{self, consts},
+    fmt::Debug,
     path::{Path, PathBuf},
     str::FromStr,
 };
-use warpgate::PluginContainer;
+use warpgate::{PluginContainer, WarpgateError};
 
 pub struct WasmPlugin {
     pub id: String,
     pub base_dir: PathBuf,
     pub bin_path: Option<PathBuf>,
     pub shim_path: Option<PathBuf>,
+    pub source: Option<PluginSource>,
     pub temp_dir: PathBuf,
     pub version: Option<String>,
 
@@ -64,9 +69,13 @@ impl WasmPlugin {
             base_dir: proto.tools_dir.join(&id),
             bin_path: None,
             container: PluginContainer::new(&id, manifest, host_funcs::create_functions(host_data))
-                .map_err(|e| ProtoError::Message(e.to_string()))?,
+                .map_err(|error| ProtoError::PluginWasmCreateFailed {
+                    id: id.clone(),
+                    error,
+                })?,
             manifest: OnceCell::new(),
             shim_path: None,
+            source: None,
             temp_dir: proto.temp_dir.join(&id),
             version: None,
             id,
@@ -79,11 +88,61 @@ impl WasmPlugin {
         Ok(wasm_plugin)
     }
 
+    /// Call a function on the plugin and cache the output before returning it.
+    pub fn cache_func<O>(&self, func: &str) -> Result<O, ProtoError>
+    where
+        O: Debug + DeserializeOwned,
+    {
+        self.container
+            .cache_func(func)
+            .map_err(|error| self.create_call_error(func, error))
+    }
+
+    /// Call a function on the plugin with the given input and cache the output before returning it.
+    pub fn cache_func_with<I, O>(&self, func: &str, input: I) -> Result<O, ProtoError>
+    where
+        I: Debug + Serialize,
+        O: Debug + DeserializeOwned,
+    {
+        self.container
+            .cache_func_with(func, input)
+            .map_err(|error| self.create_call_error(func, error))
+    }
+
+    /// Call a function on the plugin with the given input and return the output.
+    pub fn call_func_with<I, O>(&self, func: &str, input: I) -> Result<O, ProtoError>
+    where
+        I: Debug + Serialize,
+        O: Debug + DeserializeOwned,
+    {
+        self.container
+            .call_func_with(func, input)
+            .map_err(|error| self.create_call_error(func, error))
+    }
+
+    pub fn create_call_error(&self, func: &str, error: WarpgateError) -> ProtoError {
+        ProtoError::PluginWasmCallFailed {
+            id: self.id.clone(),
+            func: func.to_owned(),
+            error,
+        }
+    }
+
+    fn get_host_arch(&self) -> Result<HostArch, ProtoError> {
+        HostArch::from_str(consts::ARCH).map_err(|_| {
+            ProtoError::UnsupportedArchitecture(self.get_name(), consts::ARCH.to_owned())
+        })
+    }
+
+    fn get_host_os(&self) -> Result<HostOS, ProtoError> {
+        HostOS::from_str(consts::OS)
+            .map_err(|_| ProtoError::UnsupportedPlatform(self.get_name(), consts::OS.to_owned()))
+    }
+
     pub fn get_environment(&self) -> Result<Environment, ProtoError> {
         Ok(Environment {
-            arch: HostArch::from_str(consts::ARCH)
-                .map_err(|e| ProtoError::Message(e.to_string()))?,
-            os: HostOS::from_str(consts::OS).map_err(|e| ProtoError::Message(e.to_string()))?,
+            arch: self.get_host_arch()?,
+            os: self.get_host_os()?,
             vars: self
                 .get_metadata()?
                 .env_vars
@@ -95,32 +154,26 @@ impl WasmPlugin {
     }
 
     pub fn get_install_params(&self) -> Result<DownloadPrebuiltOutput, ProtoError> {
-        self.container
-            .cache_func_with(
-                "download_prebuilt",
-                DownloadPrebuiltInput {
-                    env: self.get_environment()?,
-                },
-            )
-            .map_err(|e| ProtoError::Message(e.to_string()))
+        self.cache_func_with(
+            "download_prebuilt",
+            DownloadPrebuiltInput {
+                env: self.get_environment()?,
+            },
+        )
     }
 
     pub fn get_metadata(&self) -> Result<ToolMetadataOutput, ProtoError> {
-        self.container
-            .cache_func_with(
-                "register_tool",
-                ToolMetadataInput {
-                    id: self.get_id().to_owned(),
-                    env: Environment {
-                        arch: HostArch::from_str(consts::ARCH)
-                            .map_err(|e| ProtoError::Message(e.to_string()))?,
-                        os: HostOS::from_str(consts::OS)
-                            .map_err(|e| ProtoError::Message(e.to_string()))?,
-                        ..Environment::default()
-                    },
+        self.cache_func_with(
+            "register_tool",
+            ToolMetadataInput {
+                id: self.get_id().to_owned(),
+                env: Environment {
+                    arch: self.get_host_arch()?,
+                    os: self.get_host_os()?,
+                    ..Environment::default()
                 },
-            )
-            .map_err(|e| ProtoError::Message(e.to_string()))
+            },
+        )
     }
 }
 
@@ -132,6 +185,10 @@ impl Describable<'_> for WasmPlugin {
     fn get_name(&self) -> String {
         self.get_metadata().unwrap().name
     }
+
+    fn get_plugin_source(&self) -> Option<&PluginSource> {
+        self.source.as_ref()
+    }
 }
 
 impl_tool!(WasmPlugin);
@@ -18,15 +18,12 @@ impl Resolvable<'_> for WasmPlugin {
     }
 
     async fn load_version_manifest(&self) -> Result<VersionManifest, ProtoError> {
-        let mut available: LoadVersionsOutput = self
-            .container
-            .cache_func_with(
-                "load_versions",
-                LoadVersionsInput {
-                    env: self.get_environment()?,
-                },
-            )
-            .map_err(|e| ProtoError::Message(e.to_string()))?;
+        let mut available: LoadVersionsOutput = self.cache_func_with(
+            "load_versions",
+            LoadVersionsInput {
+                env: self.get_environment()?,
+            },
+        )?;
 
         available.versions.sort_by(|a, d| d.cmp(a));
         available.canary_versions.sort_by(|a, d| d.cmp(a));
@@ -86,16 +83,13 @@ impl Resolvable<'_> for WasmPlugin {
         let mut version = "";
 
         if self.container.has_func("resolve_version") {
-            let resolved: ResolveVersionOutput = self
-                .container
-                .call_func_with(
-                    "resolve_version",
-                    ResolveVersionInput {
-                        initial: initial_version.to_owned(),
-                        env: self.get_environment()?,
-                    },
-                )
-                .map_err(|e| ProtoError::Message(e.to_string()))?;
+            let resolved: ResolveVersionOutput = self.call_func_with(
+                "resolve_version",
+                ResolveVersionInput {
+                    initial: initial_version.to_owned(),
+                    env: self.get_environment()?,
+                },
+            )?;
 
             if let Some(candidate) = resolved.candidate {
                 debug!(
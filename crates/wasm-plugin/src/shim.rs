@@ -13,15 +13,12 @@ impl Shimable<'_> for WasmPlugin {
         let mut created_primary = false;
 
         if self.container.has_func("create_shims") {
-            let shim_configs: CreateShimsOutput = self
-                .container
-                .cache_func_with(
-                    "create_shims",
-                    CreateShimsInput {
-                        env: self.get_environment()?,
-                    },
-                )
-                .map_err(|e| ProtoError::Message(e.to_string()))?;
+            let shim_configs: CreateShimsOutput = self.cache_func_with(
+                "create_shims",
+                CreateShimsInput {
+                    env: self.get_environment()?,
+                },
+            )?;
 
             if let Some(primary_config) = &shim_configs.primary {
                 let mut context = ShimContext::new_global(self.get_id());
@@ -41,18 +41,15 @@ impl Verifiable<'_> for WasmPlugin {
 
         // Allow plugin to provide their own checksum verification method
         if self.container.has_func("verify_checksum") {
-            let params: VerifyChecksumOutput = self
-                .container
-                .call_func_with(
-                    "verify_checksum",
-                    VerifyChecksumInput {
-                        checksum,
-                        checksum_file: self.container.to_virtual_path(checksum_file),
-                        download_file: self.container.to_virtual_path(download_file),
-                        env: self.get_environment()?,
-                    },
-                )
-                .map_err(|e| ProtoError::Message(e.to_string()))?;
+            let params: VerifyChecksumOutput = self.call_func_with(
+                "verify_checksum",
+                VerifyChecksumInput {
+                    checksum,
+                    checksum_file: self.container.to_virtual_path(checksum_file),
+                    download_file: self.container.to_virtual_path(download_file),
+                    env: self.get_environment()?,
+                },
+            )?;
 
             if params.verified {
                 return Ok(true);